@@ -57,10 +57,20 @@ pub fn AppInfoDisplay() -> Element {
             span { class: "text-base-content/70", "Executable: " }
             span { class: "break-all", "{exe_path}" }
           }
-          div {
+          div { class: "mb-1",
             span { class: "text-base-content/70", "Working Dir: " }
             span { class: "break-all", "{current_dir}" }
           }
+          div {
+            span { class: "text-base-content/70", "Data: " }
+            span { class: "text-base-content",
+              if crate::state::paths::is_portable() {
+                "portable, next to the executable"
+              } else {
+                "system directories"
+              }
+            }
+          }
         }
         // File System Status
         div {
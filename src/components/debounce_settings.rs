@@ -0,0 +1,189 @@
+use crate::components::ui::Toggler;
+use crate::libs::input_filter::{ chatter_report, clear_chatter_report };
+use crate::libs::key_codes::KeyClass;
+use crate::utils::config::use_config;
+use dioxus::prelude::*;
+use lucide_dioxus::RefreshCw;
+
+/// Parse a millisecond field, clamped to a sane range
+fn parse_ms(value: &str) -> Option<u32> {
+    value.trim().parse::<u32>().ok().map(|ms| ms.min(500))
+}
+
+#[component]
+pub fn DebounceSettings() -> Element {
+    let (config, update_config) = use_config();
+    let debounce = use_memo(move || config().debounce.clone());
+    let mut chatter = use_signal(chatter_report);
+
+    rsx! {
+      div { class: "space-y-4",
+        Toggler {
+          title: "Debounce key chatter".to_string(),
+          description: Some(
+              "Ignore a second press of the same key that arrives faster than a real keystroke could"
+                  .to_string(),
+          ),
+          checked: debounce().enabled,
+          on_change: {
+              let update_config = update_config.clone();
+              move |new_value: bool| {
+                  update_config(
+                      Box::new(move |config| {
+                          config.debounce.enabled = new_value;
+                      }),
+                  );
+              }
+          },
+        }
+
+        if debounce().enabled {
+          div { class: "bg-base-100 px-4 py-3 rounded-box space-y-2",
+            div { class: "flex items-center justify-between gap-2",
+              span { class: "text-xs font-medium", "Default (ms)" }
+              input {
+                r#type: "number",
+                min: "0",
+                max: "500",
+                class: "input input-xs w-20",
+                value: "{debounce().default_ms}",
+                onchange: {
+                    let update_config = update_config.clone();
+                    move |evt: Event<FormData>| {
+                        if let Some(ms) = parse_ms(&evt.value()) {
+                            update_config(
+                                Box::new(move |config| {
+                                    config.debounce.default_ms = ms;
+                                }),
+                            );
+                        }
+                    }
+                },
+              }
+            }
+            // Per-class overrides, empty = use the default
+            for class in KeyClass::ALL {
+              div {
+                key: "{class:?}",
+                class: "flex items-center justify-between gap-2",
+                span { class: "text-xs text-base-content/70", "{class.label()}" }
+                input {
+                  r#type: "number",
+                  min: "0",
+                  max: "500",
+                  class: "input input-xs w-20",
+                  placeholder: "{debounce().default_ms}",
+                  value: debounce().class_ms.get(&class).map(|ms| ms.to_string()).unwrap_or_default(),
+                  onchange: {
+                      let update_config = update_config.clone();
+                      move |evt: Event<FormData>| {
+                          let ms = parse_ms(&evt.value());
+                          update_config(
+                              Box::new(move |config| {
+                                  match ms {
+                                      Some(ms) => {
+                                          config.debounce.class_ms.insert(class, ms);
+                                      }
+                                      None => {
+                                          config.debounce.class_ms.remove(&class);
+                                      }
+                                  }
+                              }),
+                          );
+                      }
+                  },
+                }
+              }
+            }
+            // Per-key overrides
+            if !debounce().key_ms.is_empty() {
+              div { class: "divider my-1" }
+              for (code, ms) in debounce().key_ms.iter() {
+                div {
+                  key: "{code}",
+                  class: "flex items-center justify-between gap-2",
+                  span { class: "text-xs font-mono", "{code}" }
+                  div { class: "flex items-center gap-2",
+                    span { class: "text-xs", "{ms} ms" }
+                    button {
+                      class: "btn btn-ghost btn-xs",
+                      onclick: {
+                          let update_config = update_config.clone();
+                          let code = code.clone();
+                          move |_| {
+                              let code = code.clone();
+                              update_config(
+                                  Box::new(move |config| {
+                                      config.debounce.key_ms.remove(&code);
+                                  }),
+                              );
+                          }
+                      },
+                      "Remove"
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+
+        // Chatter report
+        div { class: "space-y-2",
+          div { class: "flex items-center gap-2 text-sm font-bold text-base-content/80",
+            span { "Chatter detected" }
+            button {
+              class: "btn btn-ghost btn-xs",
+              title: "Refresh",
+              onclick: move |_| chatter.set(chatter_report()),
+              RefreshCw { class: "w-3 h-3" }
+            }
+            if !chatter().is_empty() {
+              button {
+                class: "btn btn-ghost btn-xs",
+                onclick: move |_| {
+                    clear_chatter_report();
+                    chatter.set(Vec::new());
+                },
+                "Clear"
+              }
+            }
+          }
+          p { class: "text-xs text-base-content/60",
+            "Keys that fired again within {debounce().chatter_threshold_ms} ms of being released. Worn switches usually show up here."
+          }
+          if chatter().is_empty() {
+            div { class: "text-xs text-base-content/50", "No suspicious double-fires so far" }
+          } else {
+            for (code, count) in chatter().iter() {
+              div {
+                key: "{code}",
+                class: "flex items-center justify-between gap-2",
+                span { class: "text-xs font-mono", "{code}" }
+                div { class: "flex items-center gap-2",
+                  span { class: "badge badge-warning badge-sm", "{count}x" }
+                  button {
+                    class: "btn btn-soft btn-xs",
+                    onclick: {
+                        let update_config = update_config.clone();
+                        let code = code.clone();
+                        move |_| {
+                            let code = code.clone();
+                            update_config(
+                                Box::new(move |config| {
+                                    let ms = config.debounce.chatter_threshold_ms;
+                                    config.debounce.key_ms.insert(code, ms);
+                                }),
+                            );
+                        }
+                    },
+                    "Debounce this key"
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
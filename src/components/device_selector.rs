@@ -1,8 +1,25 @@
+use crate::libs::audio::AudioContext;
 use crate::libs::device_manager::{ DeviceInfo, DeviceManager };
 use crate::libs::input_device_manager::{ InputDeviceInfo, InputDeviceManager };
+use crate::state::app::use_app_state;
+use crate::state::config::AppConfig;
+use crate::state::soundpack::SoundpackType;
 use crate::utils::config::use_config;
 use dioxus::prelude::*;
 use lucide_dioxus::{ Headphones, Keyboard, Mouse, RefreshCw };
+use std::sync::Arc;
+
+// Per-device soundpacks need the evdev listener, which is only picked at startup
+fn per_device_needs_restart() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        !crate::libs::evdev_input_listener::is_input_listener_started()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
 
 #[derive(Clone, PartialEq, Copy)]
 pub enum DeviceType {
@@ -63,7 +80,7 @@ pub fn DeviceSelector(props: DeviceSelectorProps) -> Element {
                         }
                     }
                     DeviceType::Keyboard | DeviceType::Mouse => {
-                        let mut input_manager = InputDeviceManager::new();
+                        let mut input_manager = InputDeviceManager::from_config(&AppConfig::load());
                         match input_manager.enumerate_devices() {
                             Ok(_) => {
                                 let device_list = match device_type {
@@ -169,6 +186,53 @@ pub fn DeviceSelector(props: DeviceSelectorProps) -> Element {
         })
     };
 
+    // Soundpacks that can be assigned to a single input device
+    let audio_ctx: Arc<AudioContext> = use_context();
+    let app_state = use_app_state();
+    let device_soundpack_options = use_memo(move || {
+        let pack_type = match props.device_type {
+            DeviceType::Keyboard => SoundpackType::Keyboard,
+            DeviceType::Mouse => SoundpackType::Mouse,
+            DeviceType::AudioOutput => {
+                return Vec::new();
+            }
+        };
+        let mut packs: Vec<(String, String)> = app_state
+            .get_soundpacks()
+            .into_iter()
+            .filter(|pack| pack.soundpack_type == pack_type)
+            .map(|pack| (pack.folder_path, pack.name))
+            .collect();
+        packs.sort_by(|a, b| a.1.to_lowercase().cmp(&b.1.to_lowercase()));
+        packs
+    });
+
+    // Assign a soundpack to one input device (empty = use the global soundpack)
+    let assign_device_soundpack = {
+        let update_config = update_config.clone();
+        let audio_ctx = audio_ctx.clone();
+        let mut error_message = error_message.clone();
+
+        use_callback(move |(device_id, soundpack_id): (String, String)| {
+            update_config(
+                Box::new(move |config| {
+                    if soundpack_id.is_empty() {
+                        config.device_soundpacks.remove(&device_id);
+                    } else {
+                        config.device_soundpacks.insert(device_id, soundpack_id);
+                    }
+                })
+            );
+
+            let audio_ctx = audio_ctx.clone();
+            spawn(async move {
+                if let Err(e) = crate::libs::audio::load_device_soundpacks(&audio_ctx) {
+                    error_message.set(format!("Failed to load device soundpack: {}", e));
+                }
+            });
+        })
+    };
+
     // Get current device name for display
     let _current_device_name = use_memo(move || {
         let (selected_device, enabled_devices) = current_selection();
@@ -363,27 +427,55 @@ pub fn DeviceSelector(props: DeviceSelectorProps) -> Element {
                             div { class: "space-y-2",
                                 // Available input devices
                                 for device in input_devices().iter() {
-                                    label { 
-                                        key: "{device.id}",
-                                        class: "flex items-center gap-3 p-3 rounded-lg hover:bg-base-100 cursor-pointer transition-colors",
-                                        input {
-                                            r#type: "checkbox",
-                                            class: "checkbox checkbox-primary",
-                                            checked: current_selection().1.contains(&device.id),
-                                            onchange: {
-                                                let device_id = device.id.clone();
-                                                move |_| {
-                                                    handle_device_action.call(device_id.clone());
+                                    div { key: "{device.id}", class: "space-y-1",
+                                        label {
+                                            class: "flex items-center gap-3 p-3 rounded-lg hover:bg-base-100 cursor-pointer transition-colors",
+                                            input {
+                                                r#type: "checkbox",
+                                                class: "checkbox checkbox-primary",
+                                                checked: current_selection().1.contains(&device.id),
+                                                onchange: {
+                                                    let device_id = device.id.clone();
+                                                    move |_| {
+                                                        handle_device_action.call(device_id.clone());
+                                                    }
                                                 }
                                             }
+                                            div { class: "flex items-center gap-2 flex-1",
+                                                {device_icon()}
+                                                div { class: "flex-1 min-w-0",
+                                                    div { class: "text-sm font-medium truncate", "{device.name}" }
+                                                    div { class: "text-xs text-base-content/60", "{device.device_type:?}" }
+                                                }
+                                                div { class: "badge badge-success badge-sm", "Available" }
+                                            }
                                         }
-                                        div { class: "flex items-center gap-2 flex-1",
-                                            {device_icon()}
-                                            div { class: "flex-1 min-w-0",
-                                                div { class: "text-sm font-medium truncate", "{device.name}" }
-                                                div { class: "text-xs text-base-content/60", "{device.device_type:?}" }
+                                        // Per-device soundpack
+                                        div { class: "flex items-center gap-2 px-3",
+                                            span { class: "text-xs text-base-content/60", "Soundpack" }
+                                            select {
+                                                class: "select select-xs select-bordered flex-1 min-w-0",
+                                                onchange: {
+                                                    let device_id = device.id.clone();
+                                                    move |evt: Event<FormData>| {
+                                                        assign_device_soundpack.call((device_id.clone(), evt.value()));
+                                                    }
+                                                },
+                                                option { value: "", "Same as global" }
+                                                for (pack_id, pack_name) in device_soundpack_options().iter() {
+                                                    option {
+                                                        key: "{pack_id}",
+                                                        value: "{pack_id}",
+                                                        selected: config().device_soundpacks.get(&device.id) == Some(pack_id),
+                                                        "{pack_name}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        if config().device_soundpacks.contains_key(&device.id) && per_device_needs_restart() {
+                                            div { class: "text-xs text-base-content/60 px-3",
+                                                "Takes effect after restarting MechAura"
                                             }
-                                            div { class: "badge badge-success badge-sm", "Available" }
                                         }
                                     }
                                 }
@@ -426,7 +518,7 @@ pub fn KeyboardSelector() -> Element {
         DeviceSelector {
             device_type: DeviceType::Keyboard,
             label: "Keyboard Devices".to_string(),
-            description: Some("Select which keyboards should generate sound effects".to_string()),
+            description: Some("Select which keyboards should generate sound effects (none selected = all)".to_string()),
         }
     }
 }
@@ -437,7 +529,7 @@ pub fn MouseSelector() -> Element {
         DeviceSelector {
             device_type: DeviceType::Mouse,
             label: "Mouse Devices".to_string(),
-            description: Some("Select which mice should generate sound effects".to_string()),
+            description: Some("Select which mice should generate sound effects (none selected = all)".to_string()),
         }
     }
 }
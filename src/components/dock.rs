@@ -19,7 +19,10 @@ pub fn Dock() -> Element {
         }
         // Button Soundpacks
         button {
-          class: if matches!(route, crate::libs::routes::Route::Soundpacks {}) { "dock-active" } else { "" },
+          class: if matches!(
+              route,
+              crate::libs::routes::Route::Soundpacks {} | crate::libs::routes::Route::SoundpackEditor { .. }
+          ) { "dock-active" } else { "" },
           onclick: move |_| {
               nav.push("/soundpacks");
           },
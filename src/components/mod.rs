@@ -1,4 +1,5 @@
 pub mod app_info;
+pub mod debounce_settings;
 pub mod device_selector;
 pub mod dock;
 pub mod header;
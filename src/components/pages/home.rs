@@ -1,6 +1,14 @@
 use crate::components::logo::Logo;
-use crate::components::soundpack_selector::{ KeyboardSoundpackSelector, MouseSoundpackSelector };
-use crate::components::volume_slider::{ KeyboardVolumeSlider, MouseVolumeSlider };
+use crate::components::soundpack_selector::{
+    GamepadSoundpackSelector,
+    KeyboardSoundpackSelector,
+    MouseSoundpackSelector,
+};
+use crate::components::volume_slider::{
+    GamepadVolumeSlider,
+    KeyboardVolumeSlider,
+    MouseVolumeSlider,
+};
 use crate::libs::AudioContext;
 use crate::utils::config::use_config;
 use crate::utils::constants::APP_NAME_DISPLAY;
@@ -22,10 +30,28 @@ pub fn HomePage(audio_ctx: Arc<AudioContext>) -> Element {
     // Volume states from config
     let mut volume = use_signal(|| config().volume);
     let mut mouse_volume = use_signal(|| config().mouse_volume);
+    let mut gamepad_volume = use_signal(|| config().gamepad_volume);
+
+    // Follow volume changes saved elsewhere, e.g. by `mechaura ctl set_volume`
+    let saved_volume = use_memo(move || config().volume);
+    let saved_mouse_volume = use_memo(move || config().mouse_volume);
+    use_effect(move || {
+        let saved = saved_volume();
+        if *volume.peek() != saved {
+            volume.set(saved);
+        }
+    });
+    use_effect(move || {
+        let saved = saved_mouse_volume();
+        if *mouse_volume.peek() != saved {
+            mouse_volume.set(saved);
+        }
+    });
 
     // Use atomic counters to track save tasks and cancel old ones
     let save_counter = use_signal(|| Arc::new(AtomicU64::new(0)));
     let mouse_save_counter = use_signal(|| Arc::new(AtomicU64::new(0)));
+    let gamepad_save_counter = use_signal(|| Arc::new(AtomicU64::new(0)));
 
     // Update audio system volume when the volume control changes (enable_sound is handled by sound_manager)
     let ctx = audio_ctx.clone();
@@ -39,6 +65,12 @@ pub fn HomePage(audio_ctx: Arc<AudioContext>) -> Element {
         ctx.set_mouse_volume(mouse_volume());
     });
 
+    // Update audio system gamepad volume when the gamepad volume control changes
+    let ctx = audio_ctx.clone();
+    use_effect(move || {
+        ctx.set_gamepad_volume(gamepad_volume());
+    });
+
     // Debounce effect for saving keyboard volume config changes
     {
         let update_config = update_config.clone();
@@ -99,6 +131,29 @@ pub fn HomePage(audio_ctx: Arc<AudioContext>) -> Element {
         });
     }
 
+    // Debounce effect for saving gamepad volume config changes
+    {
+        let update_config = update_config.clone();
+        use_effect(move || {
+            let current_gamepad_volume = gamepad_volume();
+            let current_task_id = gamepad_save_counter().fetch_add(1, Ordering::SeqCst) + 1;
+
+            let update_config = update_config.clone();
+            let gamepad_save_counter_clone = gamepad_save_counter();
+
+            spawn(async move {
+                Delay::new(Duration::from_millis(500)).await;
+                if gamepad_save_counter_clone.load(Ordering::SeqCst) == current_task_id {
+                    update_config(
+                        Box::new(move |config| {
+                            config.gamepad_volume = current_gamepad_volume;
+                        })
+                    );
+                }
+            });
+        });
+    }
+
     rsx! {
       div { class: "flex flex-col gap-10 px-3 pb-0",
         div { class: "mb-2 mt-4",
@@ -127,6 +182,19 @@ pub fn HomePage(audio_ctx: Arc<AudioContext>) -> Element {
               },
             }
           }
+          // Gamepads are captured through evdev, so this section is Linux only
+          if cfg!(target_os = "linux") {
+            div { class: "divider m-0" }
+            div { class: "{crate::utils::spacing::SECTION_SPACING}",
+              GamepadSoundpackSelector {}
+              GamepadVolumeSlider {
+                volume: gamepad_volume,
+                on_change: move |new_gamepad_volume: f32| {
+                    gamepad_volume.set(new_gamepad_volume);
+                },
+              }
+            }
+          }
           // div { class: "divider m-0" }
           div { class: "text-center space-y-2 mt-8",
             // Version
@@ -2,10 +2,12 @@ pub mod customize;
 pub mod mood;
 pub mod home;
 pub mod settings;
+pub mod soundpack_editor;
 pub mod soundpacks;
 
 pub use customize::CustomizePage;
 pub use mood::MoodPage;
 pub use home::HomePage;
 pub use settings::SettingsPage;
+pub use soundpack_editor::SoundpackEditorPage;
 pub use soundpacks::Soundpacks;
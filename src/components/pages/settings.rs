@@ -1,5 +1,6 @@
 use crate::components::ui::{ Collapse, PageHeader, Toggler };
-use crate::components::device_selector::AudioOutputSelector;
+use crate::components::debounce_settings::DebounceSettings;
+use crate::components::device_selector::{ AudioOutputSelector, KeyboardSelector, MouseSelector };
 use crate::libs::theme::{ use_theme, BuiltInTheme, Theme };
 use crate::libs::tray_service::request_tray_update;
 use crate::utils::config::use_config;
@@ -106,8 +107,8 @@ pub fn SettingsPage() -> Element {
                 }
                 // Auto Start
                 Toggler {
-                  title: "Start with Windows".to_string(),
-                  description: Some(format!("Automatically start {} when Windows boots", APP_NAME)),
+                  title: "Start with system".to_string(),
+                  description: Some(format!("Automatically start {} when you log in", APP_NAME)),
                   checked: auto_start(),
                   on_change: {
                       let update_config = update_config.clone();
@@ -135,7 +136,7 @@ pub fn SettingsPage() -> Element {
                 if auto_start() {
                   Toggler {
                     title: "Start minimized to tray".to_string(),
-                    description: Some("When starting with Windows, open minimized to system tray".to_string()),
+                    description: Some("When starting with the system, open minimized to system tray".to_string()),
                     checked: start_minimized(),
                     on_change: {
                         let update_config = update_config.clone();
@@ -178,6 +179,11 @@ pub fn SettingsPage() -> Element {
               div { class: "space-y-2",
                 // Audio Output Device
                 AudioOutputSelector {}
+                // Input devices (per-device filtering is only available through evdev on Linux)
+                if cfg!(target_os = "linux") {
+                  KeyboardSelector {}
+                  MouseSelector {}
+                }
                 // Device Information
                 div { class: "text-xs mt-2 text-base-content font-bold",
                   "* Restart the application for changes to take effect"
@@ -185,6 +191,15 @@ pub fn SettingsPage() -> Element {
               }
             },
           }
+          // Typing Section
+          Collapse {
+            title: "Typing".to_string(),
+            group_name: "setting-accordion".to_string(),
+            content_class: "collapse-content text-sm",
+            children: rsx! {
+              DebounceSettings {}
+            },
+          }
           // Auto-Update Section
           Collapse {
             title: "Updates".to_string(),
@@ -0,0 +1,762 @@
+use crate::components::ui::{ PageHeader, Toggler };
+use crate::libs::audio::AudioContext;
+use crate::libs::key_codes::ANSI_LAYOUT;
+use crate::libs::routes::Route;
+use crate::state::app::use_state_trigger;
+use crate::state::paths;
+use crate::state::soundpack::{ SoundPack, SoundpackOptions, SoundpackType };
+use crate::utils::config_converter::load_audio_file_samples;
+use crate::utils::key_segmentation::mix_to_mono;
+use crate::utils::segment_edges::{ snap_timing, SegmentFades };
+use dioxus::document::eval;
+use dioxus::prelude::*;
+use lucide_dioxus::{
+    ArrowLeft,
+    ArrowLeftRight,
+    AudioWaveform,
+    Play,
+    Save,
+    Trash,
+    TriangleAlert,
+    ZoomIn,
+    ZoomOut,
+};
+use rodio::buffer::SamplesBuffer;
+use rodio::Sink;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// The waveform is drawn in a fixed coordinate space and stretched to the card width
+const WAVE_WIDTH: f32 = 1000.0;
+const WAVE_HEIGHT: f32 = 120.0;
+
+const DISCARD_MODAL_ID: &str = "editor_discard_modal";
+const WAVE_COLUMNS: usize = 500;
+const MIN_SEGMENT_MS: f32 = 1.0;
+const MIN_VIEW_MS: f32 = 50.0;
+
+/// Decoded audio of the pack being edited
+struct EditorAudio {
+    samples: Vec<f32>,
+    channels: u16,
+    sample_rate: u32,
+    mono: Vec<f32>,
+    duration_ms: f32,
+}
+
+/// Move one edge of a segment, it stays inside the audio and at least MIN_SEGMENT_MS long
+fn set_segment_edge(timing: &mut [f32; 2], edge: usize, ms: f32, duration_ms: f32) {
+    if edge == 0 {
+        timing[0] = ms.min(timing[1] - MIN_SEGMENT_MS).max(0.0);
+    } else {
+        timing[1] = ms.min(duration_ms).max(timing[0] + MIN_SEGMENT_MS);
+    }
+}
+
+/// Load the pack config and decode its audio file
+fn load_editor_data(soundpack_id: &str) -> Result<(SoundPack, EditorAudio), String> {
+    let config_path = paths::soundpacks::config_json(soundpack_id);
+    let content = std::fs
+        ::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path, e))?;
+    let soundpack: SoundPack = serde_json
+        ::from_str(&content)
+        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+
+    if soundpack.definition_method != "single" {
+        return Err(
+            format!(
+                "Only packs with one audio file can be edited here, this pack uses the '{}' method",
+                soundpack.definition_method
+            )
+        );
+    }
+
+    let audio_file = soundpack.audio_file
+        .as_ref()
+        .ok_or_else(|| "No audio_file field in soundpack config".to_string())?;
+    let audio_path = std::path::Path
+        ::new(&paths::soundpacks::soundpack_dir(soundpack_id))
+        .join(audio_file.trim_start_matches("./"));
+    let (samples, channels, sample_rate) = load_audio_file_samples(
+        &audio_path.to_string_lossy()
+    ).map_err(|e| format!("Failed to decode {}: {}", audio_path.display(), e))?;
+
+    let mono = mix_to_mono(&samples, channels);
+    let duration_ms = (mono.len() as f32) * 1000.0 / (sample_rate as f32);
+    Ok((soundpack, EditorAudio { samples, channels, sample_rate, mono, duration_ms }))
+}
+
+/// Write the edited pack back to its config.json
+fn save_soundpack(soundpack_id: &str, soundpack: &SoundPack) -> Result<(), String> {
+    let mut soundpack = soundpack.clone();
+    // Dragging produces long fractions, a tenth of a millisecond is plenty
+    for definition in soundpack.definitions.values_mut() {
+        for timing in definition.timing.iter_mut() {
+            timing[0] = (timing[0] * 10.0).round() / 10.0;
+            timing[1] = (timing[1] * 10.0).round() / 10.0;
+        }
+    }
+
+    let json = serde_json
+        ::to_string_pretty(&soundpack)
+        .map_err(|e| format!("Failed to serialize soundpack: {}", e))?;
+    let config_path = paths::soundpacks::config_json(soundpack_id);
+    std::fs::write(&config_path, json).map_err(|e| format!("Failed to write {}: {}", config_path, e))
+}
+
+/// Min/max envelope of the visible part of the recording as an SVG path
+fn waveform_path(audio: &EditorAudio, view_start_ms: f32, view_span_ms: f32) -> String {
+    let samples_per_ms = (audio.sample_rate as f32) / 1000.0;
+    let middle = WAVE_HEIGHT / 2.0;
+    let mut path = String::new();
+
+    for column in 0..WAVE_COLUMNS {
+        let column_start = view_start_ms + (view_span_ms * (column as f32)) / (WAVE_COLUMNS as f32);
+        let column_end = column_start + view_span_ms / (WAVE_COLUMNS as f32);
+        let first = ((column_start * samples_per_ms) as usize).min(audio.mono.len());
+        let last = ((column_end * samples_per_ms).ceil() as usize).min(audio.mono.len());
+        if first >= last {
+            continue;
+        }
+
+        let (low, high) = audio.mono[first..last]
+            .iter()
+            .fold((0.0f32, 0.0f32), |(low, high), &s| (low.min(s), high.max(s)));
+        let x = ((column as f32) + 0.5) * (WAVE_WIDTH / (WAVE_COLUMNS as f32));
+        path.push_str(
+            &format!(
+                "M{:.1} {:.1}V{:.1}",
+                x,
+                middle - high.clamp(-1.0, 1.0) * middle,
+                middle - low.clamp(-1.0, 1.0) * middle + 0.5
+            )
+        );
+    }
+    path
+}
+
+/// Play part of the recording through the keyboard output, with the pack's edge handling
+fn play_segment(
+    audio_ctx: &AudioContext,
+    audio: &EditorAudio,
+    options: &SoundpackOptions,
+    start_ms: f32,
+    end_ms: f32
+) {
+    let (start_ms, end_ms) = if options.snap_to_zero_crossings {
+        let (start, end) = snap_timing(&audio.mono, audio.sample_rate, (start_ms as f64, end_ms as f64));
+        (start as f32, end as f32)
+    } else {
+        (start_ms, end_ms)
+    };
+    let channels = audio.channels.max(1) as usize;
+    let frame_at = |ms: f32| ((ms.max(0.0) / 1000.0) * (audio.sample_rate as f32)) as usize;
+    let first = (frame_at(start_ms) * channels).min(audio.samples.len());
+    let last = (frame_at(end_ms) * channels).min(audio.samples.len());
+    if first >= last {
+        return;
+    }
+
+    let mut samples = audio.samples[first..last].to_vec();
+    SegmentFades::from_options(options).apply(&mut samples, audio.channels, audio.sample_rate);
+    let segment = SamplesBuffer::new(audio.channels, audio.sample_rate, samples);
+    if let Ok(sink) = Sink::try_new(&audio_ctx.stream_handle) {
+        sink.set_volume(audio_ctx.get_volume());
+        sink.append(segment);
+        sink.detach();
+    }
+}
+
+/// Short label for a key code on the visual keyboard
+fn key_label(code: &str) -> String {
+    let label = match code {
+        "Escape" => "Esc",
+        "Backquote" => "`",
+        "Minus" => "-",
+        "Equal" => "=",
+        "Backspace" => "⌫",
+        "BracketLeft" => "[",
+        "BracketRight" => "]",
+        "Backslash" => "\\",
+        "Semicolon" => ";",
+        "Quote" => "'",
+        "Comma" => ",",
+        "Period" => ".",
+        "Slash" => "/",
+        "CapsLock" => "Caps",
+        "ShiftLeft" | "ShiftRight" => "Shift",
+        "ControlLeft" | "ControlRight" => "Ctrl",
+        "AltLeft" | "AltRight" => "Alt",
+        "MetaLeft" | "MetaRight" => "Meta",
+        "ContextMenu" => "Menu",
+        "PrintScreen" => "PrtSc",
+        "ScrollLock" => "ScrLk",
+        "PageUp" => "PgUp",
+        "PageDown" => "PgDn",
+        "Insert" => "Ins",
+        "Delete" => "Del",
+        "NumLock" => "Num",
+        "ArrowUp" => "↑",
+        "ArrowDown" => "↓",
+        "ArrowLeft" => "←",
+        "ArrowRight" => "→",
+        "NumpadDivide" => "/",
+        "NumpadMultiply" => "*",
+        "NumpadSubtract" => "-",
+        "NumpadAdd" => "+",
+        "NumpadDecimal" => ".",
+        "NumpadEnter" => "⏎",
+        other => {
+            return other
+                .strip_prefix("Key")
+                .or_else(|| other.strip_prefix("Digit"))
+                .or_else(|| other.strip_prefix("Numpad"))
+                .unwrap_or(other)
+                .to_string();
+        }
+    };
+    label.to_string()
+}
+
+/// Visible window around all segments of a key
+fn view_for_key(soundpack: &SoundPack, key: &str, duration_ms: f32) -> Option<(f32, f32)> {
+    let timing = &soundpack.definitions.get(key)?.timing;
+    if timing.is_empty() {
+        return None;
+    }
+    let start = timing.iter().map(|t| t[0]).fold(f32::MAX, f32::min);
+    let end = timing.iter().map(|t| t[1]).fold(f32::MIN, f32::max);
+
+    let span = (end - start + 80.0).max(150.0).min(duration_ms.max(MIN_VIEW_MS));
+    let view_start = (start - (span - (end - start)) / 2.0).clamp(0.0, (duration_ms - span).max(0.0));
+    Some((view_start, span))
+}
+
+#[component]
+pub fn SoundpackEditorPage(soundpack_id: String) -> Element {
+    let nav = navigator();
+    let audio_ctx: Arc<AudioContext> = use_context();
+    let state_trigger = use_state_trigger();
+
+    let mut soundpack = use_signal(|| None::<SoundPack>);
+    let mut audio = use_signal(|| None::<Arc<EditorAudio>>);
+    let mut load_error = use_signal(|| None::<String>);
+    let mut selected_key = use_signal(|| None::<String>);
+    let mut swap_source = use_signal(|| None::<String>);
+    let mut view = use_signal(|| (0.0f32, 1000.0f32)); // (start_ms, span_ms)
+    let mut dragging = use_signal(|| None::<(usize, usize)>); // (timing index, edge)
+    let mut wave_element = use_signal(|| None::<Rc<MountedData>>);
+    let mut wave_bounds = use_signal(|| (0.0f64, 0.0f64)); // (left, width) in pixels
+    let mut dirty = use_signal(|| false);
+    let mut saving = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    // Decoding a long recording takes a moment, keep it off the UI thread
+    let load_id = soundpack_id.clone();
+    use_future(move || {
+        let load_id = load_id.clone();
+        async move {
+            let result = tokio::task
+                ::spawn_blocking(move || load_editor_data(&load_id)).await
+                .unwrap_or_else(|e| Err(format!("Failed to load soundpack: {}", e)));
+            match result {
+                Ok((pack, decoded)) => {
+                    let mut keys: Vec<&String> = pack.definitions.keys().collect();
+                    keys.sort();
+                    if let Some(first) = keys.first() {
+                        if let Some(first_view) = view_for_key(&pack, first, decoded.duration_ms) {
+                            view.set(first_view);
+                        }
+                        selected_key.set(Some((*first).clone()));
+                    } else {
+                        view.set((0.0, decoded.duration_ms.max(MIN_VIEW_MS)));
+                    }
+                    soundpack.set(Some(pack));
+                    audio.set(Some(Arc::new(decoded)));
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to open soundpack editor: {}", e);
+                    load_error.set(Some(e));
+                }
+            }
+        }
+    });
+
+    // Only recomputed when the visible window changes, not while dragging
+    let wave_path = use_memo(move || {
+        let (view_start, view_span) = view();
+        audio()
+            .map(|decoded| waveform_path(&decoded, view_start, view_span))
+            .unwrap_or_default()
+    });
+
+    let refresh_bounds = move || {
+        if let Some(element) = wave_element() {
+            spawn(async move {
+                if let Ok(rect) = element.get_client_rect().await {
+                    wave_bounds.set((rect.origin.x, rect.size.width));
+                }
+            });
+        }
+    };
+
+    let mut drag_to = move |client_x: f64| {
+        let Some((index, edge)) = dragging() else {
+            return;
+        };
+        let Some(key) = selected_key() else {
+            return;
+        };
+        let (left, width) = wave_bounds();
+        if width <= 0.0 {
+            return;
+        }
+        let (view_start, view_span) = view();
+        let duration_ms = audio().map_or(0.0, |decoded| decoded.duration_ms);
+        let ms = view_start + (((client_x - left) / width) as f32) * view_span;
+
+        let mut pack = soundpack.write();
+        let timing = pack
+            .as_mut()
+            .and_then(|pack| pack.definitions.get_mut(&key))
+            .and_then(|definition| definition.timing.get_mut(index));
+        if let Some(timing) = timing {
+            set_segment_edge(timing, edge, ms, duration_ms);
+            dirty.set(true);
+        }
+    };
+
+    let mut select_key = move |key: String| {
+        if let Some(source) = swap_source() {
+            swap_source.set(None);
+            if source != key {
+                // Move the source segments to the clicked key, and its old segments back
+                if let Some(pack) = soundpack.write().as_mut() {
+                    let moved = pack.definitions.remove(&source);
+                    if let Some(previous) = pack.definitions.remove(&key) {
+                        pack.definitions.insert(source.clone(), previous);
+                    }
+                    if let Some(moved) = moved {
+                        pack.definitions.insert(key.clone(), moved);
+                    }
+                }
+                dirty.set(true);
+            }
+        }
+
+        let duration_ms = audio().map_or(0.0, |decoded| decoded.duration_ms);
+        if let Some(key_view) = soundpack().and_then(|pack| view_for_key(&pack, &key, duration_ms)) {
+            view.set(key_view);
+        }
+        selected_key.set(Some(key));
+    };
+
+    let mut zoom = move |factor: f32| {
+        let duration_ms = audio().map_or(0.0, |decoded| decoded.duration_ms).max(MIN_VIEW_MS);
+        let (view_start, view_span) = view();
+        let center = view_start + view_span / 2.0;
+        let span = (view_span * factor).clamp(MIN_VIEW_MS, duration_ms);
+        view.set(((center - span / 2.0).clamp(0.0, duration_ms - span), span));
+    };
+
+    let on_save = {
+        let soundpack_id = soundpack_id.clone();
+        let audio_ctx = audio_ctx.clone();
+        move |_| {
+            let Some(pack) = soundpack() else {
+                return;
+            };
+            let soundpack_id = soundpack_id.clone();
+            let audio_ctx = audio_ctx.clone();
+            let state_trigger = state_trigger.clone();
+            spawn(async move {
+                saving.set(true);
+                match save_soundpack(&soundpack_id, &pack) {
+                    Ok(_) => {
+                        println!("💾 Saved soundpack timings: {}", soundpack_id);
+                        // Pick up the new timings if this pack is currently selected
+                        crate::state::app::reload_current_soundpacks(&audio_ctx);
+                        state_trigger.call(());
+                        dirty.set(false);
+                        status.set(Some(Ok("Changes saved".to_string())));
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Failed to save soundpack {}: {}", soundpack_id, e);
+                        status.set(Some(Err(e)));
+                    }
+                }
+                saving.set(false);
+            });
+        }
+    };
+
+    let pack_name = soundpack()
+        .map(|pack| pack.name)
+        .unwrap_or_else(|| soundpack_id.clone());
+
+    rsx! {
+      div { class: "space-y-4",
+        PageHeader {
+          title: "Edit sound pack".to_string(),
+          subtitle: pack_name,
+          icon: Some(rsx! {
+            AudioWaveform { class: "w-8 h-8 mx-auto" }
+          }),
+        }
+
+        div { class: "flex items-center gap-2",
+          button {
+            class: "btn btn-sm btn-ghost",
+            onclick: move |_| {
+                if dirty() {
+                    eval(&format!("document.getElementById(\"{}\").showModal()", DISCARD_MODAL_ID));
+                } else {
+                    nav.push(Route::Soundpacks {});
+                }
+            },
+            ArrowLeft { class: "w-4 h-4 mr-1" }
+            "Back"
+          }
+          div { class: "flex-1 text-xs text-right",
+            match status() {
+                Some(Ok(message)) => rsx! {
+                  span { class: "text-success", "{message}" }
+                },
+                Some(Err(message)) => rsx! {
+                  span { class: "text-error", "{message}" }
+                },
+                None if dirty() => rsx! {
+                  span { class: "text-base-content/50", "Unsaved changes" }
+                },
+                None => rsx! {},
+            }
+          }
+          button {
+            class: "btn btn-sm btn-primary",
+            disabled: !dirty() || saving(),
+            onclick: on_save,
+            if saving() {
+              span { class: "loading loading-spinner loading-xs" }
+            } else {
+              Save { class: "w-4 h-4 mr-1" }
+            }
+            "Save"
+          }
+        }
+
+        if let Some(error) = load_error() {
+          div { class: "alert alert-error text-sm", "{error}" }
+        } else if let (Some(pack), Some(decoded)) = (soundpack(), audio()) {
+          // Waveform with the segments of the selected key
+          div { class: "bg-base-200 rounded-box p-3 space-y-2",
+            div { class: "flex items-center gap-2 text-xs text-base-content/60",
+              span { class: "flex-1",
+                "{view().0:.0} – {view().0 + view().1:.0} ms of {decoded.duration_ms:.0} ms"
+              }
+              button {
+                class: "btn btn-xs btn-ghost",
+                title: "Zoom in",
+                onclick: move |_| zoom(0.5),
+                ZoomIn { class: "w-4 h-4" }
+              }
+              button {
+                class: "btn btn-xs btn-ghost",
+                title: "Zoom out",
+                onclick: move |_| zoom(2.0),
+                ZoomOut { class: "w-4 h-4" }
+              }
+            }
+            svg {
+              class: "w-full h-32 bg-base-300 rounded-box select-none",
+              view_box: "0 0 {WAVE_WIDTH} {WAVE_HEIGHT}",
+              preserve_aspect_ratio: "none",
+              onmounted: move |evt: MountedEvent| {
+                  wave_element.set(Some(evt.data()));
+                  refresh_bounds();
+              },
+              onmousemove: move |evt| drag_to(evt.client_coordinates().x),
+              onmouseup: move |_| dragging.set(None),
+              onmouseleave: move |_| dragging.set(None),
+              // Other keys, so overlapping segments are easy to spot
+              for (key, definition) in pack.definitions.iter().filter(|(key, _)| Some((*key).clone()) != selected_key()) {
+                for (index, timing) in definition.timing.iter().enumerate() {
+                  rect {
+                    key: "{key}-{index}",
+                    x: "{(timing[0] - view().0) / view().1 * WAVE_WIDTH}",
+                    y: "0",
+                    width: "{((timing[1] - timing[0]) / view().1 * WAVE_WIDTH).max(0.0)}",
+                    height: "{WAVE_HEIGHT}",
+                    class: "fill-base-content",
+                    fill_opacity: "0.06",
+                  }
+                }
+              }
+              path {
+                d: "{wave_path}",
+                class: "stroke-base-content/70",
+                stroke_width: "1",
+                vector_effect: "non-scaling-stroke",
+                fill: "none",
+              }
+              if let Some(definition) = selected_key().and_then(|key| pack.definitions.get(&key).cloned()) {
+                for (index, timing) in definition.timing.iter().copied().enumerate() {
+                  rect {
+                    x: "{(timing[0] - view().0) / view().1 * WAVE_WIDTH}",
+                    y: "0",
+                    width: "{((timing[1] - timing[0]) / view().1 * WAVE_WIDTH).max(0.0)}",
+                    height: "{WAVE_HEIGHT}",
+                    class: if index == 0 { "fill-primary" } else { "fill-secondary" },
+                    fill_opacity: "0.25",
+                  }
+                  for edge in 0..2 {
+                    rect {
+                      x: "{(timing[edge] - view().0) / view().1 * WAVE_WIDTH - 4.0}",
+                      y: "0",
+                      width: "8",
+                      height: "{WAVE_HEIGHT}",
+                      class: if index == 0 { "fill-primary cursor-ew-resize" } else { "fill-secondary cursor-ew-resize" },
+                      fill_opacity: if dragging() == Some((index, edge)) { "0.9" } else { "0.5" },
+                      onmousedown: move |evt| {
+                          evt.prevent_default();
+                          refresh_bounds();
+                          dragging.set(Some((index, edge)));
+                      },
+                    }
+                  }
+                }
+              }
+            }
+            // Scroll through the recording
+            input {
+              r#type: "range",
+              class: "range range-xs w-full",
+              min: "0",
+              max: "{(decoded.duration_ms - view().1).max(0.0)}",
+              step: "1",
+              value: "{view().0}",
+              oninput: move |evt| {
+                  if let Ok(start) = evt.value().parse::<f32>() {
+                      let (_, span) = view();
+                      view.set((start, span));
+                  }
+              },
+            }
+          }
+
+          // Click-free playback
+          div { class: "bg-base-200 rounded-box p-3",
+            Toggler {
+              title: "Micro-fades".to_string(),
+              description: Some(
+                  format!(
+                      "Fade each segment in over {} ms and out over {} ms so cuts don't tick",
+                      pack.options.fade_in_ms,
+                      pack.options.fade_out_ms,
+                  ),
+              ),
+              checked: pack.options.micro_fades,
+              on_change: move |enabled: bool| {
+                  if let Some(pack) = soundpack.write().as_mut() {
+                      pack.options.micro_fades = enabled;
+                  }
+                  dirty.set(true);
+              },
+            }
+            Toggler {
+              title: "Snap edges to zero crossings".to_string(),
+              description: Some("Moves segment edges by up to 1 ms when the pack loads".to_string()),
+              checked: pack.options.snap_to_zero_crossings,
+              on_change: move |enabled: bool| {
+                  if let Some(pack) = soundpack.write().as_mut() {
+                      pack.options.snap_to_zero_crossings = enabled;
+                  }
+                  dirty.set(true);
+              },
+            }
+          }
+
+          // Timings of the selected key
+          if let Some(key) = selected_key() {
+            div { class: "bg-base-200 rounded-box p-3 space-y-2",
+              div { class: "flex items-center gap-2",
+                span { class: "font-medium text-sm flex-1", "{key}" }
+                button {
+                  class: if swap_source() == Some(key.clone()) { "btn btn-xs btn-accent" } else { "btn btn-xs btn-soft" },
+                  title: "Pick another key to swap segments with",
+                  onclick: {
+                      let key = key.clone();
+                      move |_| {
+                          if swap_source() == Some(key.clone()) {
+                              swap_source.set(None);
+                          } else {
+                              swap_source.set(Some(key.clone()));
+                          }
+                      }
+                  },
+                  ArrowLeftRight { class: "w-4 h-4 mr-1" }
+                  "Reassign"
+                }
+                button {
+                  class: "btn btn-xs btn-soft btn-error",
+                  title: "Remove the sound of this key",
+                  disabled: !pack.definitions.contains_key(&key),
+                  onclick: {
+                      let key = key.clone();
+                      move |_| {
+                          if let Some(pack) = soundpack.write().as_mut() {
+                              pack.definitions.remove(&key);
+                          }
+                          dirty.set(true);
+                      }
+                  },
+                  Trash { class: "w-4 h-4" }
+                }
+              }
+              if swap_source().is_some() {
+                p { class: "text-xs text-base-content/60",
+                  "Click the key that should get these segments, its own segments move here."
+                }
+              }
+              if let Some(definition) = pack.definitions.get(&key) {
+                for (index, timing) in definition.timing.iter().copied().enumerate() {
+                  div { class: "flex items-center gap-2 text-xs",
+                    span { class: if index == 0 { "badge badge-primary badge-sm w-12" } else { "badge badge-secondary badge-sm w-12" },
+                      if index == 0 { "Down" } else { "Up" }
+                    }
+                    for edge in 0..2 {
+                      input {
+                        r#type: "number",
+                        class: "input input-xs w-24",
+                        step: "0.1",
+                        min: "0",
+                        max: "{decoded.duration_ms}",
+                        value: "{timing[edge]:.1}",
+                        onchange: {
+                            let key = key.clone();
+                            move |evt: FormEvent| {
+                                let Ok(ms) = evt.value().parse::<f32>() else {
+                                    return;
+                                };
+                                let duration_ms = audio().map_or(0.0, |decoded| decoded.duration_ms);
+                                if let Some(timing) = soundpack
+                                    .write()
+                                    .as_mut()
+                                    .and_then(|pack| pack.definitions.get_mut(&key))
+                                    .and_then(|definition| definition.timing.get_mut(index))
+                                {
+                                    set_segment_edge(timing, edge, ms, duration_ms);
+                                }
+                                dirty.set(true);
+                            }
+                        },
+                      }
+                    }
+                    span { class: "text-base-content/50", "{timing[1] - timing[0]:.1} ms" }
+                    button {
+                      class: "btn btn-xs btn-ghost ml-auto",
+                      title: "Play this segment",
+                      onclick: {
+                          let audio_ctx = audio_ctx.clone();
+                          let decoded = decoded.clone();
+                          let options = pack.options.clone();
+                          move |_| play_segment(&audio_ctx, &decoded, &options, timing[0], timing[1])
+                      },
+                      Play { class: "w-4 h-4" }
+                    }
+                  }
+                }
+              } else {
+                p { class: "text-xs text-base-content/60",
+                  "This key has no sound. Use Reassign on another key to give it one."
+                }
+              }
+            }
+          }
+
+          // Visual keyboard, also used as the target for reassigning
+          div { class: "bg-base-200 rounded-box p-3 space-y-1 overflow-x-auto",
+            if pack.soundpack_type == SoundpackType::Keyboard {
+              for row in ANSI_LAYOUT.iter() {
+                div { class: "flex gap-1",
+                  for code in row.iter() {
+                    button {
+                      key: "{code}",
+                      class: if selected_key().as_deref() == Some(*code) { "btn btn-xs btn-primary min-w-8 px-1" } else if pack.definitions.contains_key(*code) { "btn btn-xs btn-soft min-w-8 px-1" } else { "btn btn-xs btn-ghost min-w-8 px-1 opacity-50" },
+                      title: "{code}",
+                      onclick: move |_| select_key(code.to_string()),
+                      "{key_label(code)}"
+                    }
+                  }
+                }
+              }
+            }
+            // Keys the layout does not cover, and every key of mouse and gamepad packs
+            div { class: "flex flex-wrap gap-1 pt-1",
+              for code in {
+                  let mut others: Vec<String> = pack
+                      .definitions
+                      .keys()
+                      .filter(|code| {
+                          pack.soundpack_type != SoundpackType::Keyboard
+                              || !ANSI_LAYOUT.iter().any(|row| row.contains(&code.as_str()))
+                      })
+                      .cloned()
+                      .collect();
+                  others.sort();
+                  others
+              } {
+                button {
+                  key: "{code}",
+                  class: if selected_key().as_deref() == Some(code.as_str()) { "btn btn-xs btn-primary" } else { "btn btn-xs btn-soft" },
+                  onclick: {
+                      let code = code.clone();
+                      move |_| select_key(code.clone())
+                  },
+                  "{code}"
+                }
+              }
+            }
+          }
+        } else {
+          div { class: "flex items-center justify-center gap-2 p-8 text-sm text-base-content/60",
+            span { class: "loading loading-spinner loading-sm" }
+            "Loading waveform..."
+          }
+        }
+
+        // Leaving with unsaved timings drops them
+        dialog { class: "modal", id: DISCARD_MODAL_ID,
+          div { class: "modal-box",
+            h3 { class: "text-lg font-bold", "Discard unsaved changes?" }
+            div { class: "flex items-start gap-3 mt-6",
+              div { class: "flex-shrink-0 w-10 h-10 rounded-full bg-warning/10 flex items-center justify-center",
+                TriangleAlert { class: "w-5 h-5 text-warning" }
+              }
+              div { class: "text-sm text-base-content/70",
+                "The timings of this sound pack were changed but not saved. Going back loses these changes."
+              }
+            }
+            div { class: "flex justify-end gap-2 pt-6",
+              form { method: "dialog",
+                button { class: "btn btn-ghost", "Keep editing" }
+              }
+              form { method: "dialog",
+                button {
+                  class: "btn btn-warning",
+                  r#type: "submit",
+                  onclick: move |_| {
+                      nav.push(Route::Soundpacks {});
+                  },
+                  "Discard"
+                }
+              }
+            }
+          }
+          form { method: "dialog", class: "modal-backdrop",
+            button { "close" }
+          }
+        }
+      }
+    }
+}
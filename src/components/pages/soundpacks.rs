@@ -1,16 +1,24 @@
 use crate::{
-    components::ui::{ PageHeader, SoundpackImportModal, SoundpackManager, SoundpackTable },
+    components::ui::{
+        PageHeader,
+        SoundpackCatalog,
+        SoundpackImportModal,
+        SoundpackManager,
+        SoundpackTable,
+    },
     state::app::{ use_app_state, use_state_trigger },
 };
 use dioxus::document::eval;
 use dioxus::prelude::*;
-use lucide_dioxus::{ Keyboard, Mouse, Music, Settings2 };
+use lucide_dioxus::{ Gamepad2, Keyboard, Library, Mouse, Music, Settings2 };
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TabType {
     Keyboard,
     Mouse,
+    Gamepad,
+    Catalog,
     Manage,
 }
 
@@ -36,10 +44,17 @@ pub fn Soundpacks() -> Element {
         .cloned()
         .collect();
 
+    let gamepad_soundpacks: Vec<_> = all_soundpacks
+        .iter()
+        .filter(|pack| pack.soundpack_type == crate::state::soundpack::SoundpackType::Gamepad)
+        .cloned()
+        .collect();
+
     println!(
-        "🔄 Filtered: {} keyboard, {} mouse soundpacks",
+        "🔄 Filtered: {} keyboard, {} mouse, {} gamepad soundpacks",
         keyboard_soundpacks.len(),
-        mouse_soundpacks.len()
+        mouse_soundpacks.len(),
+        gamepad_soundpacks.len()
     );
 
     // Get access to audio context for reloading soundpacks
@@ -107,6 +122,52 @@ pub fn Soundpacks() -> Element {
             }
           }
 
+          // Gamepad tab
+          label { class: "tab [--tab-border-color:var(--color-base-300)] [--tab-bg:var(--color-base-200)]",
+            input {
+              r#type: "radio",
+              name: "soundpack-tab",
+              checked: current_tab() == TabType::Gamepad,
+              onchange: move |_| {
+                  current_tab.set(TabType::Gamepad);
+              },
+            }
+            Gamepad2 { class: "w-5 h-5 mr-2" }
+            "Gamepad"
+          }
+          div { class: "tab-content overflow-hidden bg-base-200 border-base-300 py-4 px-0",
+            SoundpackTable {
+              soundpacks: gamepad_soundpacks,
+              soundpack_type: "Gamepad",
+              on_add_click: Some(
+                  EventHandler::new(move |_| {
+                      current_tab.set(TabType::Gamepad);
+                      eval("soundpack_import_modal.showModal()");
+                  }),
+              ),
+            }
+          }
+
+          // Catalog tab
+          label { class: "tab [--tab-border-color:var(--color-base-300)] [--tab-bg:var(--color-base-200)]",
+            input {
+              r#type: "radio",
+              name: "soundpack-tab",
+              checked: current_tab() == TabType::Catalog,
+              onchange: move |_| {
+                  current_tab.set(TabType::Catalog);
+              },
+            }
+            Library { class: "w-5 h-5 mr-2" }
+            "Catalog"
+          }
+          div { class: "tab-content overflow-hidden bg-base-200 border-base-300 py-4 px-0",
+            // Only mounted while open, so the index is fetched when someone looks at it
+            if current_tab() == TabType::Catalog {
+              SoundpackCatalog {}
+            }
+          }
+
           // Manage tab
           label { class: "tab [--tab-border-color:var(--color-base-300)] [--tab-bg:var(--color-base-200)]",
             input { 
@@ -134,7 +195,8 @@ pub fn Soundpacks() -> Element {
           target_soundpack_type: match current_tab() {
               TabType::Keyboard => Some(crate::state::soundpack::SoundpackType::Keyboard),
               TabType::Mouse => Some(crate::state::soundpack::SoundpackType::Mouse),
-              TabType::Manage => None, // Let user choose in manage tab
+              TabType::Gamepad => Some(crate::state::soundpack::SoundpackType::Gamepad),
+              TabType::Catalog | TabType::Manage => None, // Let user choose in manage tab
           },
           on_import_success: EventHandler::new(move |_| {
               trigger_update(());
@@ -1,8 +1,10 @@
 use crate::libs::audio::AudioContext;
+use crate::state::config::{ SoundpackBrowserConfig, SoundpackSort };
 use crate::utils::config::use_config;
+use crate::utils::soundpack_search;
 use dioxus::prelude::*;
 use futures_timer::Delay;
-use lucide_dioxus::{ Check, ChevronDown, Keyboard, Mouse, Music, Search };
+use lucide_dioxus::{ Check, ChevronDown, Gamepad2, Keyboard, Mouse, Music, Search, Star };
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -10,6 +12,17 @@ use std::time::Duration;
 pub enum SelectorType {
     Keyboard,
     Mouse,
+    Gamepad,
+}
+
+impl SelectorType {
+    fn soundpack_type(&self) -> crate::state::soundpack::SoundpackType {
+        match self {
+            SelectorType::Keyboard => crate::state::soundpack::SoundpackType::Keyboard,
+            SelectorType::Mouse => crate::state::soundpack::SoundpackType::Mouse,
+            SelectorType::Gamepad => crate::state::soundpack::SoundpackType::Gamepad,
+        }
+    }
 }
 
 #[derive(Props, Clone, PartialEq)]
@@ -43,53 +56,47 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
     let (config, update_config) = use_config();
 
     // UI state
-    let error = use_signal(String::new);
+    let mut error = use_signal(String::new);
     let mut is_open = use_signal(|| false);
     let mut search_query = use_signal(String::new);
-    let is_loading = use_signal(|| false);
+    let mut highlighted = use_signal(|| 0usize);
+    let mut is_loading = use_signal(|| false);
+    let scan_progress = crate::state::app::use_scan_progress();
 
     // Use global app state for soundpacks
     let soundpacks = use_memo(move || app_state.get_soundpacks());
 
+    // Favorites, tags and sorting are shared by all selectors
+    let browser = use_memo(move || config().soundpack_browser);
+
     // Get current soundpack based on type
     let current = use_memo(move || {
         let config = config();
         match soundpack_type {
             SelectorType::Keyboard => config.keyboard_soundpack.clone(),
             SelectorType::Mouse => config.mouse_soundpack.clone(),
+            SelectorType::Gamepad => config.gamepad_soundpack.clone(),
         }
-    }); // Filter soundpacks based on search query and type, then sort by last_modified
-    let filtered_soundpacks = use_memo(move || {
-        let query = search_query().to_lowercase();
-        let all_packs = soundpacks(); // Filter by type first
-        let type_filtered_packs: Vec<_> = all_packs
+    });
+    let type_soundpacks = use_memo(move || {
+        soundpacks()
             .into_iter()
-            .filter(|pack| {
-                match soundpack_type {
-                    SelectorType::Keyboard =>
-                        pack.soundpack_type == crate::state::soundpack::SoundpackType::Keyboard,
-                    SelectorType::Mouse =>
-                        pack.soundpack_type == crate::state::soundpack::SoundpackType::Mouse,
-                }
-            })
-            .collect();
-
-        // Then filter by search query
-        let mut filtered_packs = if query.is_empty() {
-            type_filtered_packs
-        } else {
-            type_filtered_packs
-                .into_iter()
-                .filter(|pack| {
-                    pack.name.to_lowercase().contains(&query) ||
-                        pack.id.to_lowercase().contains(&query) ||
-                        pack.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
-                })
-                .collect()
-        }; // Sort by last_modified in descending order (most recent first)
-        filtered_packs.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
-
-        filtered_packs
+            .filter(|pack| pack.soundpack_type == soundpack_type.soundpack_type())
+            .collect::<Vec<_>>()
+    });
+    // Filter by search query and tags, favorites first, then the chosen sort
+    let filtered_soundpacks = use_memo(move || {
+        soundpack_search::browse(type_soundpacks(), &search_query(), &browser())
+    });
+    // Tags of this type's packs, plus selected ones from other types so they can be cleared
+    let tag_facets = use_memo(move || {
+        let mut facets = soundpack_search::tag_facets(&type_soundpacks());
+        for tag in browser().tags {
+            if !facets.iter().any(|(facet, _)| facet.eq_ignore_ascii_case(&tag)) {
+                facets.push((tag, 0));
+            }
+        }
+        facets
     });
     let current_soundpack = use_memo(
         move ||
@@ -114,21 +121,108 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
                 "No mouse sound packs found",
                 "No sound packs available",
             ),
+        SelectorType::Gamepad =>
+            (
+                "Select a gamepad sound pack...",
+                "Search gamepad sound packs...",
+                "No gamepad sound packs found",
+                "No sound packs available",
+            ),
     };
 
     // Check if there are any soundpacks available for this type
-    let has_soundpacks = use_memo(move || {
-        soundpacks()
-            .into_iter()
-            .any(|pack| {
-                match soundpack_type {
-                    SelectorType::Keyboard =>
-                        pack.soundpack_type == crate::state::soundpack::SoundpackType::Keyboard,
-                    SelectorType::Mouse =>
-                        pack.soundpack_type == crate::state::soundpack::SoundpackType::Mouse,
+    let has_soundpacks = use_memo(move || !type_soundpacks().is_empty());
+
+    let mut close = move || {
+        is_open.set(false);
+        search_query.set(String::new());
+        highlighted.set(0);
+    };
+
+    let update_browser = {
+        let update_config = update_config.clone();
+        use_callback(move |change: Box<dyn FnOnce(&mut SoundpackBrowserConfig)>| {
+            update_config(Box::new(move |config| change(&mut config.soundpack_browser)));
+        })
+    };
+
+    let select_pack = {
+        let update_config = update_config.clone();
+        use_callback(move |pack_id: String| {
+            close();
+            error.set(String::new());
+            if pack_id == current() || !soundpacks().iter().any(|p| p.folder_path == pack_id) {
+                return;
+            }
+            let pack_id_config = pack_id.clone();
+            update_config(
+                Box::new(move |config| {
+                    config.soundpack_browser.record_use(&pack_id_config);
+                    match soundpack_type {
+                        SelectorType::Keyboard => {
+                            config.keyboard_soundpack = pack_id_config;
+                        }
+                        SelectorType::Mouse => {
+                            config.mouse_soundpack = pack_id_config;
+                        }
+                        SelectorType::Gamepad => {
+                            config.gamepad_soundpack = pack_id_config;
+                        }
+                    }
+                })
+            );
+            let audio_ctx = audio_ctx.clone();
+            spawn(async move {
+                is_loading.set(true);
+                Delay::new(Duration::from_millis(1)).await;
+                let result = match soundpack_type {
+                    SelectorType::Keyboard => {
+                        crate::libs::audio::load_keyboard_soundpack(&audio_ctx, &pack_id)
+                    }
+                    SelectorType::Mouse => {
+                        crate::libs::audio::load_mouse_soundpack(&audio_ctx, &pack_id)
+                    }
+                    SelectorType::Gamepad => {
+                        crate::libs::audio::load_gamepad_soundpack(&audio_ctx, &pack_id)
+                    }
+                };
+                if let Err(e) = result {
+                    let type_str = soundpack_type.soundpack_type().folder_name();
+                    error.set(format!("Failed to load {} soundpack: {}", type_str, e));
                 }
-            })
-    });
+                is_loading.set(false);
+            });
+        })
+    };
+
+    // Arrow keys move the highlight, Enter picks it, Escape closes the list
+    let on_search_key = move |evt: KeyboardEvent| {
+        let count = filtered_soundpacks.read().len();
+        match evt.key() {
+            Key::ArrowDown => {
+                evt.prevent_default();
+                if count > 0 {
+                    highlighted.set((highlighted() + 1).min(count - 1));
+                }
+            }
+            Key::ArrowUp => {
+                evt.prevent_default();
+                highlighted.set(highlighted().saturating_sub(1));
+            }
+            Key::Enter => {
+                evt.prevent_default();
+                let pack_id = filtered_soundpacks
+                    .read()
+                    .get(highlighted())
+                    .map(|pack| pack.folder_path.clone());
+                if let Some(pack_id) = pack_id {
+                    select_pack.call(pack_id);
+                }
+            }
+            Key::Escape => close(),
+            _ => {}
+        }
+    };
 
     rsx! {
       div { class: "space-y-2",
@@ -145,6 +239,7 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
             onclick: move |_| {
                 if has_soundpacks() {
                     is_open.set(!is_open());
+                    highlighted.set(0);
                 }
             },
             div { class: "flex items-center gap-3 flex-1 ",
@@ -201,19 +296,79 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
                   soundpack_type,
               ),
               // Search input
-              div { class: "p-3 border-b border-base-200",
+              div { class: "p-3 border-b border-base-200 space-y-2",
                 div { class: "relative",
                   Search { class: "absolute left-3 top-1/2 -translate-y-1/2 w-4 h-4 text-primary/50" }
                   input {
                     class: "input input-sm w-full px-4 py-2 text-base-content placeholder:text-base-content/40",
                     placeholder: "{search_placeholder}",
                     value: "{search_query}",
-                    oninput: move |evt| search_query.set(evt.value()),
+                    oninput: move |evt| {
+                        search_query.set(evt.value());
+                        highlighted.set(0);
+                    },
+                    onkeydown: on_search_key,
                     autofocus: true,
                   }
                 }
+                // Sorting and tag chips
+                div { class: "flex items-center gap-2",
+                  span { class: "text-xs text-base-content/60", "Sort" }
+                  select {
+                    class: "select select-xs select-bordered",
+                    onchange: move |evt: Event<FormData>| {
+                        if let Some(sort) = SoundpackSort::ALL
+                            .into_iter()
+                            .find(|sort| sort.label() == evt.value())
+                        {
+                            update_browser.call(Box::new(move |browser| browser.sort = sort));
+                        }
+                    },
+                    for sort in SoundpackSort::ALL {
+                      option {
+                        key: "{sort.label()}",
+                        value: "{sort.label()}",
+                        selected: browser().sort == sort,
+                        "{sort.label()}"
+                      }
+                    }
+                  }
+                }
+                if !tag_facets.read().is_empty() {
+                  div { class: "flex flex-wrap gap-1",
+                    for (tag , count) in tag_facets() {
+                      button {
+                        key: "{tag}",
+                        class: format!(
+                            "badge badge-sm cursor-pointer {}",
+                            if browser().tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                                "badge-primary"
+                            } else {
+                                "badge-ghost"
+                            },
+                        ),
+                        onclick: {
+                            let tag = tag.clone();
+                            move |_| {
+                                let tag = tag.clone();
+                                highlighted.set(0);
+                                update_browser.call(Box::new(move |browser| browser.toggle_tag(&tag)));
+                            }
+                        },
+                        "{tag} ({count})"
+                      }
+                    }
+                  }
+                }
               }
 
+              // The list updates once a running scan finishes
+              if let Some(progress) = scan_progress() {
+                div { class: "flex items-center gap-2 px-4 py-2 text-xs text-base-content/60 border-b border-base-300",
+                  span { class: "loading loading-spinner loading-xs" }
+                  "Scanning sound packs {progress.scanned}/{progress.total}..."
+                }
+              }
               // Soundpack list
               div { class: "overflow-y-auto max-h-50",
                 if filtered_soundpacks.read().is_empty() {
@@ -221,85 +376,22 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
                     "{not_found_text}"
                   }
                 } else {
-                  for pack in filtered_soundpacks.read().iter() {
+                  for (index , pack) in filtered_soundpacks.read().iter().enumerate() {
                     button {
                       key: "{pack.id}",
                       class: format!(
-                          "w-full px-4 rounded-none py-2 text-left btn btn-lg justify-start gap-4 border-b border-base-300 last:border-b-0 h-auto {}",
-                          if pack.folder_path == current() { "btn-disabled" } else { "btn-ghost" },
+                          "w-full px-4 rounded-none py-2 text-left btn btn-lg justify-start gap-4 border-b border-base-300 last:border-b-0 h-auto {} {}",
+                          if pack.folder_path == current() { "btn-ghost btn-active" } else { "btn-ghost" },
+                          if index == highlighted() { "bg-base-300" } else { "" },
                       ),
-                      disabled: pack.folder_path == current(),
+                      // Not disabled, so the current pack can still be pinned
+                      onmouseenter: move |_| highlighted.set(index),
                       // Use folder_path for comparison
                       onclick: {
                           let pack_id = pack.folder_path.clone();
-                          let mut error = error.clone();
-                          let soundpacks = soundpacks.clone();
-                          let mut is_open = is_open.clone();
-                          let mut search_query = search_query.clone();
-                          let is_loading = is_loading.clone();
-                          let audio_ctx = audio_ctx.clone();
-                          let update_config = update_config.clone();
-                          let soundpack_type_click = soundpack_type.clone();
-                          move |_| {
-                              is_open.set(false);
-                              search_query.set(String::new());
-                              error.set(String::new());
-                              if let Some(_) = soundpacks().iter().find(|p| p.folder_path == pack_id) {
-                                  let pack_id_clone = pack_id.clone();
-                                  let soundpack_type_clone = soundpack_type_click.clone();
-                                  update_config(
-                                      Box::new(move |config| {
-                                          match soundpack_type_clone {
-                                              SelectorType::Keyboard => {
-                                                  config.keyboard_soundpack = pack_id_clone;
-                                              }
-                                              SelectorType::Mouse => {
-                                                  config.mouse_soundpack = pack_id_clone;
-                                              }
-                                          }
-                                      }),
-                                  );
-                                  let pack_id_async = pack_id.clone();
-                                  let audio_ctx_async = audio_ctx.clone();
-                                  let mut error_async = error.clone();
-                                  let mut is_loading_async = is_loading.clone();
-                                  let soundpack_type_async = soundpack_type_click.clone();
-                                  spawn(async move {
-                                      is_loading_async.set(true);
-                                      Delay::new(Duration::from_millis(1)).await;
-                                      let result = match soundpack_type_async {
-                                          SelectorType::Keyboard => {
-                                              crate::libs::audio::load_keyboard_soundpack(
-                                                  &audio_ctx_async,
-                                                  &pack_id_async,
-                                              )
-                                          }
-                                          SelectorType::Mouse => {
-                                              crate::libs::audio::load_mouse_soundpack(
-                                                  &audio_ctx_async,
-                                                  &pack_id_async,
-                                              )
-                                          }
-                                      };
-                                      match result {
-                                          Ok(_) => {}
-                                          Err(e) => {
-                                              let type_str = match soundpack_type_async {
-                                                  SelectorType::Keyboard => "keyboard",
-                                                  SelectorType::Mouse => "mouse",
-                                              };
-                                              error_async
-                                                  .set(
-                                                      format!("Failed to load {} soundpack: {}", type_str, e),
-                                                  );
-                                          }
-                                      }
-                                      is_loading_async.set(false);
-                                  });
-                              }
-                          }
+                          move |_| select_pack.call(pack_id.clone())
                       },
-                      div { class: "flex items-center justify-between gap-3 ",
+                      div { class: "flex items-center justify-between gap-3 w-full",
                         div { class: "flex-shrink-0 w-8 h-8 rounded-box flex items-center justify-center bg-base-100 overflow-hidden relative",
                           if let Some(icon) = &pack.icon {
                             if !icon.is_empty() {
@@ -332,6 +424,25 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
                             }
                           }
                         }
+                        // Pin as favorite, a span since buttons can't be nested
+                        span {
+                          class: "flex-shrink-0 p-1",
+                          title: if browser().is_favorite(&pack.folder_path) { "Unpin" } else { "Pin to top" },
+                          onclick: {
+                              let pack_id = pack.folder_path.clone();
+                              move |evt: MouseEvent| {
+                                  evt.stop_propagation();
+                                  let pack_id = pack_id.clone();
+                                  update_browser
+                                      .call(Box::new(move |browser| browser.toggle_favorite(&pack_id)));
+                              }
+                          },
+                          if browser().is_favorite(&pack.folder_path) {
+                            Star { class: "w-4 h-4 text-warning fill-current" }
+                          } else {
+                            Star { class: "w-4 h-4 text-base-content/30" }
+                          }
+                        }
                       }
                     }
                   }
@@ -342,13 +453,7 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
         }
         // Click outside to close
         if is_open() && has_soundpacks() {
-          div {
-            class: "fixed inset-0 z-40",
-            onclick: move |_| {
-                is_open.set(false);
-                search_query.set(String::new());
-            },
-          }
+          div { class: "fixed inset-0 z-40", onclick: move |_| close() }
         }
 
         // Error display
@@ -359,7 +464,7 @@ fn SoundpackDropdown(soundpack_type: SelectorType) -> Element {
     }
 }
 
-// Wrapper components for keyboard and mouse soundpack selectors
+// Wrapper components for keyboard, mouse and gamepad soundpack selectors
 
 #[component]
 pub fn KeyboardSoundpackSelector() -> Element {
@@ -386,3 +491,16 @@ pub fn MouseSoundpackSelector() -> Element {
         }
     }
 }
+
+#[component]
+pub fn GamepadSoundpackSelector() -> Element {
+    rsx! {
+        SoundpackSelector {
+            soundpack_type: SelectorType::Gamepad,
+            label: "Gamepad".to_string(),
+            icon: rsx! {
+                Gamepad2 { class: "w-4 h-4" }
+            },
+        }
+    }
+}
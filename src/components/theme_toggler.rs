@@ -3,9 +3,10 @@ use crate::state::config::AppConfig;
 use crate::state::themes::ThemesConfig;
 use crate::utils::config::use_config;
 use crate::utils::theme::use_themes;
+use crate::utils::theme_share::{ decode_theme, encode_theme, theme_share_link, THEME_FILE_EXTENSION };
 use dioxus::document::eval;
 use dioxus::prelude::*;
-use lucide_dioxus::{ Ellipsis, ExternalLink, Palette, Pencil, Plus, Trash2 };
+use lucide_dioxus::{ Ellipsis, ExternalLink, FileDown, Link, Palette, Pencil, Plus, Trash2 };
 
 #[component]
 pub fn ThemeToggler() -> Element {
@@ -20,6 +21,7 @@ pub fn ThemeToggler() -> Element {
 
     // State for editing themes
     let mut editing_theme = use_signal(|| None::<String>); // Theme ID being edited
+    let mut share_status = use_signal(|| None::<Result<String, String>>);
 
     rsx! {
       div { class: "space-y-8 mt-4",
@@ -127,6 +129,58 @@ pub fn ThemeToggler() -> Element {
                                 eval("theme_creator_modal.showModal()");
                             }
                         },
+                        on_share: {
+                            let theme_data = (*theme_data).clone();
+                            move |_| {
+                                match theme_share_link(&theme_data) {
+                                    Ok(link) => {
+                                        let link = serde_json::to_string(&link).unwrap_or_default();
+                                        eval(&format!("navigator.clipboard.writeText({})", link));
+                                        share_status
+                                            .set(
+                                                Some(Ok(format!("Copied a share link for {}", theme_data.name))),
+                                            );
+                                    }
+                                    Err(e) => share_status.set(Some(Err(e))),
+                                }
+                            }
+                        },
+                        on_export: {
+                            let theme_data = (*theme_data).clone();
+                            move |_| {
+                                let theme_data = theme_data.clone();
+                                spawn(async move {
+                                    let file_dialog = rfd::AsyncFileDialog::new()
+                                        .add_filter("MechAura Theme", &[THEME_FILE_EXTENSION])
+                                        .set_title("Export Theme")
+                                        .set_file_name(format!("{}.{}", theme_data.name, THEME_FILE_EXTENSION))
+                                        .save_file()
+                                        .await;
+                                    let Some(file_handle) = file_dialog else {
+                                        return;
+                                    };
+                                    let result = encode_theme(&theme_data)
+                                        .and_then(|payload| {
+                                            std::fs::write(file_handle.path(), payload)
+                                                .map_err(|e| format!("Failed to write theme file: {}", e))
+                                        });
+                                    match result {
+                                        Ok(()) => {
+                                            share_status
+                                                .set(
+                                                    Some(
+                                                        Ok(format!("Saved {}", file_handle.path().display())),
+                                                    ),
+                                                )
+                                        }
+                                        Err(e) => {
+                                            eprintln!("❌ Failed to export theme: {}", e);
+                                            share_status.set(Some(Err(e)));
+                                        }
+                                    }
+                                });
+                            }
+                        },
                       }
                     }
                   }
@@ -136,6 +190,15 @@ pub fn ThemeToggler() -> Element {
                   }
               }
           }
+          match share_status() {
+              Some(Ok(message)) => rsx! {
+                div { class: "text-xs text-success break-all", "{message}" }
+              },
+              Some(Err(message)) => rsx! {
+                div { class: "text-xs text-error break-all", "{message}" }
+              },
+              None => rsx! {},
+          }
           // Create new theme button
           CreateThemeButton { editing_theme_id: editing_theme }
         }
@@ -152,6 +215,8 @@ struct CustomThemeButtonProps {
     on_select: EventHandler<MouseEvent>,
     on_delete: EventHandler<MouseEvent>,
     on_edit: EventHandler<MouseEvent>,
+    on_share: EventHandler<MouseEvent>,
+    on_export: EventHandler<MouseEvent>,
     is_built_in: bool,
 }
 
@@ -197,12 +262,18 @@ fn CustomThemeButton(props: CustomThemeButtonProps) -> Element {
                   "Edit"
                 }
               }
-              // li {
-              //   a { href: "", class: "disabled",
-              //     Share2 { class: "w-4 h-4 mr-1" }
-              //     "Share"
-              //   }
-              // }
+              li {
+                a { onclick: props.on_share,
+                  Link { class: "w-4 h-4 mr-1" }
+                  "Copy share link"
+                }
+              }
+              li {
+                a { onclick: props.on_export,
+                  FileDown { class: "w-4 h-4 mr-1" }
+                  "Export file"
+                }
+              }
               li {
                 a {
                   class: "text-error",
@@ -239,13 +310,13 @@ fn CreateThemeButton(props: CreateThemeButtonProps) -> Element {
           Palette { class: "w-4 h-4 mr-1" }
           "Create"
         }
-        // Disabled button for adding themes
-        // This is just a placeholder for future functionality
-        div { class: "tooltip", "data-tip": "Coming soon!",
-          button { class: "btn btn-ghost btn-sm", disabled: true,
-            Plus { class: "w-4 h-4 mr-1" }
-            "Add"
-          }
+        button {
+          class: "btn btn-ghost btn-sm",
+          onclick: move |_| {
+              eval("theme_import_modal.showModal()");
+          },
+          Plus { class: "w-4 h-4 mr-1" }
+          "Import"
         }
         a {
           class: "btn btn-ghost btn-sm",
@@ -256,6 +327,7 @@ fn CreateThemeButton(props: CreateThemeButtonProps) -> Element {
           ExternalLink { class: "w-4 h-4 ml-1" }
         }
         ThemeCreatorModal { editing_theme_id: props.editing_theme_id }
+        ThemeImportModal {}
       }
     }
 }
@@ -449,3 +521,92 @@ fn ThemeCreatorModal(props: ThemeCreatorModalProps) -> Element {
       }
     }
 }
+
+#[component]
+fn ThemeImportModal() -> Element {
+    let (_themes, update_themes) = use_themes();
+    let mut input = use_signal(String::new);
+    let mut error = use_signal(String::new);
+
+    let on_open_file = move |_| {
+        spawn(async move {
+            let file_dialog = rfd::AsyncFileDialog
+                ::new()
+                .add_filter("MechAura Theme", &[THEME_FILE_EXTENSION])
+                .set_title("Select Theme File")
+                .pick_file().await;
+            let Some(file_handle) = file_dialog else {
+                return;
+            };
+            match std::fs::read_to_string(file_handle.path()) {
+                Ok(content) => {
+                    input.set(content);
+                    error.set(String::new());
+                }
+                Err(e) => error.set(format!("Failed to read theme file: {}", e)),
+            }
+        });
+    };
+
+    let on_import = move |_| {
+        match decode_theme(&input()) {
+            Ok(theme) => {
+                println!("📥 Importing shared theme {}", theme.name);
+                update_themes(
+                    Box::new(move |themes: &mut ThemesConfig| {
+                        if let Err(e) = themes.import_theme(theme) {
+                            eprintln!("Failed to import theme: {}", e);
+                        }
+                    })
+                );
+                input.set(String::new());
+                error.set(String::new());
+                eval("theme_import_modal.close()");
+            }
+            Err(e) => error.set(e),
+        }
+    };
+
+    rsx! {
+      dialog { class: "modal", id: "theme_import_modal",
+        div { class: "modal-box",
+          form { method: "dialog",
+            button { class: "btn btn-sm btn-circle btn-ghost absolute right-2 top-2",
+              "✕"
+            }
+          }
+          h3 { class: "font-bold text-lg mb-4", "Import theme" }
+          div { class: "space-y-4",
+            fieldset { class: "fieldset",
+              legend { class: "fieldset-legend", "Share link or theme data" }
+              textarea {
+                class: "textarea w-full h-32 font-mono text-xs break-all",
+                placeholder: "mechaura://import-theme/...",
+                value: input(),
+                oninput: move |e| input.set(e.value()),
+              }
+              div { class: "label", "Paste a share link, or open a .{THEME_FILE_EXTENSION} file" }
+            }
+            if !error().is_empty() {
+              div { class: "alert alert-error", {error()} }
+            }
+            div { class: "flex justify-between gap-2",
+              button { class: "btn btn-ghost btn-sm", onclick: on_open_file,
+                FileDown { class: "w-4 h-4 mr-1" }
+                "Open file"
+              }
+              button {
+                class: "btn btn-primary btn-sm",
+                disabled: input().trim().is_empty(),
+                onclick: on_import,
+                "Import theme"
+              }
+            }
+          }
+        }
+        form { method: "dialog", class: "modal-backdrop",
+          button { "close" }
+        }
+      }
+    }
+}
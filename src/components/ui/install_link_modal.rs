@@ -0,0 +1,241 @@
+use crate::libs::audio::{ load_gamepad_soundpack, load_keyboard_soundpack, load_mouse_soundpack };
+use crate::libs::protocol::{ resolve_install_source, take_pending_install, InstallSource };
+use crate::libs::AudioContext;
+use crate::state::app::refresh_cache;
+use crate::state::config::AppConfig;
+use crate::state::soundpack::{ SoundpackCache, SoundpackType };
+use crate::utils::soundpack_catalog::{ install_entry, CatalogEntry };
+use crate::utils::soundpack_installer::{ InstallOutcome, SoundpackInfo };
+use dioxus::document::eval;
+use dioxus::prelude::*;
+use lucide_dioxus::{ Check, Download, TriangleAlert };
+use std::sync::Arc;
+
+const MODAL_ID: &str = "install_link_modal";
+
+/// Make a freshly installed pack the active one for its type
+///
+/// `soundpack_id` is the cache ID (`keyboard/<folder>`), the cache must already list the pack.
+fn activate_soundpack(audio_ctx: &AudioContext, soundpack_id: &str) -> Result<(), String> {
+    let cache = SoundpackCache::load();
+    let metadata = cache.soundpacks
+        .values()
+        .find(|metadata| metadata.id == soundpack_id)
+        .ok_or_else(|| format!("Installed soundpack {} was not found", soundpack_id))?;
+    let folder_path = metadata.folder_path.clone();
+
+    let mut config = AppConfig::load();
+    match metadata.soundpack_type {
+        SoundpackType::Keyboard => {
+            load_keyboard_soundpack(audio_ctx, &folder_path)?;
+            config.keyboard_soundpack = folder_path;
+        }
+        SoundpackType::Mouse => {
+            load_mouse_soundpack(audio_ctx, &folder_path)?;
+            config.mouse_soundpack = folder_path;
+        }
+        SoundpackType::Gamepad => {
+            load_gamepad_soundpack(audio_ctx, &folder_path)?;
+            config.gamepad_soundpack = folder_path;
+        }
+    }
+    config.last_updated = chrono::Utc::now();
+    config.save()
+}
+
+/// Confirmation for `mechaura://install-soundpack` links, nothing is downloaded before it
+#[component]
+pub fn InstallLinkModal() -> Element {
+    let audio_ctx: Arc<AudioContext> = use_context();
+    let mut request = use_signal(|| None::<InstallSource>);
+    let mut entry = use_signal(|| None::<CatalogEntry>);
+    let mut error = use_signal(|| None::<String>);
+    let mut installing = use_signal(|| false);
+    let mut installed = use_signal(|| None::<String>);
+    // Installed pack with the same ID, and whether to activate once it is replaced
+    let mut conflict = use_signal(|| None::<(SoundpackInfo, bool)>);
+
+    // Pick up links one at a time, the next one waits until this dialog is closed
+    use_future(move || async move {
+        loop {
+            if request().is_none() {
+                if let Some(source) = take_pending_install() {
+                    request.set(Some(source.clone()));
+                    entry.set(None);
+                    error.set(None);
+                    installed.set(None);
+                    conflict.set(None);
+                    eval(&format!("document.getElementById(\"{}\").showModal()", MODAL_ID));
+                    match resolve_install_source(&source).await {
+                        Ok(resolved) => entry.set(Some(resolved)),
+                        Err(e) => {
+                            eprintln!("❌ Failed to look up soundpack link: {}", e);
+                            error.set(Some(e));
+                        }
+                    }
+                }
+            }
+            futures_timer::Delay::new(std::time::Duration::from_millis(250)).await;
+        }
+    });
+
+    let mut close = move || {
+        if !installing() {
+            request.set(None);
+        }
+    };
+
+    let on_install = use_callback(move |(activate, replace): (bool, bool)| {
+        let Some(target) = entry() else {
+            return;
+        };
+        let audio_ctx = audio_ctx.clone();
+        spawn(async move {
+            installing.set(true);
+            error.set(None);
+            conflict.set(None);
+            match install_entry(&target, replace).await {
+                Ok(InstallOutcome::AlreadyInstalled(info)) => {
+                    // A link must not overwrite an installed pack and its edits without asking
+                    println!("📦 Soundpack {} is already installed, asking before replacing it", info.id);
+                    conflict.set(Some((info, activate)));
+                }
+                Ok(InstallOutcome::Installed(info)) => {
+                    println!("✅ Installed soundpack {} from a link", info.id);
+                    // Activating looks the pack up in the cache, so wait for the scan
+                    refresh_cache().await;
+                    let mut message = format!("Installed {}", info.name);
+                    if activate {
+                        let soundpack_id = info.cache_id();
+                        match activate_soundpack(&audio_ctx, &soundpack_id) {
+                            Ok(()) => message.push_str(" and made it the active pack"),
+                            Err(e) => {
+                                eprintln!("❌ Failed to activate soundpack {}: {}", soundpack_id, e);
+                                error.set(Some(e));
+                            }
+                        }
+                    }
+                    installed.set(Some(message));
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to install soundpack from link: {}", e);
+                    error.set(Some(e));
+                }
+            }
+            installing.set(false);
+        });
+    });
+
+    rsx! {
+      dialog {
+        class: "modal",
+        id: MODAL_ID,
+        oncancel: move |_| close(),
+        div { class: "modal-box",
+          h3 { class: "text-lg font-bold", "Install soundpack?" }
+
+          div { class: "space-y-4 mt-6",
+            if let Some(target) = entry() {
+              div { class: "space-y-1",
+                div { class: "font-medium", "{target.name}" }
+                if let Some(author) = &target.author {
+                  div { class: "text-sm text-base-content/60", "by {author}" }
+                }
+                if let Some(description) = &target.description {
+                  div { class: "text-sm text-base-content/70", "{description}" }
+                }
+                div { class: "text-xs text-base-content/50 break-all", "From {target.zip_url}" }
+                div { class: "text-xs font-mono text-base-content/50 break-all", "sha256 {target.sha256}" }
+                if target.size > 0 {
+                  div { class: "text-xs text-base-content/50",
+                    "{target.size as f64 / (1024.0 * 1024.0):.1} MB"
+                  }
+                }
+              }
+              div { class: "text-xs text-base-content/60",
+                "A link opened this dialog. Only install soundpacks from sources you trust."
+              }
+            } else if error().is_none() {
+              div { class: "flex items-center gap-2 text-sm text-base-content/60",
+                span { class: "loading loading-spinner loading-xs" }
+                "Looking up the soundpack..."
+              }
+            }
+
+            if let Some(message) = error() {
+              div { class: "flex items-start gap-2 text-sm text-error break-all",
+                TriangleAlert { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                "{message}"
+              }
+            }
+            if let Some((info, activate)) = conflict() {
+              div { class: "flex items-start gap-3 p-3 rounded-box bg-warning/10",
+                TriangleAlert { class: "w-5 h-5 text-warning flex-shrink-0 mt-0.5" }
+                div { class: "flex-1 space-y-2",
+                  div { class: "font-medium text-sm", "Replace installed soundpack?" }
+                  div { class: "text-sm text-base-content/70",
+                    "\"{info.name}\" is already installed. Installing it again replaces the installed copy, and any changes made to it are lost."
+                  }
+                  div { class: "flex justify-end gap-2",
+                    button {
+                      class: "btn btn-sm btn-ghost",
+                      onclick: move |_| conflict.set(None),
+                      "Keep installed"
+                    }
+                    button {
+                      class: "btn btn-sm btn-warning",
+                      disabled: installing(),
+                      onclick: move |_| on_install.call((activate, true)),
+                      if installing() {
+                        span { class: "loading loading-spinner loading-xs mr-1" }
+                      } else {
+                        Download { class: "w-4 h-4 mr-1" }
+                      }
+                      "Replace"
+                    }
+                  }
+                }
+              }
+            }
+            if let Some(message) = installed() {
+              div { class: "flex items-start gap-2 text-sm text-success",
+                Check { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                "{message}"
+              }
+            }
+
+            // Action buttons
+            div { class: "flex justify-end gap-2 pt-2",
+              form { method: "dialog",
+                button {
+                  class: "btn btn-ghost",
+                  disabled: installing(),
+                  onclick: move |_| close(),
+                  if installed().is_some() { "Close" } else { "Cancel" }
+                }
+              }
+              if installed().is_none() && conflict().is_none() {
+                button {
+                  class: "btn btn-soft",
+                  disabled: entry().is_none() || installing(),
+                  onclick: move |_| on_install.call((false, false)),
+                  "Install"
+                }
+                button {
+                  class: "btn btn-primary",
+                  disabled: entry().is_none() || installing(),
+                  onclick: move |_| on_install.call((true, false)),
+                  if installing() {
+                    span { class: "loading loading-spinner loading-xs mr-1" }
+                  } else {
+                    Download { class: "w-4 h-4 mr-1" }
+                  }
+                  "Install and use"
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -1,8 +1,12 @@
 pub mod collapse;
 pub mod color_picker;
 pub mod confirm_delete_modal;
+pub mod install_link_modal;
 pub mod page_header;
 pub mod progress_step;
+pub mod soundpack_catalog;
+pub mod soundpack_doctor_modal;
+pub mod soundpack_export_modal;
 pub mod soundpack_import_modal;
 pub mod soundpack_manager;
 pub mod soundpack_table;
@@ -11,9 +15,13 @@ pub mod toggler;
 pub use collapse::Collapse;
 pub use color_picker::ColorPicker;
 pub use confirm_delete_modal::ConfirmDeleteModal;
+pub use install_link_modal::InstallLinkModal;
 pub use page_header::PageHeader;
 pub use progress_step::ImportStep;
 pub use progress_step::ProgressStep;
+pub use soundpack_catalog::SoundpackCatalog;
+pub use soundpack_doctor_modal::SoundpackDoctorModal;
+pub use soundpack_export_modal::SoundpackExportModal;
 pub use soundpack_import_modal::SoundpackImportModal;
 pub use soundpack_manager::SoundpackManager;
 pub use soundpack_table::SoundpackTable;
@@ -0,0 +1,298 @@
+use crate::state::app::{ refresh_cache, use_app_state };
+use crate::state::config::AppConfig;
+use crate::utils::soundpack_catalog::{ fetch_catalog, install_entry, Catalog, CatalogEntry };
+use crate::utils::soundpack_installer::InstallOutcome;
+use dioxus::document::eval;
+use dioxus::prelude::*;
+use lucide_dioxus::{ Check, Download, Music, RefreshCw, TriangleAlert };
+use std::collections::HashSet;
+
+const REPLACE_MODAL_ID: &str = "catalog_replace_modal";
+
+#[component]
+pub fn SoundpackCatalog() -> Element {
+    let app_state = use_app_state();
+    let mut catalog_url = use_signal(|| AppConfig::load().catalog_url);
+    let mut catalog = use_signal(|| None::<Catalog>);
+    let mut loading = use_signal(|| false);
+    let mut load_error = use_signal(|| None::<String>);
+    let mut search_query = use_signal(String::new);
+    let mut selected_tag = use_signal(|| None::<String>);
+    let mut installing = use_signal(|| None::<String>); // ID of the entry being installed
+    let mut status = use_signal(|| None::<Result<String, String>>);
+    let mut pending_replace = use_signal(|| None::<CatalogEntry>); // Installed entry to download again
+
+    let load_catalog = move || {
+        spawn(async move {
+            loading.set(true);
+            load_error.set(None);
+            let url = catalog_url();
+            match fetch_catalog(&url).await {
+                Ok(loaded) => {
+                    println!("📚 Loaded {} catalog entries from {}", loaded.soundpacks.len(), url);
+                    catalog.set(Some(loaded));
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to load soundpack catalog: {}", e);
+                    catalog.set(None);
+                    load_error.set(Some(e));
+                }
+            }
+            loading.set(false);
+        });
+    };
+
+    // Load the catalog when the tab is opened, there is none until the user sets a URL
+    use_hook(move || {
+        if !catalog_url().trim().is_empty() {
+            load_catalog();
+        }
+    });
+
+    let on_load = move |_| {
+        let mut config = AppConfig::load();
+        if config.catalog_url != catalog_url() {
+            config.catalog_url = catalog_url();
+            if let Err(e) = config.save() {
+                eprintln!("❌ Failed to save catalog URL: {}", e);
+            }
+        }
+        load_catalog();
+    };
+
+    let mut confirm_replace = move |entry: CatalogEntry| {
+        pending_replace.set(Some(entry));
+        eval(&format!("document.getElementById(\"{}\").showModal()", REPLACE_MODAL_ID));
+    };
+
+    let on_install = move |entry: CatalogEntry, replace: bool| {
+        spawn(async move {
+            installing.set(Some(entry.id.clone()));
+            status.set(None);
+            match install_entry(&entry, replace).await {
+                Ok(InstallOutcome::AlreadyInstalled(info)) => {
+                    // Installed under another folder name than the catalog ID
+                    println!("📦 Soundpack {} is already installed, asking before replacing it", info.id);
+                    confirm_replace(entry.clone());
+                }
+                Ok(InstallOutcome::Installed(info)) => {
+                    println!("✅ Installed soundpack {} from the catalog", info.id);
+                    // Wait for the scan so the entry shows as installed right away
+                    refresh_cache().await;
+                    status.set(Some(Ok(format!("Installed {}", info.name))));
+                }
+                Err(e) => {
+                    eprintln!("❌ Failed to install {} from the catalog: {}", entry.id, e);
+                    status.set(Some(Err(e)));
+                }
+            }
+            installing.set(None);
+        });
+    };
+
+    // Cache IDs are `<type>/<folder>`, catalog entries are named after the folder
+    let installed_ids: HashSet<String> = app_state
+        .get_soundpacks()
+        .into_iter()
+        .filter_map(|pack| pack.id.rsplit('/').next().map(str::to_string))
+        .collect();
+
+    rsx! {
+      div { class: "space-y-4 px-4",
+        // Catalog source
+        div { class: "flex items-center gap-2",
+          input {
+            class: "input input-sm flex-1",
+            placeholder: "Catalog URL or file path",
+            value: "{catalog_url}",
+            oninput: move |evt| catalog_url.set(evt.value()),
+          }
+          button {
+            class: "btn btn-sm btn-soft",
+            disabled: loading(),
+            onclick: on_load,
+            if loading() {
+              span { class: "loading loading-spinner loading-xs mr-1" }
+            } else {
+              RefreshCw { class: "w-4 h-4 mr-1" }
+            }
+            "Load"
+          }
+        }
+
+        if catalog_url().trim().is_empty() {
+          div { class: "text-sm text-base-content/60",
+            "Enter the URL or file path of a soundpack catalog index and press Load."
+          }
+        }
+
+        if let Some(message) = load_error() {
+          div { class: "flex items-start gap-2 text-sm text-error break-all",
+            TriangleAlert { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+            "{message}"
+          }
+        }
+        match status() {
+            Some(Ok(message)) => rsx! {
+              div { class: "flex items-start gap-2 text-sm text-success",
+                Check { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                "{message}"
+              }
+            },
+            Some(Err(message)) => rsx! {
+              div { class: "flex items-start gap-2 text-sm text-error break-all",
+                TriangleAlert { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                "{message}"
+              }
+            },
+            None => rsx! {},
+        }
+
+        if let Some(loaded) = catalog() {
+          input {
+            class: "input input-sm w-full",
+            placeholder: "Search the catalog...",
+            value: "{search_query}",
+            oninput: move |evt| search_query.set(evt.value()),
+          }
+          // Tag filter
+          div { class: "flex flex-wrap gap-1",
+            for tag in loaded.tags() {
+              button {
+                class: if selected_tag() == Some(tag.clone()) { "badge badge-sm badge-primary cursor-pointer" } else { "badge badge-sm badge-ghost cursor-pointer" },
+                onclick: {
+                    let tag = tag.clone();
+                    move |_| {
+                        if selected_tag() == Some(tag.clone()) {
+                            selected_tag.set(None);
+                        } else {
+                            selected_tag.set(Some(tag.clone()));
+                        }
+                    }
+                },
+                "{tag}"
+              }
+            }
+          }
+
+          div { class: "space-y-2",
+            for entry in loaded.search(&search_query(), selected_tag().as_deref()).into_iter().cloned() {
+              div {
+                key: "{entry.id}",
+                class: "flex items-center gap-3 bg-base-100 rounded-box p-2",
+                if let Some(preview) = &entry.preview {
+                  div { class: "w-10 h-10 rounded-box overflow-hidden flex-shrink-0",
+                    img {
+                      class: "w-full h-full object-cover",
+                      src: "{preview}",
+                      alt: "{entry.name}",
+                    }
+                  }
+                } else {
+                  div { class: "w-10 h-10 rounded-box bg-base-300 flex items-center justify-center flex-shrink-0",
+                    Music { class: "w-4 h-4 text-base-content/40" }
+                  }
+                }
+                div { class: "flex-1 min-w-0",
+                  div { class: "font-medium text-sm line-clamp-1", "{entry.name}" }
+                  div { class: "text-xs text-base-content/50",
+                    if let Some(author) = &entry.author {
+                      "by {author}"
+                    }
+                    if entry.size > 0 {
+                      " · {entry.size as f64 / (1024.0 * 1024.0):.1} MB"
+                    }
+                  }
+                  if let Some(description) = &entry.description {
+                    div { class: "text-xs text-base-content/70 line-clamp-2", "{description}" }
+                  }
+                  div { class: "flex flex-wrap gap-1 mt-1",
+                    for tag in entry.tags.iter() {
+                      span { class: "badge badge-xs badge-ghost", "{tag}" }
+                    }
+                  }
+                }
+                if installed_ids.contains(&entry.id) {
+                  span { class: "badge badge-sm badge-success", "Installed" }
+                  button {
+                    class: "btn btn-sm btn-ghost",
+                    disabled: installing().is_some(),
+                    onclick: {
+                        let entry = entry.clone();
+                        move |_| confirm_replace(entry.clone())
+                    },
+                    if installing() == Some(entry.id.clone()) {
+                      span { class: "loading loading-spinner loading-xs mr-1" }
+                    }
+                    "Reinstall"
+                  }
+                } else {
+                  button {
+                    class: "btn btn-sm btn-primary",
+                    disabled: installing().is_some(),
+                    onclick: {
+                        let entry = entry.clone();
+                        move |_| on_install(entry.clone(), false)
+                    },
+                    if installing() == Some(entry.id.clone()) {
+                      span { class: "loading loading-spinner loading-xs mr-1" }
+                    } else {
+                      Download { class: "w-4 h-4 mr-1" }
+                    }
+                    "Install"
+                  }
+                }
+              }
+            }
+            if loaded.soundpacks.is_empty() {
+              div { class: "text-sm text-base-content/50", "The catalog is empty." }
+            }
+          }
+        }
+
+        // Reinstalling replaces the installed copy, including edits made to it
+        dialog { class: "modal", id: REPLACE_MODAL_ID,
+          div { class: "modal-box",
+            h3 { class: "text-lg font-bold", "Replace installed soundpack?" }
+            div { class: "flex items-start gap-3 mt-6",
+              div { class: "flex-shrink-0 w-10 h-10 rounded-full bg-warning/10 flex items-center justify-center",
+                TriangleAlert { class: "w-5 h-5 text-warning" }
+              }
+              div { class: "text-sm text-base-content/70",
+                if let Some(entry) = pending_replace() {
+                  "\"{entry.name}\" is already installed. Downloading it again replaces the installed copy, and any changes made to it are lost."
+                }
+              }
+            }
+            div { class: "flex justify-end gap-2 pt-6",
+              form { method: "dialog",
+                button {
+                  class: "btn btn-ghost",
+                  onclick: move |_| pending_replace.set(None),
+                  "Cancel"
+                }
+              }
+              form { method: "dialog",
+                button {
+                  class: "btn btn-warning",
+                  r#type: "submit",
+                  onclick: move |_| {
+                      let entry = pending_replace();
+                      pending_replace.set(None);
+                      if let Some(entry) = entry {
+                          on_install(entry, true);
+                      }
+                  },
+                  Download { class: "w-4 h-4 mr-1" }
+                  "Replace"
+                }
+              }
+            }
+          }
+          form { method: "dialog", class: "modal-backdrop",
+            button { onclick: move |_| pending_replace.set(None), "close" }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::state::app::store_doctor_report;
+use crate::state::paths;
+use crate::utils::soundpack_doctor::{ diagnose_soundpack, DoctorReport };
+use dioxus::prelude::*;
+use lucide_dioxus::{ Check, Stethoscope, TriangleAlert };
+use std::path::Path;
+
+#[component]
+pub fn SoundpackDoctorModal(
+    modal_id: String,
+    soundpack_id: String,
+    soundpack_name: String,
+    report: Option<DoctorReport>
+) -> Element {
+    let mut running = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let on_run = {
+        let soundpack_id = soundpack_id.clone();
+        move |_| {
+            let soundpack_id = soundpack_id.clone();
+            spawn(async move {
+                running.set(true);
+                error.set(None);
+                let soundpack_dir = paths::soundpacks::soundpack_dir(&soundpack_id);
+                let result = tokio::task
+                    ::spawn_blocking(move || diagnose_soundpack(Path::new(&soundpack_dir))).await
+                    .unwrap_or_else(|e| Err(format!("Doctor task failed: {}", e)));
+
+                match result {
+                    Ok(report) => {
+                        println!(
+                            "🩺 Soundpack {} checked, {} findings",
+                            soundpack_id,
+                            report.findings.len()
+                        );
+                        // The UI follows through the cache generation, no rescan needed
+                        let _ = tokio::task::spawn_blocking(move ||
+                            store_doctor_report(&soundpack_id, report)
+                        ).await;
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Failed to check soundpack {}: {}", soundpack_id, e);
+                        error.set(Some(e));
+                    }
+                }
+                running.set(false);
+            });
+        }
+    };
+
+    rsx! {
+      dialog { class: "modal", id: "{modal_id}",
+        div { class: "modal-box max-w-2xl",
+          form { method: "dialog",
+            button { class: "btn btn-sm btn-circle btn-ghost absolute right-2 top-2",
+              "✕"
+            }
+          }
+          h3 { class: "text-lg font-bold", "Soundpack doctor" }
+
+          div { class: "space-y-4 mt-6",
+            div { class: "text-sm text-base-content/70",
+              "Checks \"{soundpack_name}\" for missing keys, clipping, DC offset, late starts, clicky cuts and uneven loudness."
+            }
+
+            if let Some(message) = error() {
+              div { class: "flex items-start gap-2 text-sm text-error break-all",
+                TriangleAlert { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                "{message}"
+              }
+            }
+
+            match &report {
+                None => rsx! {
+                  div { class: "text-sm text-base-content/50", "Not checked yet." }
+                },
+                Some(report) if report.findings.is_empty() => rsx! {
+                  div { class: "flex items-start gap-2 text-sm text-success",
+                    Check { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                    "No problems found."
+                  }
+                },
+                Some(report) => rsx! {
+                  div { class: "space-y-3 max-h-80 overflow-y-auto",
+                    for finding in report.findings.iter() {
+                      div { class: "space-y-1",
+                        div { class: "flex items-center gap-2",
+                          span { class: "badge badge-sm badge-warning", "{finding.check.label()}" }
+                          span { class: "text-sm", "{finding.message}" }
+                        }
+                        div { class: "text-xs font-mono text-base-content/50 break-words",
+                          "{finding.keys.join(\", \")}"
+                        }
+                      }
+                    }
+                  }
+                },
+            }
+
+            // Action buttons
+            div { class: "flex justify-end gap-2 pt-2",
+              form { method: "dialog",
+                button { class: "btn btn-ghost", "Close" }
+              }
+              button {
+                class: "btn btn-primary",
+                disabled: running(),
+                onclick: on_run,
+                if running() {
+                  span { class: "loading loading-spinner loading-xs mr-1" }
+                } else {
+                  Stethoscope { class: "w-4 h-4 mr-1" }
+                }
+                "Run checks"
+              }
+            }
+          }
+        }
+        form { method: "dialog", class: "modal-backdrop",
+          button { "close" }
+        }
+      }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::state::paths;
+use crate::utils::soundpack_export::{ export_shareable_zip, ExportLayout };
+use dioxus::prelude::*;
+use lucide_dioxus::{ Check, Package, TriangleAlert };
+use std::path::Path;
+
+#[component]
+pub fn SoundpackExportModal(modal_id: String, soundpack_id: String, soundpack_name: String) -> Element {
+    let mut layout = use_signal(|| ExportLayout::V2);
+    let mut exporting = use_signal(|| false);
+    let mut result = use_signal(|| None::<Result<String, String>>);
+
+    let on_export = {
+        let soundpack_id = soundpack_id.clone();
+        move |_| {
+            let soundpack_id = soundpack_id.clone();
+            spawn(async move {
+                let soundpack_dir = paths::soundpacks::soundpack_dir(&soundpack_id);
+                let folder_name = Path::new(&soundpack_dir)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "soundpack".to_string());
+
+                let file_dialog = rfd::AsyncFileDialog
+                    ::new()
+                    .add_filter("ZIP Files", &["zip"])
+                    .set_title("Export Sound Pack")
+                    .set_file_name(format!("{}.zip", folder_name))
+                    .save_file().await;
+                let Some(file_handle) = file_dialog else {
+                    // User cancelled the dialog
+                    return;
+                };
+
+                exporting.set(true);
+                result.set(None);
+                let output_path = file_handle.path().to_path_buf();
+                match export_shareable_zip(Path::new(&soundpack_dir), &output_path, layout()) {
+                    Ok(report) => {
+                        println!("📦 Exported soundpack {} to {}", soundpack_id, output_path.display());
+                        let mut message = format!(
+                            "Saved {} files to {}",
+                            report.files,
+                            output_path.display()
+                        );
+                        if !report.dropped_keys.is_empty() {
+                            message.push_str(
+                                &format!(
+                                    ". Left out keys Mechvibes does not know: {}",
+                                    report.dropped_keys.join(", ")
+                                )
+                            );
+                        }
+                        result.set(Some(Ok(message)));
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Failed to export soundpack {}: {}", soundpack_id, e);
+                        result.set(Some(Err(e)));
+                    }
+                }
+                exporting.set(false);
+            });
+        }
+    };
+
+    rsx! {
+      dialog { class: "modal", id: "{modal_id}",
+        div { class: "modal-box",
+          form { method: "dialog",
+            button { class: "btn btn-sm btn-circle btn-ghost absolute right-2 top-2",
+              "✕"
+            }
+          }
+          h3 { class: "text-lg font-bold", "Export soundpack" }
+
+          div { class: "space-y-4 mt-6",
+            div { class: "text-sm text-base-content/70",
+              "Save \"{soundpack_name}\" as a ZIP that can be imported again. Only the config and the files it uses are included."
+            }
+            // Layout choice
+            div { class: "space-y-2",
+              label { class: "flex items-start gap-3 cursor-pointer",
+                input {
+                  r#type: "radio",
+                  class: "radio radio-sm mt-0.5",
+                  name: "{modal_id}_layout",
+                  checked: layout() == ExportLayout::V2,
+                  onchange: move |_| layout.set(ExportLayout::V2),
+                }
+                div {
+                  div { class: "text-sm font-medium", "MechAura (V2)" }
+                  div { class: "text-xs text-base-content/60",
+                    "Keeps keyup sounds and mouse or gamepad buttons"
+                  }
+                }
+              }
+              label { class: "flex items-start gap-3 cursor-pointer",
+                input {
+                  r#type: "radio",
+                  class: "radio radio-sm mt-0.5",
+                  name: "{modal_id}_layout",
+                  checked: layout() == ExportLayout::MechvibesV1,
+                  onchange: move |_| layout.set(ExportLayout::MechvibesV1),
+                }
+                div {
+                  div { class: "text-sm font-medium", "Mechvibes compatible (V1)" }
+                  div { class: "text-xs text-base-content/60",
+                    "Works in Mechvibes too, keyboard packs only and keydown sounds only"
+                  }
+                }
+              }
+            }
+
+            if let Some(outcome) = result() {
+              match outcome {
+                  Ok(message) => rsx! {
+                    div { class: "flex items-start gap-2 text-sm text-success break-all",
+                      Check { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                      "{message}"
+                    }
+                  },
+                  Err(message) => rsx! {
+                    div { class: "flex items-start gap-2 text-sm text-error break-all",
+                      TriangleAlert { class: "w-4 h-4 flex-shrink-0 mt-0.5" }
+                      "{message}"
+                    }
+                  },
+              }
+            }
+
+            // Action buttons
+            div { class: "flex justify-end gap-2 pt-2",
+              form { method: "dialog",
+                button { class: "btn btn-ghost", "Close" }
+              }
+              button {
+                class: "btn btn-primary",
+                disabled: exporting(),
+                onclick: on_export,
+                if exporting() {
+                  span { class: "loading loading-spinner loading-xs mr-1" }
+                } else {
+                  Package { class: "w-4 h-4 mr-1" }
+                }
+                "Export"
+              }
+            }
+          }
+        }
+        form { method: "dialog", class: "modal-backdrop",
+          button { "close" }
+        }
+      }
+    }
+}
@@ -1,17 +1,27 @@
 use crate::{
     components::ui::{ ImportStep, ProgressStep },
-    state::app::{ use_app_state, use_state_trigger },
+    state::app::{ refresh_cache, use_app_state },
     utils::delay,
     utils::soundpack_installer::{
         check_soundpack_id_conflict,
+        check_zip_timings,
         extract_and_install_soundpack_with_type,
         get_soundpack_id_from_zip,
     },
-    utils::soundpack_validator::{ validate_soundpack_structure, validate_zip_file },
+    utils::soundpack_validator::{ validate_soundpack_structure, validate_zip_file, TimingReport },
 };
 use dioxus::prelude::*;
-use lucide_dioxus::FolderArchive;
+use lucide_dioxus::{ FolderArchive, TriangleAlert };
 use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// What to do with a pack whose timings do not fit its audio
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimingChoice {
+    Cancel,
+    InstallAsIs,
+    Clamp,
+}
 
 #[component]
 pub fn SoundpackImportModal(
@@ -33,9 +43,13 @@ pub fn SoundpackImportModal(
     let file_selected_message = use_signal(|| String::new());
     let installation_success_message = use_signal(|| String::new());
     let finalization_success_message = use_signal(|| String::new());
+    // Timing problems found while validating, shown until the user picks a TimingChoice
+    let timing_report = use_signal(|| None::<TimingReport>);
+    // The waiting import task, the report buttons send the choice to it
+    let timing_reply = use_signal(|| None::<oneshot::Sender<TimingChoice>>);
+    let timing_error = use_signal(|| None::<String>); // The audio could not be checked at all
     // Get app state outside the handler
     let app_state = use_app_state();
-    let state_trigger = use_state_trigger();
 
     // Reset function to clear all states
     let reset_modal = {
@@ -48,6 +62,9 @@ pub fn SoundpackImportModal(
         let mut file_selected_message = file_selected_message.clone();
         let mut installation_success_message = installation_success_message.clone();
         let mut finalization_success_message = finalization_success_message.clone();
+        let mut timing_report = timing_report.clone();
+        let mut timing_error = timing_error.clone();
+        let mut timing_reply = timing_reply.clone();
 
         Callback::new(move |_| {
             is_loading.set(false);
@@ -59,6 +76,10 @@ pub fn SoundpackImportModal(
             file_selected_message.set(String::new());
             installation_success_message.set(String::new());
             finalization_success_message.set(String::new());
+            timing_report.set(None);
+            timing_error.set(None);
+            // Dropping the sender ends an import still waiting for a choice
+            timing_reply.set(None);
         })
     };
 
@@ -72,7 +93,6 @@ pub fn SoundpackImportModal(
     let handle_import_click = {
         let audio_ctx = audio_ctx.clone();
         let app_state = app_state.clone();
-        let state_trigger = state_trigger.clone();
         let reset_modal = reset_modal.clone();
         let target_soundpack_type = target_soundpack_type.clone(); // Clone for closure
         let error_step = error_step.clone();
@@ -86,7 +106,6 @@ pub fn SoundpackImportModal(
             let audio_ctx = audio_ctx.clone();
             let app_state = app_state.clone();
             let on_import_success = on_import_success.clone();
-            let state_trigger = state_trigger.clone();
             let reset_modal = reset_modal.clone();
             let mut error_step = error_step.clone();
             let mut error_message = error_message.clone();
@@ -95,6 +114,9 @@ pub fn SoundpackImportModal(
             let mut current_step = current_step.clone();
             let mut file_selected_message = file_selected_message.clone();
             let mut is_loading = is_loading.clone();
+            let mut timing_report = timing_report.clone();
+            let mut timing_reply = timing_reply.clone();
+            let mut timing_error = timing_error.clone();
 
             spawn(async move {
                 // Reset modal state before starting import
@@ -159,6 +181,45 @@ pub fn SoundpackImportModal(
                     }
                 }
 
+                // Decode the audio and check every segment before anything is installed
+                let mut fix_timings = None::<TimingReport>;
+                let check_path = file_path.clone();
+                let checked = tokio::task
+                    ::spawn_blocking(move || check_zip_timings(&check_path)).await
+                    .unwrap_or_else(|e| Err(format!("Timing check task failed: {}", e)));
+                match checked {
+                    Ok(report) if report.issues.is_empty() => {}
+                    Ok(report) => timing_report.set(Some(report)),
+                    // Audio this check can't decode may still play, so leave it to the user
+                    Err(e) => {
+                        eprintln!("⚠️ Failed to check soundpack timings: {}", e);
+                        timing_error.set(Some(e));
+                    }
+                }
+
+                if timing_report().is_some() || timing_error().is_some() {
+                    // Wait for a choice from the report, closing the modal drops the sender
+                    let (reply, choice) = oneshot::channel();
+                    timing_reply.set(Some(reply));
+                    let Ok(choice) = choice.await else {
+                        return;
+                    };
+                    let report = timing_report.write().take();
+                    timing_error.set(None);
+
+                    match choice {
+                        TimingChoice::Cancel => {
+                            reset_modal.call(());
+                            return;
+                        }
+                        TimingChoice::InstallAsIs => {}
+                        // Clamp with the report the user saw instead of decoding the audio again
+                        TimingChoice::Clamp => {
+                            fix_timings = report;
+                        }
+                    }
+                }
+
                 // =============================================
                 // Step 3: Checking for conflicts
                 // =============================================
@@ -196,7 +257,11 @@ pub fn SoundpackImportModal(
                 println!("⚒️ Installing soundpack ...");
 
                 let soundpack_info = match
-                    extract_and_install_soundpack_with_type(&file_path, target_soundpack_type)
+                    extract_and_install_soundpack_with_type(
+                        &file_path,
+                        target_soundpack_type,
+                        fix_timings.as_ref()
+                    )
                 {
                     Ok(info) => info,
                     Err(e) => {
@@ -223,8 +288,8 @@ pub fn SoundpackImportModal(
                 delay::Delay::ms(500).await;
 
                 // Refresh the soundpack cache to show the new soundpack in the UI
-                println!("🔄 Triggering soundpack cache refresh after import...");
-                state_trigger.call(());
+                println!("🔄 Refreshing soundpack cache after import...");
+                refresh_cache().await;
 
                 // Notify parent component (this will trigger UI update)
                 on_import_success.call(());
@@ -243,6 +308,13 @@ pub fn SoundpackImportModal(
         }
     };
 
+    // Continue the import waiting on the timing report
+    let choose = move |choice: TimingChoice| {
+        if let Some(reply) = timing_reply.clone().write().take() {
+            let _ = reply.send(choice);
+        }
+    };
+
     // Render the modal
     rsx! {
       dialog { class: "modal", id: "{modal_id}",
@@ -333,6 +405,84 @@ pub fn SoundpackImportModal(
                 }
               }
 
+              // Timing check that failed before any segment was looked at
+              if let Some(message) = timing_error() {
+                div { class: "card border border-warning/40 bg-base-200 text-sm p-4 space-y-3",
+                  div { class: "flex items-center gap-2 font-medium",
+                    TriangleAlert { class: "w-4 h-4 text-warning" }
+                    "The timings could not be checked against the audio"
+                  }
+                  div { class: "text-base-content/70 break-all", "{message}" }
+                  div { class: "flex justify-end gap-2",
+                    button {
+                      class: "btn btn-xs btn-ghost",
+                      onclick: move |_| choose(TimingChoice::Cancel),
+                      "Cancel"
+                    }
+                    button {
+                      class: "btn btn-xs btn-soft",
+                      onclick: move |_| choose(TimingChoice::InstallAsIs),
+                      "Install anyway"
+                    }
+                  }
+                }
+              }
+
+              // Per-key timing report
+              if let Some(report) = timing_report() {
+                div { class: "card border border-warning/40 bg-base-200 text-sm p-4 space-y-3",
+                  div { class: "flex items-center gap-2 font-medium",
+                    TriangleAlert { class: "w-4 h-4 text-warning" }
+                    "{report.issues.len()} of {report.segments_checked} segments do not fit the audio"
+                  }
+                  div { class: "overflow-y-auto max-h-48",
+                    table { class: "table table-xs",
+                      thead {
+                        tr {
+                          th { "Key" }
+                          th { "Segment" }
+                          th { "Problem" }
+                          th {}
+                        }
+                      }
+                      tbody {
+                        for issue in report.issues.iter() {
+                          tr {
+                            td { class: "font-mono", "{issue.key}" }
+                            td { if issue.index == 0 { "Down" } else { "Up" } }
+                            td { class: "text-base-content/70", "{issue.message}" }
+                            td {
+                              if issue.clamped_end_ms.is_some() {
+                                span { class: "badge badge-xs badge-success badge-soft", "fixable" }
+                              }
+                            }
+                          }
+                        }
+                      }
+                    }
+                  }
+                  div { class: "flex justify-end gap-2",
+                    button {
+                      class: "btn btn-xs btn-ghost",
+                      onclick: move |_| choose(TimingChoice::Cancel),
+                      "Cancel"
+                    }
+                    button {
+                      class: "btn btn-xs btn-soft",
+                      onclick: move |_| choose(TimingChoice::InstallAsIs),
+                      "Install anyway"
+                    }
+                    if report.fixable_count() > 0 {
+                      button {
+                        class: "btn btn-xs btn-primary",
+                        onclick: move |_| choose(TimingChoice::Clamp),
+                        "Clamp {report.fixable_count()} and install"
+                      }
+                    }
+                  }
+                }
+              }
+
               // Success message display
               if !success_message.read().is_empty() {
                 div { class: "alert alert-success alert-soft",
@@ -9,6 +9,7 @@ pub fn SoundpackManager(on_import_click: EventHandler<MouseEvent>) -> Element {
     let audio_ctx: Arc<crate::libs::audio::AudioContext> = use_context();
     let state_trigger = crate::state::app::use_state_trigger(); // UI state for notification and loading
     let refreshing_soundpacks = use_signal(|| false);
+    let scan_progress = crate::state::app::use_scan_progress();
     let refresh_soundpacks_cache = {
         let audio_ctx_refresh = audio_ctx.clone();
         let mut refreshing_soundpacks = refreshing_soundpacks.clone();
@@ -59,19 +60,21 @@ pub fn SoundpackManager(on_import_click: EventHandler<MouseEvent>) -> Element {
     // Get current counts from cache
     let soundpack_count_keyboard = app_state.optimized_cache.count.keyboard;
     let soundpack_count_mouse = app_state.optimized_cache.count.mouse;
+    let soundpack_count_gamepad = app_state.optimized_cache.count.gamepad;
+    let soundpack_count_total = soundpack_count_keyboard + soundpack_count_mouse + soundpack_count_gamepad;
 
     rsx! {
       div { class: "space-y-4",
         div { class: "text-base-content",
           div {
             div { class: "font-medium text-sm pb-1",
-              if soundpack_count_keyboard + soundpack_count_mouse == 0 {
+              if soundpack_count_total == 0 {
                 "Click refresh to scan for sound packs"
               } else {
-                "Found {soundpack_count_keyboard + soundpack_count_mouse} sound pack(s)"
+                "Found {soundpack_count_total} sound pack(s)"
               }
             }
-            if soundpack_count_keyboard + soundpack_count_mouse > 0 {
+            if soundpack_count_total > 0 {
               ul { class: "list-disc pl-6",
                 li { class: "text-sm text-base-content/70",
                   "Keyboard: {soundpack_count_keyboard}"
@@ -79,6 +82,11 @@ pub fn SoundpackManager(on_import_click: EventHandler<MouseEvent>) -> Element {
                 li { class: "text-sm text-base-content/70",
                   "Mouse: {soundpack_count_mouse}"
                 }
+                if soundpack_count_gamepad > 0 {
+                  li { class: "text-sm text-base-content/70",
+                    "Gamepad: {soundpack_count_gamepad}"
+                  }
+                }
               }
             }
           }
@@ -91,8 +99,11 @@ pub fn SoundpackManager(on_import_click: EventHandler<MouseEvent>) -> Element {
             button {
               class: "btn  btn-soft btn-sm",
               onclick: refresh_soundpacks_cache,
-              disabled: refreshing_soundpacks(),
-              if refreshing_soundpacks() {
+              disabled: refreshing_soundpacks() || scan_progress().is_some(),
+              if let Some(progress) = scan_progress() {
+                span { class: "loading loading-spinner loading-xs mr-2" }
+                "Scanning {progress.scanned}/{progress.total}..."
+              } else if refreshing_soundpacks() {
                 span { class: "loading loading-spinner loading-xs mr-2" }
                 "Refreshing..."
               } else {
@@ -163,6 +174,16 @@ pub fn SoundpackManager(on_import_click: EventHandler<MouseEvent>) -> Element {
               FolderOpen { class: "w-4 h-4 mr-1" }
               "Mouse"
             }
+            button {
+              class: "btn btn-soft btn-sm",
+              onclick: move |_| {
+                  let custom_gamepad_dir = crate::state::paths::soundpacks::get_custom_soundpacks_dir().join("gamepad");
+                  let _ = std::fs::create_dir_all(&custom_gamepad_dir);
+                  let _ = crate::utils::path::open_path(&custom_gamepad_dir.to_string_lossy());
+              },
+              FolderOpen { class: "w-4 h-4 mr-1" }
+              "Gamepad"
+            }
           }
         }
         div { class: "divider" }
@@ -1,13 +1,13 @@
 use crate::state::paths;
 use crate::state::soundpack::SoundpackMetadata;
-use crate::state::{ app::use_state_trigger };
+use crate::state::app::{ refresh_cache, use_state_trigger };
 use crate::utils::path::{ open_path, directory_exists };
 use dioxus::document::eval;
 use dioxus::prelude::*;
-use lucide_dioxus::{ FolderOpen, Music, Plus, RefreshCw, Trash };
+use lucide_dioxus::{ AudioWaveform, FolderOpen, Music, Package, Plus, RefreshCw, Stethoscope, Trash };
 use std::sync::Arc;
 
-use super::ConfirmDeleteModal;
+use super::{ ConfirmDeleteModal, SoundpackDoctorModal, SoundpackExportModal };
 
 /// Open a soundpack folder in the system file manager
 /// Opens the specific soundpack folder
@@ -51,6 +51,13 @@ fn delete_soundpack(soundpack_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Only custom packs with one audio file can be opened in the segment editor, built-in ones
+/// may be read-only (e.g. under /usr/share)
+fn can_edit_segments(soundpack: &SoundpackMetadata) -> bool {
+    !paths::soundpacks::is_builtin_soundpack(&soundpack.id) &&
+        soundpack.definition_method.as_deref() == Some("single")
+}
+
 #[component]
 pub fn SoundpackTable(
     soundpacks: Vec<SoundpackMetadata>,
@@ -62,7 +69,6 @@ pub fn SoundpackTable(
 
     // Refresh state
     let refreshing_soundpacks = use_signal(|| false);
-    let state_trigger = use_state_trigger();
     let audio_ctx: Arc<crate::libs::audio::AudioContext> = use_context();
 
     // Filter soundpacks based on search query - computed every render to be reactive to props changes
@@ -88,7 +94,6 @@ pub fn SoundpackTable(
     let refresh_soundpacks_cache = {
         let audio_ctx_refresh = audio_ctx.clone();
         let refreshing_soundpacks = refreshing_soundpacks.clone();
-        let state_trigger_clone = state_trigger.clone();
         Callback::new(move |_| {
             // Prevent multiple concurrent refreshes
             if refreshing_soundpacks() {
@@ -98,7 +103,6 @@ pub fn SoundpackTable(
 
             let audio_ctx = audio_ctx_refresh.clone();
             let mut refreshing_soundpacks = refreshing_soundpacks.clone();
-            let state_trigger = state_trigger_clone.clone();
 
             spawn(async move {
                 refreshing_soundpacks.set(true);
@@ -107,8 +111,8 @@ pub fn SoundpackTable(
                 // Reload soundpacks in audio context
                 crate::state::app::reload_current_soundpacks(&audio_ctx);
 
-                // Trigger state update to refresh UI
-                state_trigger.call(());
+                // Rescan before clearing the spinner, the UI follows once the scan is done
+                refresh_cache().await;
 
                 println!("✅ Soundpack cache refreshed");
                 refreshing_soundpacks.set(false);
@@ -175,6 +179,7 @@ pub fn SoundpackTable(
 #[component]
 pub fn SoundpackTableRow(soundpack: SoundpackMetadata) -> Element {
     let state_trigger = use_state_trigger();
+    let nav = navigator();
 
     // Handlers for button clicks
     let on_open_folder = {
@@ -264,17 +269,79 @@ pub fn SoundpackTableRow(soundpack: SoundpackMetadata) -> Element {
             if let Some(author) = &soundpack.author {
               div { class: "text-xs text-base-content/50", "by {author}" }
             }
+            // Doctor results from the last check
+            if let Some(report) = &soundpack.doctor {
+              div { class: "flex flex-wrap gap-1 mt-1",
+                if report.findings.is_empty() {
+                  span { class: "badge badge-xs badge-success", "healthy" }
+                }
+                for finding in report.findings.iter() {
+                  span {
+                    class: "badge badge-xs badge-warning",
+                    title: "{finding.message}",
+                    "{finding.check.label()}"
+                  }
+                }
+              }
+            }
           }
         }
         // Actions
         td {
           div { class: "flex items-center justify-end gap-1",
+            if can_edit_segments(&soundpack) {
+              button {
+                class: "btn btn-soft btn-xs",
+                title: "Edit segments",
+                onclick: {
+                    let soundpack_id = soundpack.id.clone();
+                    move |_| {
+                        nav.push(crate::libs::routes::Route::SoundpackEditor {
+                            segments: soundpack_id.split('/').map(String::from).collect(),
+                        });
+                    }
+                },
+                AudioWaveform { class: "w-4 h-4" }
+              }
+            }
             button {
               class: "btn btn-soft btn-xs",
               title: "Open soundpack folder",
               onclick: on_open_folder,
               FolderOpen { class: "w-4 h-4" }
             }
+            button {
+              class: "btn btn-soft btn-xs",
+              title: "Export as ZIP",
+              onclick: {
+                  let soundpack_id = soundpack.id.clone();
+                  move |_| {
+                      eval(
+                          &format!(
+                              "document.getElementById(\"export_modal_{}\").showModal()",
+                              soundpack_id,
+                          ),
+                      );
+                  }
+              },
+              Package { class: "w-4 h-4" }
+            }
+            button {
+              class: "btn btn-soft btn-xs",
+              title: "Check sound quality",
+              onclick: {
+                  let soundpack_id = soundpack.id.clone();
+                  move |_| {
+                      eval(
+                          &format!(
+                              "document.getElementById(\"doctor_modal_{}\").showModal()",
+                              soundpack_id,
+                          ),
+                      );
+                  }
+              },
+              Stethoscope { class: "w-4 h-4" }
+            }
             button {
               class: "btn btn-soft btn-error btn-xs",
               title: "Delete this soundpack",
@@ -291,6 +358,19 @@ pub fn SoundpackTableRow(soundpack: SoundpackMetadata) -> Element {
           }
         }
       }
+      // Export options
+      SoundpackExportModal {
+        modal_id: format!("export_modal_{}", soundpack.id),
+        soundpack_id: soundpack.id.clone(),
+        soundpack_name: soundpack.name.clone(),
+      }
+      // Quality report
+      SoundpackDoctorModal {
+        modal_id: format!("doctor_modal_{}", soundpack.id),
+        soundpack_id: soundpack.id.clone(),
+        soundpack_name: soundpack.name.clone(),
+        report: soundpack.doctor.clone(),
+      }
       // Delete confirmation modal
       ConfirmDeleteModal {
         modal_id: format!("confirm_delete_modal_{}", soundpack.id),
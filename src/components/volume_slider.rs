@@ -6,6 +6,7 @@ use lucide_dioxus::{ Volume2, VolumeOff };
 pub enum VolumeType {
     Keyboard, // Controls enable_keyboard_sound
     Mouse, // Controls enable_mouse_sound
+    Gamepad, // Controls enable_gamepad_sound
 }
 
 #[component]
@@ -24,6 +25,7 @@ fn VolumeSliderBase(
         match volume_type {
             VolumeType::Keyboard => config.enable_keyboard_sound,
             VolumeType::Mouse => config.enable_mouse_sound,
+            VolumeType::Gamepad => config.enable_gamepad_sound,
         }
     });
 
@@ -114,6 +116,15 @@ fn VolumeSliderBase(
                                   }),
                               );
                           }
+                          VolumeType::Gamepad => {
+                              let config = config();
+                              let new_enable_gamepad = !config.enable_gamepad_sound;
+                              update_config(
+                                  Box::new(move |config| {
+                                      config.enable_gamepad_sound = new_enable_gamepad;
+                                  }),
+                              );
+                          }
                       }
                   }
               },
@@ -164,3 +175,15 @@ pub fn KeyboardVolumeSlider(volume: Signal<f32>, on_change: Option<EventHandler<
         }
     }
 }
+
+#[component]
+pub fn GamepadVolumeSlider(volume: Signal<f32>, on_change: Option<EventHandler<f32>>) -> Element {
+    rsx! {
+        VolumeSliderBase {
+            volume,
+            on_change,
+            id: "gamepad-volume-slider".to_string(),
+            volume_type: VolumeType::Gamepad,
+        }
+    }
+}
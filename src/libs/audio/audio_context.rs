@@ -1,5 +1,6 @@
 use crate::state::config::AppConfig;
 use crate::libs::device_manager::DeviceManager;
+use crate::utils::segment_edges::SegmentFades;
 use rodio::{ OutputStream, OutputStreamHandle, Sink };
 use std::collections::HashMap;
 use std::sync::{ Arc, Mutex };
@@ -7,6 +8,14 @@ use std::time::Instant;
 
 static AUDIO_VOLUME: std::sync::OnceLock<Mutex<f32>> = std::sync::OnceLock::new();
 static MOUSE_AUDIO_VOLUME: std::sync::OnceLock<Mutex<f32>> = std::sync::OnceLock::new();
+static GAMEPAD_AUDIO_VOLUME: std::sync::OnceLock<Mutex<f32>> = std::sync::OnceLock::new();
+
+/// A soundpack loaded for a specific input device (see `AppConfig::device_soundpacks`)
+pub(crate) struct DeviceSoundpack {
+    pub(crate) samples: (Vec<f32>, u16, u32),
+    pub(crate) map: HashMap<String, Vec<[f32; 2]>>,
+    pub(crate) fades: SegmentFades,
+}
 
 #[derive(Clone)]
 pub struct AudioContext {
@@ -16,11 +25,21 @@ pub struct AudioContext {
     pub(crate) mouse_samples: Arc<Mutex<Option<(Vec<f32>, u16, u32)>>>,
     pub(crate) key_map: Arc<Mutex<HashMap<String, Vec<[f32; 2]>>>>,
     pub(crate) mouse_map: Arc<Mutex<HashMap<String, Vec<[f32; 2]>>>>,
+    pub(crate) gamepad_samples: Arc<Mutex<Option<(Vec<f32>, u16, u32)>>>,
+    pub(crate) gamepad_map: Arc<Mutex<HashMap<String, Vec<[f32; 2]>>>>,
+    // Edge ramps of the currently loaded packs
+    pub(crate) keyboard_fades: Arc<Mutex<SegmentFades>>,
+    pub(crate) mouse_fades: Arc<Mutex<SegmentFades>>,
+    pub(crate) gamepad_fades: Arc<Mutex<SegmentFades>>,
+    // Per-device soundpacks, keyed by soundpack ID
+    pub(crate) device_packs: Arc<Mutex<HashMap<String, Arc<DeviceSoundpack>>>>,
     pub(crate) max_voices: usize,
     pub(crate) key_pressed: Arc<Mutex<HashMap<String, bool>>>,
     pub(crate) mouse_pressed: Arc<Mutex<HashMap<String, bool>>>,
+    pub(crate) gamepad_pressed: Arc<Mutex<HashMap<String, bool>>>,
     pub(crate) key_sinks: Arc<Mutex<HashMap<String, Sink>>>,
     pub(crate) mouse_sinks: Arc<Mutex<HashMap<String, Sink>>>,
+    pub(crate) gamepad_sinks: Arc<Mutex<HashMap<String, Sink>>>,
     pub(crate) device_manager: DeviceManager,
     // Timing tracking for rapid event detection
     pub(crate) last_keyboard_sound_time: Arc<Mutex<Option<Instant>>>,
@@ -89,11 +108,19 @@ impl AudioContext {
             mouse_samples: Arc::new(Mutex::new(None)),
             key_map: Arc::new(Mutex::new(HashMap::new())),
             mouse_map: Arc::new(Mutex::new(HashMap::new())),
+            gamepad_samples: Arc::new(Mutex::new(None)),
+            gamepad_map: Arc::new(Mutex::new(HashMap::new())),
+            keyboard_fades: Arc::new(Mutex::new(SegmentFades::default())),
+            mouse_fades: Arc::new(Mutex::new(SegmentFades::default())),
+            gamepad_fades: Arc::new(Mutex::new(SegmentFades::default())),
+            device_packs: Arc::new(Mutex::new(HashMap::new())),
             max_voices: 20, // Increased max voices to reduce audio interruptions
             key_pressed: Arc::new(Mutex::new(HashMap::new())),
             mouse_pressed: Arc::new(Mutex::new(HashMap::new())),
+            gamepad_pressed: Arc::new(Mutex::new(HashMap::new())),
             key_sinks: Arc::new(Mutex::new(HashMap::new())),
             mouse_sinks: Arc::new(Mutex::new(HashMap::new())),
+            gamepad_sinks: Arc::new(Mutex::new(HashMap::new())),
             device_manager,
             last_keyboard_sound_time: Arc::new(Mutex::new(None)),
             last_mouse_sound_time: Arc::new(Mutex::new(None)),
@@ -102,6 +129,7 @@ impl AudioContext {
         let config = AppConfig::load();
         AUDIO_VOLUME.get_or_init(|| Mutex::new(config.volume));
         MOUSE_AUDIO_VOLUME.get_or_init(|| Mutex::new(config.mouse_volume));
+        GAMEPAD_AUDIO_VOLUME.get_or_init(|| Mutex::new(config.gamepad_volume));
 
         // Load soundpack from config
         match super::soundpack_loader::load_soundpack(&context) {
@@ -162,6 +190,29 @@ impl AudioContext {
             .map(|v| *v)
             .unwrap_or(1.0)
     }
+    pub fn set_gamepad_volume(&self, volume: f32) {
+        let gamepad_sinks = self.gamepad_sinks.lock().unwrap();
+        for sink in gamepad_sinks.values() {
+            sink.set_volume(volume);
+        }
+
+        if let Some(global) = GAMEPAD_AUDIO_VOLUME.get() {
+            let mut g = global.lock().unwrap();
+            *g = volume;
+        }
+
+        let mut config = AppConfig::load();
+        config.gamepad_volume = volume;
+        let _ = config.save();
+    }
+
+    pub fn get_gamepad_volume(&self) -> f32 {
+        GAMEPAD_AUDIO_VOLUME.get()
+            .and_then(|v| v.lock().ok())
+            .map(|v| *v)
+            .unwrap_or(1.0)
+    }
+
     pub fn create_with_device(device_id: Option<String>) -> Result<Self, String> {
         // Initialize device manager
         let device_manager = DeviceManager::new();
@@ -203,11 +254,19 @@ impl AudioContext {
             mouse_samples: Arc::new(Mutex::new(None)),
             key_map: Arc::new(Mutex::new(HashMap::new())),
             mouse_map: Arc::new(Mutex::new(HashMap::new())),
+            gamepad_samples: Arc::new(Mutex::new(None)),
+            gamepad_map: Arc::new(Mutex::new(HashMap::new())),
+            keyboard_fades: Arc::new(Mutex::new(SegmentFades::default())),
+            mouse_fades: Arc::new(Mutex::new(SegmentFades::default())),
+            gamepad_fades: Arc::new(Mutex::new(SegmentFades::default())),
+            device_packs: Arc::new(Mutex::new(HashMap::new())),
             max_voices: 20, // Increased max voices to reduce audio interruptions
             key_pressed: Arc::new(Mutex::new(HashMap::new())),
             mouse_pressed: Arc::new(Mutex::new(HashMap::new())),
+            gamepad_pressed: Arc::new(Mutex::new(HashMap::new())),
             key_sinks: Arc::new(Mutex::new(HashMap::new())),
             mouse_sinks: Arc::new(Mutex::new(HashMap::new())),
+            gamepad_sinks: Arc::new(Mutex::new(HashMap::new())),
             device_manager,
             last_keyboard_sound_time: Arc::new(Mutex::new(None)),
             last_mouse_sound_time: Arc::new(Mutex::new(None)),
@@ -216,7 +275,8 @@ impl AudioContext {
         // Initialize volume from config
         let config = AppConfig::load();
         AUDIO_VOLUME.get_or_init(|| Mutex::new(config.volume));
-        MOUSE_AUDIO_VOLUME.get_or_init(|| Mutex::new(config.mouse_volume)); // Load soundpack from config
+        MOUSE_AUDIO_VOLUME.get_or_init(|| Mutex::new(config.mouse_volume));
+        GAMEPAD_AUDIO_VOLUME.get_or_init(|| Mutex::new(config.gamepad_volume)); // Load soundpack from config
         match super::soundpack_loader::load_soundpack(&context) {
             Ok(_) => {}
             Err(e) => eprintln!("❌ Failed to load initial soundpack: {}", e),
@@ -4,4 +4,9 @@ pub mod soundpack_loader;
 pub mod music_player;
 
 pub use audio_context::AudioContext;
-pub use soundpack_loader::{ load_keyboard_soundpack, load_mouse_soundpack };
+pub use soundpack_loader::{
+    load_device_soundpacks,
+    load_gamepad_soundpack,
+    load_keyboard_soundpack,
+    load_mouse_soundpack,
+};
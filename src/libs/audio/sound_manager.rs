@@ -2,11 +2,30 @@ use rodio::buffer::SamplesBuffer;
 use rodio::Sink;
 use std::collections::HashMap;
 
-use super::audio_context::AudioContext;
+use std::sync::{ Arc, Mutex };
+
+use super::audio_context::{ AudioContext, DeviceSoundpack };
 use crate::state::config::AppConfig;
+use crate::utils::segment_edges::SegmentFades;
+
+/// Pressed-state key, scoped to the source device when it is known
+fn device_state_key(code: &str, device_id: Option<&str>) -> String {
+    match device_id {
+        Some(id) => format!("{}@{}", code, id),
+        None => code.to_string(),
+    }
+}
+
+/// Where a segment plays: its volume, the sinks it joins and the pressed state reset on eviction
+struct SegmentOutput<'a> {
+    volume: f32,
+    sinks: &'a Mutex<HashMap<String, Sink>>,
+    pressed: &'a Mutex<HashMap<String, bool>>,
+}
 
 impl AudioContext {
-    pub fn play_key_event_sound(&self, key: &str, is_keydown: bool) {
+    /// Play a key sound, using the soundpack assigned to `device_id` if there is one
+    pub fn play_key_event_sound(&self, key: &str, is_keydown: bool, device_id: Option<&str>) {
         // println!(
         //     "⌨️ Key event received: {} ({})",
         //     key,
@@ -19,20 +38,34 @@ impl AudioContext {
             return;
         }
 
+        // Track pressed state per device so the same key on two keyboards doesn't block itself
+        let state_key = device_state_key(key, device_id);
         let mut pressed = self.key_pressed.lock().unwrap();
         if is_keydown {
-            if *pressed.get(key).unwrap_or(&false) {
+            if *pressed.get(&state_key).unwrap_or(&false) {
                 return;
             }
-            pressed.insert(key.to_string(), true);
+            pressed.insert(state_key.clone(), true);
         } else {
-            if !*pressed.get(key).unwrap_or(&false) {
+            if !*pressed.get(&state_key).unwrap_or(&false) {
                 return;
             }
-            pressed.insert(key.to_string(), false);
+            pressed.insert(state_key.clone(), false);
         }
-        drop(pressed); // Get timestamp and end time
-        let key_map = self.key_map.lock().unwrap();
+        drop(pressed);
+
+        // Devices with an assigned soundpack play from it, everything else uses the global one
+        let device_pack = self.device_soundpack(&config, device_id);
+        let global_map;
+        let key_map = match &device_pack {
+            Some(pack) => &pack.map,
+            None => {
+                global_map = self.key_map.lock().unwrap();
+                &*global_map
+            }
+        };
+
+        // Get timestamp and end time
         let (start, end) = match key_map.get(key) {
             Some(arr) if arr.len() == 2 => {
                 let idx = if is_keydown { 0 } else { 1 };
@@ -99,12 +132,39 @@ impl AudioContext {
                 return;
             }
         };
-        drop(key_map);
 
-        self.play_sound_segment(key, start, end, is_keydown);
+        let (pcm_opt, fades) = match &device_pack {
+            Some(pack) => (Some(pack.samples.clone()), pack.fades),
+            None => (self.keyboard_samples.lock().unwrap().clone(), *self.keyboard_fades.lock().unwrap()),
+        };
+        let output = SegmentOutput {
+            volume: self.get_volume(),
+            sinks: &self.key_sinks,
+            pressed: &self.key_pressed,
+        };
+        self.play_sound_segment(pcm_opt, fades, &state_key, [start, end], is_keydown, output);
+    }
+
+    /// Look up the loaded soundpack assigned to an input device
+    fn device_soundpack(
+        &self,
+        config: &AppConfig,
+        device_id: Option<&str>
+    ) -> Option<Arc<DeviceSoundpack>> {
+        let pack_id = config.device_soundpacks.get(device_id?)?;
+        self.device_packs.lock().unwrap().get(pack_id).cloned()
     }
-    fn play_sound_segment(&self, key: &str, start: f32, end: f32, is_keydown: bool) {
-        let pcm_opt = self.keyboard_samples.lock().unwrap().clone();
+
+    /// Play `[start, end]` ms of a keyboard or gamepad sound
+    fn play_sound_segment(
+        &self,
+        pcm_opt: Option<(Vec<f32>, u16, u32)>,
+        fades: SegmentFades,
+        key: &str,
+        [start, end]: [f32; 2],
+        is_keydown: bool,
+        output: SegmentOutput
+    ) {
         if let Some((samples, channels, sample_rate)) = pcm_opt {
             // Calculate total audio duration in milliseconds
             let total_duration =
@@ -176,15 +236,16 @@ impl AudioContext {
 
                 // Use clamped values if they're reasonable
                 if clamped_duration > 1.0 && clamped_end_sample > start_sample {
-                    let segment_samples = samples[start_sample..clamped_end_sample].to_vec();
+                    let mut segment_samples = samples[start_sample..clamped_end_sample].to_vec();
+                    fades.apply(&mut segment_samples, channels, sample_rate);
                     let segment = SamplesBuffer::new(channels, sample_rate, segment_samples);
 
                     if let Ok(sink) = Sink::try_new(&self.stream_handle) {
-                        sink.set_volume(self.get_volume());
+                        sink.set_volume(output.volume);
                         sink.append(segment);
 
-                        let mut key_sinks = self.key_sinks.lock().unwrap();
-                        self.manage_active_sinks(&mut key_sinks);
+                        let mut key_sinks = output.sinks.lock().unwrap();
+                        self.manage_active_sinks(&mut key_sinks, output.pressed);
                         key_sinks.insert(
                             format!("{}-{}", key, if is_keydown { "down" } else { "up" }),
                             sink
@@ -214,26 +275,31 @@ impl AudioContext {
                 return;
             }
 
-            let segment_samples = samples[start_sample..end_sample].to_vec();
+            let mut segment_samples = samples[start_sample..end_sample].to_vec();
+            fades.apply(&mut segment_samples, channels, sample_rate);
             let segment = SamplesBuffer::new(channels, sample_rate, segment_samples);
 
             if let Ok(sink) = Sink::try_new(&self.stream_handle) {
-                sink.set_volume(self.get_volume());
+                sink.set_volume(output.volume);
                 sink.append(segment);
 
-                let mut key_sinks = self.key_sinks.lock().unwrap();
-                self.manage_active_sinks(&mut key_sinks);
+                let mut key_sinks = output.sinks.lock().unwrap();
+                self.manage_active_sinks(&mut key_sinks, output.pressed);
                 key_sinks.insert(
                     format!("{}-{}", key, if is_keydown { "down" } else { "up" }),
                     sink
                 );
             }
         } else {
-            eprintln!("❌ No keyboard PCM buffer available");
+            eprintln!("❌ No PCM buffer available for '{}'", key);
         }
     }
 
-    fn manage_active_sinks(&self, key_sinks: &mut std::sync::MutexGuard<HashMap<String, Sink>>) {
+    fn manage_active_sinks(
+        &self,
+        key_sinks: &mut std::sync::MutexGuard<HashMap<String, Sink>>,
+        key_pressed: &Mutex<HashMap<String, bool>>
+    ) {
         // First, clean up finished sinks (those that have stopped playing)
         let finished_keys: Vec<String> = key_sinks
             .iter()
@@ -250,35 +316,58 @@ impl AudioContext {
             // Find the oldest sink (first in iteration order) and remove it
             if let Some((old_key, _)) = key_sinks.iter().next().map(|(k, _)| (k.clone(), ())) {
                 key_sinks.remove(&old_key);
-                let mut pressed = self.key_pressed.lock().unwrap();
+                let mut pressed = key_pressed.lock().unwrap();
                 pressed.insert(old_key, false);
             }
         }
     }
 
-    pub fn play_mouse_event_sound(&self, button: &str, is_buttondown: bool) {
+    /// Play a mouse sound, using the soundpack assigned to `device_id` if there is one
+    pub fn play_mouse_event_sound(
+        &self,
+        button: &str,
+        is_buttondown: bool,
+        device_id: Option<&str>
+    ) {
         // Check enable_sound from config before playing audio
         let config = AppConfig::load();
         if !config.enable_sound || !config.enable_mouse_sound {
             return;
         }
 
-        let mut pressed = self.mouse_pressed.lock().unwrap();
-        if is_buttondown {
-            if *pressed.get(button).unwrap_or(&false) {
-                return;
-            }
-            pressed.insert(button.to_string(), true);
-        } else {
-            if !*pressed.get(button).unwrap_or(&false) {
-                return;
+        // Wheel notches are momentary: no release event, so skip pressed-state tracking
+        let is_wheel = button.starts_with("MouseWheel");
+        if is_wheel && !is_buttondown {
+            return;
+        }
+
+        let state_key = device_state_key(button, device_id);
+        if !is_wheel {
+            let mut pressed = self.mouse_pressed.lock().unwrap();
+            if is_buttondown {
+                if *pressed.get(&state_key).unwrap_or(&false) {
+                    return;
+                }
+                pressed.insert(state_key.clone(), true);
+            } else {
+                if !*pressed.get(&state_key).unwrap_or(&false) {
+                    return;
+                }
+                pressed.insert(state_key.clone(), false);
             }
-            pressed.insert(button.to_string(), false);
         }
-        drop(pressed);
+
+        let device_pack = self.device_soundpack(&config, device_id);
+        let global_map;
+        let mouse_map = match &device_pack {
+            Some(pack) => &pack.map,
+            None => {
+                global_map = self.mouse_map.lock().unwrap();
+                &*global_map
+            }
+        };
 
         // Get timestamp and duration
-        let mouse_map = self.mouse_map.lock().unwrap();
         let (start, duration) = match mouse_map.get(button) {
             Some(arr) if arr.len() == 2 => {
                 let idx = if is_buttondown { 0 } else { 1 };
@@ -312,19 +401,23 @@ impl AudioContext {
                 return;
             }
         };
-        drop(mouse_map);
 
-        self.play_mouse_sound_segment(button, start, duration, is_buttondown);
+        let (pcm_opt, fades) = match &device_pack {
+            Some(pack) => (Some(pack.samples.clone()), pack.fades),
+            None => (self.mouse_samples.lock().unwrap().clone(), *self.mouse_fades.lock().unwrap()),
+        };
+        self.play_mouse_sound_segment(pcm_opt, fades, &state_key, start, duration, is_buttondown);
     }
 
     fn play_mouse_sound_segment(
         &self,
+        pcm_opt: Option<(Vec<f32>, u16, u32)>,
+        fades: SegmentFades,
         button: &str,
         start: f32,
         duration: f32,
         is_buttondown: bool
     ) {
-        let pcm_opt = self.mouse_samples.lock().unwrap().clone();
         if let Some((samples, channels, sample_rate)) = pcm_opt {
             // Calculate total audio duration in milliseconds
             let total_duration =
@@ -405,7 +498,8 @@ impl AudioContext {
                 return;
             }
 
-            let segment_samples = samples[start_sample..end_sample].to_vec();
+            let mut segment_samples = samples[start_sample..end_sample].to_vec();
+            fades.apply(&mut segment_samples, channels, sample_rate);
             let segment = SamplesBuffer::new(channels, sample_rate, segment_samples);
 
             if let Ok(sink) = Sink::try_new(&self.stream_handle) {
@@ -450,6 +544,54 @@ impl AudioContext {
         }
     }
 
+    /// Play a gamepad button sound, using the soundpack assigned to `device_id` if there is one
+    pub fn play_gamepad_event_sound(&self, button: &str, is_down: bool, device_id: Option<&str>) {
+        let config = AppConfig::load();
+        if !config.enable_sound || !config.enable_gamepad_sound {
+            return;
+        }
+
+        let state_key = device_state_key(button, device_id);
+        {
+            let mut pressed = self.gamepad_pressed.lock().unwrap();
+            let was_down = *pressed.get(&state_key).unwrap_or(&false);
+            if was_down == is_down {
+                return;
+            }
+            pressed.insert(state_key.clone(), is_down);
+        }
+
+        let device_pack = self.device_soundpack(&config, device_id);
+        let global_map;
+        let gamepad_map = match &device_pack {
+            Some(pack) => &pack.map,
+            None => {
+                global_map = self.gamepad_map.lock().unwrap();
+                &*global_map
+            }
+        };
+
+        // Same layout as keys: [press] or [press, release]
+        let [start, end] = match gamepad_map.get(button) {
+            Some(arr) if arr.len() == 2 => arr[if is_down { 0 } else { 1 }],
+            Some(arr) if arr.len() == 1 && is_down => arr[0],
+            _ => {
+                return;
+            }
+        };
+
+        let (pcm_opt, fades) = match &device_pack {
+            Some(pack) => (Some(pack.samples.clone()), pack.fades),
+            None => (self.gamepad_samples.lock().unwrap().clone(), *self.gamepad_fades.lock().unwrap()),
+        };
+        let output = SegmentOutput {
+            volume: self.get_gamepad_volume(),
+            sinks: &self.gamepad_sinks,
+            pressed: &self.gamepad_pressed,
+        };
+        self.play_sound_segment(pcm_opt, fades, &state_key, [start, end], is_down, output);
+    }
+
     /// Clean up finished sinks to prevent memory leaks and improve performance
     pub fn cleanup_finished_sinks(&self) {
         // Clean up finished keyboard sinks
@@ -477,5 +619,10 @@ impl AudioContext {
                 mouse_sinks.remove(&button);
             }
         }
+
+        // Clean up finished gamepad sinks
+        if let Ok(mut gamepad_sinks) = self.gamepad_sinks.lock() {
+            gamepad_sinks.retain(|_, sink| !sink.empty());
+        }
     }
 }
@@ -2,19 +2,14 @@ use crate::state::config::AppConfig;
 use crate::state::paths;
 use crate::state::soundpack::SoundPack;
 use crate::state::soundpack::{ SoundpackCache, SoundpackMetadata };
+use crate::utils::key_segmentation::mix_to_mono;
+use crate::utils::segment_edges::{ snap_timing, SegmentFades };
 
-use super::audio_context::AudioContext;
+use super::audio_context::{ AudioContext, DeviceSoundpack };
 
 /// Determine soundpack type based on the soundpack path
 fn determine_soundpack_type(soundpack_id: &str) -> crate::state::soundpack::SoundpackType {
-    if soundpack_id.starts_with("keyboard/") || soundpack_id.starts_with("keyboard\\") {
-        crate::state::soundpack::SoundpackType::Keyboard
-    } else if soundpack_id.starts_with("mouse/") || soundpack_id.starts_with("mouse\\") {
-        crate::state::soundpack::SoundpackType::Mouse
-    } else {
-        // Default to keyboard for backwards compatibility
-        crate::state::soundpack::SoundpackType::Keyboard
-    }
+    crate::state::soundpack::SoundpackType::from_soundpack_id(soundpack_id)
 }
 
 pub fn load_soundpack(context: &AudioContext) -> Result<(), String> {
@@ -22,9 +17,79 @@ pub fn load_soundpack(context: &AudioContext) -> Result<(), String> {
     // Load both keyboard and mouse soundpacks
     load_keyboard_soundpack(context, &config.keyboard_soundpack)?;
     load_mouse_soundpack(context, &config.mouse_soundpack)?;
+    if let Err(e) = load_gamepad_soundpack(context, &config.gamepad_soundpack) {
+        eprintln!("❌ Failed to load gamepad soundpack: {}", e);
+    }
+    if let Err(e) = load_device_soundpacks(context) {
+        eprintln!("❌ Failed to load per-device soundpacks: {}", e);
+    }
     Ok(())
 }
 
+/// Load every soundpack assigned to an input device in `AppConfig::device_soundpacks`
+/// and drop the ones that are no longer assigned
+pub fn load_device_soundpacks(context: &AudioContext) -> Result<(), String> {
+    let config = AppConfig::load();
+    let wanted: std::collections::HashSet<&String> = config.device_soundpacks.values().collect();
+
+    let mut device_packs = context.device_packs
+        .lock()
+        .map_err(|_| "Failed to acquire lock on device_packs".to_string())?;
+    device_packs.retain(|id, _| wanted.contains(id));
+
+    let mut errors = Vec::new();
+    for soundpack_id in wanted {
+        if soundpack_id.is_empty() || device_packs.contains_key(soundpack_id) {
+            continue;
+        }
+
+        match load_device_soundpack(soundpack_id) {
+            Ok(pack) => {
+                println!("🎛️ Loaded device soundpack: {}", soundpack_id);
+                device_packs.insert(soundpack_id.clone(), std::sync::Arc::new(pack));
+            }
+            Err(e) => errors.push(format!("{}: {}", soundpack_id, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn load_device_soundpack(soundpack_id: &str) -> Result<DeviceSoundpack, String> {
+    let soundpack_path = paths::soundpacks::soundpack_dir(soundpack_id);
+    let config_content = std::fs
+        ::read_to_string(paths::soundpacks::config_json(soundpack_id))
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    let mut soundpack: SoundPack = serde_json
+        ::from_str(&config_content)
+        .map_err(|e| format!("Failed to parse V2 soundpack config: {}", e))?;
+    soundpack.soundpack_type = determine_soundpack_type(soundpack_id);
+
+    let samples = load_audio_file(&soundpack_path, &soundpack)?;
+    let mut mappings = match soundpack.soundpack_type {
+        crate::state::soundpack::SoundpackType::Keyboard => create_key_mappings(&soundpack, &samples.0),
+        crate::state::soundpack::SoundpackType::Mouse => create_mouse_mappings(&soundpack, &samples.0),
+        crate::state::soundpack::SoundpackType::Gamepad => create_gamepad_mappings(&soundpack),
+    };
+    snap_mappings(&mut mappings, &samples, &soundpack);
+    let map = mappings
+        .into_iter()
+        .map(|(key, pairs)| {
+            let converted: Vec<[f32; 2]> = pairs
+                .into_iter()
+                .map(|(start, end)| [start as f32, end as f32])
+                .collect();
+            (key, converted)
+        })
+        .collect();
+
+    Ok(DeviceSoundpack { samples, map, fades: SegmentFades::from_options(&soundpack.options) })
+}
+
 pub fn load_keyboard_soundpack(context: &AudioContext, soundpack_id: &str) -> Result<(), String> {
     load_keyboard_soundpack_with_cache_control(context, soundpack_id, true)
 }
@@ -79,6 +144,32 @@ pub fn load_mouse_soundpack_with_cache_control(
     }
 }
 
+pub fn load_gamepad_soundpack(context: &AudioContext, soundpack_id: &str) -> Result<(), String> {
+    load_gamepad_soundpack_with_cache_control(context, soundpack_id, true)
+}
+
+pub fn load_gamepad_soundpack_with_cache_control(
+    context: &AudioContext,
+    soundpack_id: &str,
+    update_cache_on_error: bool
+) -> Result<(), String> {
+    // Gamepad sounds are optional, most users never pick a pack
+    if soundpack_id.is_empty() {
+        return Ok(());
+    }
+
+    println!("🎮 Loading gamepad soundpack: {}", soundpack_id);
+    match load_gamepad_soundpack_optimized(context, soundpack_id) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            if update_cache_on_error {
+                capture_soundpack_loading_error(soundpack_id, &e);
+            }
+            Err(e)
+        }
+    }
+}
+
 fn load_audio_file(
     soundpack_path: &str,
     soundpack: &SoundPack
@@ -439,7 +530,8 @@ pub fn load_keyboard_soundpack_optimized(
     let samples = load_audio_file(&soundpack_path, &soundpack)?;
 
     // Create key mappings (only for keyboard soundpacks)
-    let key_mappings = create_key_mappings(&soundpack, &samples.0); // Update audio context with keyboard data
+    let mut key_mappings = create_key_mappings(&soundpack, &samples.0); // Update audio context with keyboard data
+    snap_mappings(&mut key_mappings, &samples, &soundpack);
     update_keyboard_context(context, samples, key_mappings, &soundpack)?;
 
     // Update metadata cache - create metadata with no error since loading succeeded
@@ -476,7 +568,9 @@ pub fn load_keyboard_soundpack_optimized(
                     is_valid_v2: true,
                     validation_status: "loaded_with_metadata_error".to_string(),
                     can_be_converted: false,
+                    definition_method: Some(soundpack.definition_method.clone()),
                     last_error: Some(format!("Metadata creation failed: {}", e)),
+                    doctor: None,
                 };
                 cache.add_soundpack(error_metadata);
             }
@@ -520,7 +614,8 @@ pub fn load_mouse_soundpack_optimized(
     let samples = load_audio_file(&soundpack_path, &soundpack)?;
 
     // Create mouse mappings (only for mouse soundpacks)
-    let mouse_mappings = create_mouse_mappings(&soundpack, &samples.0); // Update audio context with mouse data
+    let mut mouse_mappings = create_mouse_mappings(&soundpack, &samples.0); // Update audio context with mouse data
+    snap_mappings(&mut mouse_mappings, &samples, &soundpack);
     update_mouse_context(context, samples, mouse_mappings, &soundpack)?;
 
     // Update metadata cache - create metadata with no error since loading succeeded
@@ -557,7 +652,9 @@ pub fn load_mouse_soundpack_optimized(
                     is_valid_v2: true,
                     validation_status: "loaded_with_metadata_error".to_string(),
                     can_be_converted: false,
+                    definition_method: Some(soundpack.definition_method.clone()),
                     last_error: Some(format!("Metadata creation failed: {}", e)),
+                    doctor: None,
                 };
                 cache.add_soundpack(error_metadata);
             }
@@ -569,6 +666,38 @@ pub fn load_mouse_soundpack_optimized(
     Ok(())
 }
 
+/// Direct gamepad soundpack loading
+fn load_gamepad_soundpack_optimized(context: &AudioContext, soundpack_id: &str) -> Result<(), String> {
+    let soundpack_path = paths::soundpacks::soundpack_dir(soundpack_id);
+    let config_content = std::fs
+        ::read_to_string(paths::soundpacks::config_json(soundpack_id))
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    let mut soundpack: SoundPack = serde_json
+        ::from_str(&config_content)
+        .map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    // Override soundpack_type based on folder path (more reliable than JSON content)
+    soundpack.soundpack_type = determine_soundpack_type(soundpack_id);
+    if soundpack.soundpack_type != crate::state::soundpack::SoundpackType::Gamepad {
+        return Err("This is not a gamepad soundpack".to_string());
+    }
+
+    let samples = load_audio_file(&soundpack_path, &soundpack)?;
+    let mut gamepad_mappings = create_gamepad_mappings(&soundpack);
+    snap_mappings(&mut gamepad_mappings, &samples, &soundpack);
+    update_gamepad_context(context, samples, gamepad_mappings, &soundpack)?;
+
+    let mut cache = SoundpackCache::load();
+    match create_soundpack_metadata(&soundpack_path, &soundpack) {
+        Ok(metadata) => cache.add_soundpack(metadata),
+        Err(e) => println!("⚠️ Failed to create metadata for {}: {}", soundpack_id, e),
+    }
+    cache.save();
+
+    println!("✅ Successfully loaded gamepad soundpack: {} (direct from files)", soundpack.name);
+    Ok(())
+}
+
 fn update_keyboard_context(
     context: &AudioContext,
     samples: (Vec<f32>, u16, u32), // (samples, channels, sample_rate)
@@ -606,6 +735,10 @@ fn update_keyboard_context(
         return Err("Failed to acquire lock on key_map".to_string());
     }
 
+    if let Ok(mut fades) = context.keyboard_fades.lock() {
+        *fades = SegmentFades::from_options(&soundpack.options);
+    }
+
     // Clear active keyboard audio state
     if let Ok(mut sinks) = context.key_sinks.lock() {
         let old_sinks = sinks.len();
@@ -668,6 +801,10 @@ fn update_mouse_context(
         return Err("Failed to acquire lock on mouse_map".to_string());
     }
 
+    if let Ok(mut fades) = context.mouse_fades.lock() {
+        *fades = SegmentFades::from_options(&soundpack.options);
+    }
+
     // Clear active mouse audio state
     if let Ok(mut mouse_sinks) = context.mouse_sinks.lock() {
         let old_sinks = mouse_sinks.len();
@@ -693,6 +830,53 @@ fn update_mouse_context(
     Ok(())
 }
 
+fn update_gamepad_context(
+    context: &AudioContext,
+    samples: (Vec<f32>, u16, u32), // (samples, channels, sample_rate)
+    gamepad_mappings: std::collections::HashMap<String, Vec<(f64, f64)>>,
+    soundpack: &SoundPack
+) -> Result<(), String> {
+    let mapping_count = gamepad_mappings.len();
+
+    if let Ok(mut cached) = context.gamepad_samples.lock() {
+        *cached = Some(samples);
+    } else {
+        return Err("Failed to acquire lock on gamepad_samples".to_string());
+    }
+
+    if let Ok(mut gamepad_map) = context.gamepad_map.lock() {
+        gamepad_map.clear();
+        for (button, mappings) in gamepad_mappings {
+            let converted_mappings: Vec<[f32; 2]> = mappings
+                .into_iter()
+                .map(|(start, end)| [start as f32, end as f32])
+                .collect();
+            gamepad_map.insert(button, converted_mappings);
+        }
+    } else {
+        return Err("Failed to acquire lock on gamepad_map".to_string());
+    }
+
+    if let Ok(mut fades) = context.gamepad_fades.lock() {
+        *fades = SegmentFades::from_options(&soundpack.options);
+    }
+
+    // Clear active gamepad audio state
+    if let Ok(mut sinks) = context.gamepad_sinks.lock() {
+        sinks.clear();
+    }
+    if let Ok(mut pressed) = context.gamepad_pressed.lock() {
+        pressed.clear();
+    }
+
+    println!(
+        "✅ Successfully loaded gamepad soundpack: {} ({} button mappings)",
+        soundpack.name,
+        mapping_count
+    );
+    Ok(())
+}
+
 fn create_soundpack_metadata(
     soundpack_path: &str,
     soundpack: &SoundPack
@@ -759,11 +943,31 @@ fn create_soundpack_metadata(
         is_valid_v2: true, // Assume valid since it loaded successfully
         validation_status: "valid".to_string(),
         can_be_converted: false,
+        definition_method: Some(soundpack.definition_method.clone()),
         // Error tracking - None since we successfully created metadata
         last_error: None,
+        doctor: None,
     })
 }
 
+/// Move segment edges onto zero crossings when the pack asks for it
+fn snap_mappings(
+    mappings: &mut std::collections::HashMap<String, Vec<(f64, f64)>>,
+    samples: &(Vec<f32>, u16, u32),
+    soundpack: &SoundPack
+) {
+    if !soundpack.options.snap_to_zero_crossings {
+        return;
+    }
+    let (audio_samples, channels, sample_rate) = samples;
+    let mono = mix_to_mono(audio_samples, *channels);
+    for timings in mappings.values_mut() {
+        for timing in timings.iter_mut() {
+            *timing = snap_timing(&mono, *sample_rate, *timing);
+        }
+    }
+}
+
 fn create_key_mappings(
     soundpack: &SoundPack,
     _samples: &[f32]
@@ -772,6 +976,9 @@ fn create_key_mappings(
     // For mouse soundpacks, return empty key mappings
     if soundpack.soundpack_type == crate::state::soundpack::SoundpackType::Keyboard {
         for (key, key_def) in &soundpack.definitions {
+            if !crate::libs::key_codes::is_key_code(key) {
+                println!("⚠️ Unknown key code '{}' in {}, it will never play", key, soundpack.name);
+            }
             // Convert KeyDefinition timing to Vec<(f64, f64)>
             let converted_mappings: Vec<(f64, f64)> = key_def.timing
                 .iter()
@@ -832,6 +1039,24 @@ fn create_mouse_mappings(
     mouse_mappings
 }
 
+fn create_gamepad_mappings(
+    soundpack: &SoundPack
+) -> std::collections::HashMap<String, Vec<(f64, f64)>> {
+    let mut gamepad_mappings = std::collections::HashMap::new();
+    for (button, key_def) in &soundpack.definitions {
+        if !crate::libs::key_codes::is_gamepad_code(button) {
+            println!("⚠️ Unknown gamepad code '{}' in {}, it will never play", button, soundpack.name);
+        }
+        let converted_mappings: Vec<(f64, f64)> = key_def.timing
+            .iter()
+            .map(|pair| (pair[0] as f64, pair[1] as f64))
+            .collect();
+        gamepad_mappings.insert(button.clone(), converted_mappings);
+    }
+
+    gamepad_mappings
+}
+
 /// Capture soundpack loading error and update the cache
 fn capture_soundpack_loading_error(soundpack_id: &str, error: &str) {
     // Skip creating cache entries for empty soundpack IDs
@@ -871,7 +1096,9 @@ fn capture_soundpack_loading_error(soundpack_id: &str, error: &str) {
             is_valid_v2: false,
             validation_status: "loading_error".to_string(),
             can_be_converted: false,
+            definition_method: None,
             last_error: Some(error.to_string()),
+            doctor: None,
         };
 
         cache.soundpacks.insert(soundpack_id.to_string(), error_metadata);
@@ -0,0 +1,800 @@
+/// Local control socket for scripting MechAura
+///
+/// Speaks newline-delimited JSON-RPC 2.0 over a Unix-domain socket (a named pipe on Windows),
+/// so status bars and scripts can change volume, mute, switch soundpacks and drive
+/// ambiance and music. `mechaura ctl` is the bundled client.
+use crate::libs::AudioContext;
+use crate::state::config::AppConfig;
+use crate::state::soundpack::{ SoundpackCache, SoundpackType };
+use crate::{ always_eprint, always_print, debug_eprint, debug_print };
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{ json, Value };
+use std::sync::mpsc::{ self, Receiver, Sender };
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::{ AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader };
+
+/// How long a request waits for the UI (or headless loop) to apply an audio command
+const AUDIO_COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often subscribers are checked for state changes
+const EVENT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a second launch waits for the running instance to start listening
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(10);
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+#[cfg(windows)]
+const PIPE_PREFIX: &str = r"\\.\pipe\mechaura";
+
+/// Socket path, `$MECHAURA_SOCKET` overrides the default
+#[cfg(unix)]
+pub fn socket_path() -> std::path::PathBuf {
+    if let Ok(path) = std::env::var("MECHAURA_SOCKET") {
+        return path.into();
+    }
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => std::path::PathBuf::from(dir).join("mechaura.sock"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_else(|_| "user".to_string());
+            std::env::temp_dir().join(format!("mechaura-{}.sock", user))
+        }
+    }
+}
+
+/// Pipe name, `$MECHAURA_SOCKET` overrides the default
+#[cfg(windows)]
+pub fn socket_path() -> String {
+    if let Ok(path) = std::env::var("MECHAURA_SOCKET") {
+        return path;
+    }
+    // Pipe names are global to the machine, every signed-in user gets their own
+    let user = std::env::var("USERNAME").unwrap_or_else(|_| "user".to_string());
+    format!("{}-{}", PIPE_PREFIX, user)
+}
+
+// ===== AUDIO COMMANDS =====
+
+/// Commands that need the `AudioContext`, which lives in the UI or the headless loop
+#[derive(Debug, Clone)]
+pub enum AudioCommand {
+    SetVolume(f32),
+    SetMouseVolume(f32),
+    LoadSoundpack(SoundpackType, String),
+    /// Load the active pack again after its files changed, the config stays as it is
+    ReloadSoundpack(SoundpackType, String),
+}
+
+struct PendingCommand {
+    command: AudioCommand,
+    reply: Sender<Result<(), String>>,
+}
+
+struct AudioCommandQueue {
+    sender: Sender<PendingCommand>,
+    receiver: Mutex<Receiver<PendingCommand>>,
+}
+
+static AUDIO_COMMANDS: Lazy<AudioCommandQueue> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::channel();
+    AudioCommandQueue {
+        sender,
+        receiver: Mutex::new(receiver),
+    }
+});
+
+/// Apply queued control commands, call regularly from whoever owns the `AudioContext`
+pub fn process_audio_commands(ctx: &AudioContext) {
+    let Ok(receiver) = AUDIO_COMMANDS.receiver.try_lock() else {
+        return;
+    };
+    while let Ok(pending) = receiver.try_recv() {
+        let result = apply_audio_command(ctx, &pending.command);
+        let _ = pending.reply.send(result);
+    }
+}
+
+fn apply_audio_command(ctx: &AudioContext, command: &AudioCommand) -> Result<(), String> {
+    debug_print!("🎛️ Control command: {:?}", command);
+    match command {
+        AudioCommand::SetVolume(volume) => ctx.set_volume(*volume),
+        AudioCommand::SetMouseVolume(volume) => ctx.set_mouse_volume(*volume),
+        AudioCommand::LoadSoundpack(soundpack_type, id) => {
+            let result = match soundpack_type {
+                SoundpackType::Keyboard => crate::libs::audio::load_keyboard_soundpack(ctx, id),
+                SoundpackType::Mouse => crate::libs::audio::load_mouse_soundpack(ctx, id),
+                SoundpackType::Gamepad => crate::libs::audio::load_gamepad_soundpack(ctx, id),
+            };
+            result?;
+            update_config(|config| {
+                match soundpack_type {
+                    SoundpackType::Keyboard => {
+                        config.keyboard_soundpack = id.clone();
+                    }
+                    SoundpackType::Mouse => {
+                        config.mouse_soundpack = id.clone();
+                    }
+                    SoundpackType::Gamepad => {
+                        config.gamepad_soundpack = id.clone();
+                    }
+                }
+            })?;
+        }
+        AudioCommand::ReloadSoundpack(soundpack_type, id) => {
+            use crate::libs::audio::soundpack_loader::{
+                load_gamepad_soundpack_with_cache_control,
+                load_keyboard_soundpack_with_cache_control,
+                load_mouse_soundpack_with_cache_control,
+            };
+            match soundpack_type {
+                SoundpackType::Keyboard => load_keyboard_soundpack_with_cache_control(ctx, id, true)?,
+                SoundpackType::Mouse => load_mouse_soundpack_with_cache_control(ctx, id, true)?,
+                SoundpackType::Gamepad => load_gamepad_soundpack_with_cache_control(ctx, id, true)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Queue a command for the `AudioContext` owner and wait for the result
+pub fn send_audio_command(command: AudioCommand) -> Result<(), String> {
+    let (reply, result) = mpsc::channel();
+    AUDIO_COMMANDS.sender
+        .send(PendingCommand { command, reply })
+        .map_err(|e| format!("Audio command queue closed: {}", e))?;
+    result
+        .recv_timeout(AUDIO_COMMAND_TIMEOUT)
+        .map_err(|_| "Timed out waiting for the audio engine".to_string())?
+}
+
+/// Save a config change with a fresh `last_updated`, so the UI picks it up
+fn update_config(f: impl FnOnce(&mut AppConfig)) -> Result<AppConfig, String> {
+    let mut config = AppConfig::read().ok_or_else(|| "Failed to read config.json".to_string())?;
+    f(&mut config);
+    config.last_updated = chrono::Utc::now();
+    config.save()?;
+    Ok(config)
+}
+
+// ===== SERVER =====
+
+/// Start the control server on a background thread
+pub fn start_control_server() {
+    std::thread::spawn(|| {
+        let rt = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+            Ok(rt) => rt,
+            Err(e) => {
+                always_eprint!("❌ Failed to start control socket runtime: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = rt.block_on(serve()) {
+            always_eprint!("⚠️ Control socket disabled: {}", e);
+        }
+    });
+}
+
+#[cfg(unix)]
+async fn serve() -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = socket_path();
+    if path.exists() {
+        // A live socket means another instance is already serving it
+        if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+            return Err(format!("{} is in use by another instance", path.display()));
+        }
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove stale socket: {}", e))?;
+    }
+
+    let listener = tokio::net::UnixListener
+        ::bind(&path)
+        .map_err(|e| format!("Failed to bind {}: {}", path.display(), e))?;
+    // Only the current user may control the app
+    let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    always_print!("🎛️ Control socket listening on {}", path.display());
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream));
+            }
+            Err(e) => debug_eprint!("⚠️ Control socket accept failed: {}", e),
+        }
+    }
+}
+
+#[cfg(windows)]
+async fn serve() -> Result<(), String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let name = socket_path();
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(&name)
+        .map_err(|e| format!("Failed to create {}: {}", name, e))?;
+    always_print!("🎛️ Control pipe listening on {}", name);
+
+    loop {
+        if let Err(e) = server.connect().await {
+            debug_eprint!("⚠️ Control pipe connect failed: {}", e);
+            continue;
+        }
+        // Create the next instance before handing this one off, so clients never see the pipe missing
+        let next = ServerOptions::new()
+            .create(&name)
+            .map_err(|e| format!("Failed to create {}: {}", name, e))?;
+        let connected = std::mem::replace(&mut server, next);
+        tokio::spawn(handle_connection(connected));
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+async fn serve() -> Result<(), String> {
+    Err("not supported on this platform".to_string())
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: Option<String>,
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+async fn handle_connection<S>(stream: S) where S: AsyncRead + AsyncWrite + Unpin {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request = match serde_json::from_str::<Request>(&line) {
+            Ok(request) if request.jsonrpc.as_deref() == Some("2.0") => request,
+            Ok(request) => {
+                let response = error_response(request.id, INVALID_REQUEST, "jsonrpc must be \"2.0\"");
+                if write_message(&mut writer, &response).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+            Err(e) => {
+                let code = if serde_json::from_str::<Value>(&line).is_ok() {
+                    INVALID_REQUEST
+                } else {
+                    PARSE_ERROR
+                };
+                let response = error_response(None, code, &e.to_string());
+                if write_message(&mut writer, &response).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+
+        // The connection turns into an event stream from here on
+        if request.method == "subscribe" {
+            let state = tokio::task
+                ::spawn_blocking(state_snapshot).await
+                .ok()
+                .and_then(Result::ok)
+                .unwrap_or(Value::Null);
+            let response = json!({ "jsonrpc": "2.0", "id": request.id, "result": state });
+            if write_message(&mut writer, &response).await.is_ok() {
+                stream_events(&mut writer, state).await;
+            }
+            return;
+        }
+
+        let id = request.id.clone();
+        let result = tokio::task
+            ::spawn_blocking(move || dispatch(&request.method, &request.params)).await
+            .unwrap_or_else(|e| Err((SERVER_ERROR, e.to_string())));
+
+        // Requests without an id are notifications and get no response
+        let Some(id) = id else {
+            continue;
+        };
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(Some(id), code, &message),
+        };
+        if write_message(&mut writer, &response).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Push a `state_changed` notification whenever the snapshot changes
+async fn stream_events<W>(writer: &mut W, mut last: Value) where W: AsyncWrite + Unpin {
+    loop {
+        tokio::time::sleep(EVENT_POLL_INTERVAL).await;
+        let Ok(result) = tokio::task::spawn_blocking(state_snapshot).await else {
+            return;
+        };
+        // Unreadable mid-save, compare against the last snapshot on the next poll
+        let Ok(state) = result else {
+            continue;
+        };
+        if state == last {
+            continue;
+        }
+        let event = json!({ "jsonrpc": "2.0", "method": "state_changed", "params": state });
+        if write_message(writer, &event).await.is_err() {
+            debug_print!("🎛️ Control subscriber disconnected");
+            return;
+        }
+        last = state;
+    }
+}
+
+async fn write_message<W>(writer: &mut W, message: &Value) -> std::io::Result<()>
+    where W: AsyncWrite + Unpin
+{
+    let mut line = message.to_string();
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await
+}
+
+fn error_response(id: Option<Value>, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+// ===== METHODS =====
+
+type MethodResult = Result<Value, (i64, String)>;
+
+fn dispatch(method: &str, params: &Value) -> MethodResult {
+    match method {
+        "get_state" => state_snapshot(),
+
+        // Sent by a second launch, see `single_instance`
+        "forward_args" => {
+            let args: Vec<String> = params
+                .get("args")
+                .and_then(|args| serde_json::from_value(args.clone()).ok())
+                .ok_or_else(|| (INVALID_PARAMS, "Missing list param 'args'".to_string()))?;
+            crate::libs::single_instance::handle_args(&args, true);
+            Ok(json!(true))
+        }
+
+        "get_volume" => Ok(json!(read_config()?.volume)),
+        "set_volume" => {
+            let volume = volume_param(params)?;
+            send_audio_command(AudioCommand::SetVolume(volume)).map_err(server_error)?;
+            Ok(json!(volume))
+        }
+        "get_mouse_volume" => Ok(json!(read_config()?.mouse_volume)),
+        "set_mouse_volume" => {
+            let volume = volume_param(params)?;
+            send_audio_command(AudioCommand::SetMouseVolume(volume)).map_err(server_error)?;
+            Ok(json!(volume))
+        }
+
+        "toggle_sound" => set_sound(None),
+        "set_sound" => {
+            let enabled = bool_param(params, "enabled")?;
+            set_sound(Some(enabled))
+        }
+
+        "list_soundpacks" => {
+            let filter = match params.get("type").and_then(Value::as_str) {
+                Some(name) => Some(soundpack_type_param(name)?),
+                None => None,
+            };
+            list_soundpacks(filter)
+        }
+        "set_soundpack" => {
+            let id = string_param(params, "id")?;
+            set_soundpack(&id)
+        }
+
+        "ambiance_toggle" => ambiance(|player| player.toggle_play_pause()),
+        "ambiance_play" => ambiance(|player| {
+            if !player.is_playing {
+                player.toggle_play_pause();
+            }
+        }),
+        "ambiance_pause" => ambiance(|player| {
+            if player.is_playing {
+                player.toggle_play_pause();
+            }
+        }),
+        "ambiance_toggle_mute" => ambiance(|player| player.toggle_mute()),
+        "ambiance_set_volume" => {
+            let volume = number_param(params, "volume")?.clamp(0.0, 1.0);
+            ambiance(|player| player.set_global_volume(volume))
+        }
+        "ambiance_toggle_sound" => {
+            let id = string_param(params, "id")?;
+            let known = crate::state::ambiance::AmbiancePlayerState
+                ::get_builtin_sounds()
+                .iter()
+                .any(|sound| sound.id == id);
+            if !known {
+                return Err((INVALID_PARAMS, format!("Unknown ambiance sound '{}'", id)));
+            }
+            ambiance(|player| player.toggle_sound(id))
+        }
+
+        "music_toggle" => music(|player| {
+            player.play_pause();
+        }),
+        "music_play" => music(|player| {
+            if !player.is_playing {
+                player.play_pause();
+            }
+        }),
+        "music_pause" => music(|player| {
+            if player.is_playing {
+                player.play_pause();
+            }
+        }),
+        "music_next" => music(|player| {
+            player.next_track();
+        }),
+        "music_toggle_mute" => music(|player| player.toggle_mute()),
+        "music_set_volume" => {
+            // Music volume is 0-100, like the slider on the Mood page
+            let volume = number_param(params, "volume")?.clamp(0.0, 100.0);
+            music(|player| player.set_volume(volume))
+        }
+
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method '{}'", method))),
+    }
+}
+
+/// The config as saved, without `AppConfig::load` writing it back
+///
+/// Subscribers poll several times a second, reads must never touch the user's file.
+fn read_config() -> Result<AppConfig, (i64, String)> {
+    AppConfig::read().ok_or_else(|| server_error("Failed to read config.json".to_string()))
+}
+
+/// Everything a status bar needs, also the payload of `state_changed` events
+fn state_snapshot() -> MethodResult {
+    let config = read_config()?;
+
+    let ambiance = crate::state::ambiance::get_global_ambiance_player_state_copy().map(|player| {
+        let mut active: Vec<&String> = player.active_sounds.keys().collect();
+        active.sort();
+        json!({
+            "playing": player.is_playing,
+            "muted": player.is_muted,
+            "volume": player.global_volume,
+            "active_sounds": active,
+        })
+    });
+
+    let music = crate::state::music::get_global_music_player_state_copy().map(|player| {
+        let (title, artist, _, _) = player.get_current_track_info();
+        json!({
+            "playing": player.is_playing,
+            "muted": player.is_muted,
+            "volume": player.volume,
+            "title": title,
+            "artist": artist,
+        })
+    });
+
+    Ok(
+        json!({
+        "enable_sound": config.enable_sound,
+        "volume": config.volume,
+        "mouse_volume": config.mouse_volume,
+        "gamepad_volume": config.gamepad_volume,
+        "keyboard_soundpack": config.keyboard_soundpack,
+        "mouse_soundpack": config.mouse_soundpack,
+        "gamepad_soundpack": config.gamepad_soundpack,
+        "ambiance": ambiance,
+        "music": music,
+    })
+    )
+}
+
+fn set_sound(enabled: Option<bool>) -> MethodResult {
+    let config = update_config(|config| {
+        config.enable_sound = enabled.unwrap_or(!config.enable_sound);
+    }).map_err(server_error)?;
+    crate::libs::tray_service::request_tray_update();
+    always_print!("🔄 Sound {} via control socket", if config.enable_sound {
+        "enabled"
+    } else {
+        "muted"
+    });
+    Ok(json!(config.enable_sound))
+}
+
+fn list_soundpacks(filter: Option<SoundpackType>) -> MethodResult {
+    let config = read_config()?;
+    let cache = SoundpackCache::load();
+
+    let mut packs: Vec<_> = cache.soundpacks
+        .values()
+        .filter(|pack| filter.is_none_or(|t| pack.soundpack_type == t))
+        .collect();
+    packs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    let packs: Vec<Value> = packs
+        .into_iter()
+        .map(|pack| {
+            let selected = match pack.soundpack_type {
+                SoundpackType::Keyboard => config.keyboard_soundpack == pack.folder_path,
+                SoundpackType::Mouse => config.mouse_soundpack == pack.folder_path,
+                SoundpackType::Gamepad => config.gamepad_soundpack == pack.folder_path,
+            };
+            json!({
+                "id": pack.folder_path,
+                "name": pack.name,
+                "author": pack.author,
+                "type": pack.soundpack_type.folder_name(),
+                "selected": selected,
+            })
+        })
+        .collect();
+    Ok(json!(packs))
+}
+
+fn set_soundpack(id: &str) -> MethodResult {
+    // Accept the folder path used in the config, or the pack's own id
+    let cache = SoundpackCache::load();
+    let pack = cache.soundpacks
+        .values()
+        .find(|pack| pack.folder_path == id)
+        .or_else(|| cache.soundpacks.values().find(|pack| pack.id == id))
+        .ok_or_else(|| (INVALID_PARAMS, format!("Unknown soundpack '{}'", id)))?;
+
+    send_audio_command(
+        AudioCommand::LoadSoundpack(pack.soundpack_type, pack.folder_path.clone())
+    ).map_err(server_error)?;
+    Ok(json!({ "id": pack.folder_path, "type": pack.soundpack_type.folder_name() }))
+}
+
+fn ambiance(f: impl FnOnce(&mut crate::state::ambiance::AmbiancePlayerState)) -> MethodResult {
+    crate::state::ambiance::update_global_ambiance_player_state(f);
+    Ok(state_snapshot()?["ambiance"].take())
+}
+
+fn music(f: impl FnOnce(&mut crate::state::music::MusicPlayerState)) -> MethodResult {
+    // The Mood page loads the track list, headless mode never opens it
+    if crate::state::music::get_global_music_player_state_copy().is_none() {
+        tokio::runtime::Handle
+            ::current()
+            .block_on(crate::state::music::initialize_global_music_player_state())
+            .map_err(server_error)?;
+    }
+    crate::state::music::update_global_music_player_state(f);
+    Ok(state_snapshot()?["music"].take())
+}
+
+// ===== PARAMS =====
+
+fn server_error(message: String) -> (i64, String) {
+    (SERVER_ERROR, message)
+}
+
+fn number_param(params: &Value, name: &str) -> Result<f32, (i64, String)> {
+    params
+        .get(name)
+        .and_then(Value::as_f64)
+        .map(|value| value as f32)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Missing number parameter '{}'", name)))
+}
+
+fn bool_param(params: &Value, name: &str) -> Result<bool, (i64, String)> {
+    params
+        .get(name)
+        .and_then(Value::as_bool)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Missing boolean parameter '{}'", name)))
+}
+
+fn string_param(params: &Value, name: &str) -> Result<String, (i64, String)> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Missing string parameter '{}'", name)))
+}
+
+/// Keyboard and mouse volume, up to 200% when volume boost is enabled
+fn volume_param(params: &Value) -> Result<f32, (i64, String)> {
+    let max = if read_config()?.enable_volume_boost { 2.0 } else { 1.0 };
+    Ok(number_param(params, "volume")?.clamp(0.0, max))
+}
+
+fn soundpack_type_param(name: &str) -> Result<SoundpackType, (i64, String)> {
+    SoundpackType::ALL.into_iter()
+        .find(|t| t.folder_name() == name)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Unknown soundpack type '{}'", name)))
+}
+
+// ===== CLIENT =====
+
+/// `mechaura ctl <method> [key=value ...]`, returns the process exit code
+pub fn run_ctl(args: &[String]) -> i32 {
+    let Some(method) = args.first() else {
+        eprintln!("{}", CTL_USAGE);
+        return 2;
+    };
+    if method == "help" || method == "--help" || method == "-h" {
+        println!("{}", CTL_USAGE);
+        return 0;
+    }
+
+    let params = match parse_ctl_params(&args[1..]) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("mechaura ctl: {}", e);
+            return 2;
+        }
+    };
+    let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+
+    let rt = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("mechaura ctl: {}", e);
+            return 1;
+        }
+    };
+    rt.block_on(async {
+        let stream = match connect().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("mechaura ctl: MechAura is not running ({})", e);
+                return 3;
+            }
+        };
+        ctl_request(stream, &request, method == "subscribe").await
+    })
+}
+
+const CTL_USAGE: &str =
+    "Usage: mechaura ctl <method> [key=value ...] | [json params]
+
+Methods:
+  get_state                     Sound, volumes, soundpacks, ambiance and music
+  subscribe                     Print state_changed events until interrupted
+  get_volume | get_mouse_volume
+  set_volume volume=0.5         Keyboard volume, 0-1 (0-2 with volume boost)
+  set_mouse_volume volume=0.5
+  toggle_sound | set_sound enabled=false
+  list_soundpacks [type=keyboard|mouse|gamepad]
+  set_soundpack id=keyboard/<folder>
+  ambiance_toggle | ambiance_play | ambiance_pause | ambiance_toggle_mute
+  ambiance_set_volume volume=0.5 | ambiance_toggle_sound id=rain
+  music_toggle | music_play | music_pause | music_next | music_toggle_mute
+  music_set_volume volume=50    Music volume, 0-100";
+
+/// `key=value` pairs (values parsed as JSON when possible) or a single JSON object
+fn parse_ctl_params(args: &[String]) -> Result<Value, String> {
+    if let [single] = args {
+        if single.trim_start().starts_with('{') {
+            return serde_json::from_str(single).map_err(|e| format!("invalid JSON params: {}", e));
+        }
+    }
+
+    let mut params = serde_json::Map::new();
+    for arg in args {
+        let (key, value) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("expected key=value, got '{}'", arg))?;
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        params.insert(key.to_string(), value);
+    }
+    Ok(Value::Object(params))
+}
+
+/// Hand the arguments of a second launch to the running instance
+pub fn forward_args(args: &[String]) -> Result<(), String> {
+    let request =
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "forward_args", "params": { "args": args } });
+    let rt = tokio::runtime::Builder
+        ::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    rt.block_on(async {
+        // The running instance may hold the lock but still be starting its control server
+        let deadline = std::time::Instant::now() + FORWARD_TIMEOUT;
+        let stream = loop {
+            match connect().await {
+                Ok(stream) => break stream,
+                Err(_) if std::time::Instant::now() < deadline => {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                Err(e) => return Err(format!("The running instance is not reachable: {}", e)),
+            }
+        };
+
+        let (reader, mut writer) = tokio::io::split(stream);
+        write_message(&mut writer, &request).await.map_err(|e| e.to_string())?;
+        let line = BufReader::new(reader)
+            .lines()
+            .next_line().await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Connection closed".to_string())?;
+        let response: Value = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+        match response.get("error") {
+            Some(error) =>
+                Err(error.get("message").and_then(Value::as_str).unwrap_or("unknown error").to_string()),
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(unix)]
+async fn connect() -> std::io::Result<tokio::net::UnixStream> {
+    tokio::net::UnixStream::connect(socket_path()).await
+}
+
+#[cfg(windows)]
+async fn connect() -> std::io::Result<tokio::net::windows::named_pipe::NamedPipeClient> {
+    tokio::net::windows::named_pipe::ClientOptions::new().open(socket_path())
+}
+
+#[cfg(not(any(unix, windows)))]
+async fn connect() -> std::io::Result<tokio::io::DuplexStream> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "not supported on this platform"))
+}
+
+async fn ctl_request<S>(stream: S, request: &Value, follow: bool) -> i32
+    where S: AsyncRead + AsyncWrite + Unpin
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    if let Err(e) = write_message(&mut writer, request).await {
+        eprintln!("mechaura ctl: {}", e);
+        return 1;
+    }
+
+    let mut lines = BufReader::new(reader).lines();
+    let response = match lines.next_line().await {
+        Ok(Some(line)) => line,
+        Ok(None) => {
+            eprintln!("mechaura ctl: connection closed");
+            return 1;
+        }
+        Err(e) => {
+            eprintln!("mechaura ctl: {}", e);
+            return 1;
+        }
+    };
+    let response: Value = match serde_json::from_str(&response) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("mechaura ctl: invalid response: {}", e);
+            return 1;
+        }
+    };
+
+    if let Some(error) = response.get("error") {
+        let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+        eprintln!("mechaura ctl: {}", message);
+        return 1;
+    }
+    let result = response.get("result").cloned().unwrap_or(Value::Null);
+    println!("{}", serde_json::to_string_pretty(&result).unwrap_or_default());
+
+    if follow {
+        // One compact line per event, easy to pipe into jq or a status bar
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Ok(event) = serde_json::from_str::<Value>(&line) {
+                println!("{}", event["params"]);
+            }
+        }
+    }
+    0
+}
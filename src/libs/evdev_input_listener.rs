@@ -1,201 +1,489 @@
+use crate::libs::input_device_manager::{evdev_device_id, InputDeviceManager, InputDeviceType};
+use crate::libs::input_manager::InputEvent;
+use crate::libs::key_codes::{evdev_gamepad_code, evdev_key_code};
+use crate::state::config::AppConfig;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[cfg(target_os = "linux")]
 use std::sync::mpsc::Sender;
 
+/// Minimum time between two wheel sounds in the same direction
 #[cfg(target_os = "linux")]
-pub fn start_evdev_keyboard_listener(
-    keyboard_tx: Sender<String>,
+const WHEEL_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Amount of REL_WHEEL_HI_RES units that make up one wheel notch
+#[cfg(target_os = "linux")]
+const HI_RES_UNITS_PER_NOTCH: i32 = 120;
+
+/// Set once the keyboard and mouse listener runs, events only carry device IDs through it
+#[cfg(target_os = "linux")]
+static INPUT_LISTENER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether keyboard and mouse input goes through evdev, which main.rs decides at startup
+#[cfg(target_os = "linux")]
+pub fn is_input_listener_started() -> bool {
+    INPUT_LISTENER_STARTED.load(Ordering::Relaxed)
+}
+
+/// How often the enabled device lists are re-read from the config
+#[cfg(target_os = "linux")]
+const DEVICE_FILTER_REFRESH: Duration = Duration::from_secs(1);
+
+/// An opened evdev device together with the per-device state needed to decode its events
+#[cfg(target_os = "linux")]
+struct EvdevSource {
+    device: evdev::Device,
+    /// InputDeviceManager ID of the keyboard role, if the device has one
+    keyboard_id: Option<String>,
+    /// InputDeviceManager ID of the mouse role, if the device has one
+    mouse_id: Option<String>,
+    /// Device reports REL_WHEEL, so REL_WHEEL_HI_RES duplicates are ignored
+    has_lowres_wheel: bool,
+    /// Accumulated REL_WHEEL_HI_RES value for devices without REL_WHEEL
+    hi_res_accumulator: i32,
+}
+
+/// An opened gamepad or joystick with the state needed to turn axes into button events
+#[cfg(target_os = "linux")]
+struct GamepadSource {
+    device: evdev::Device,
+    /// InputDeviceManager ID of the gamepad role
+    id: String,
+    /// Analog triggers on pads that have no digital BTN_TL2/BTN_TR2
+    triggers: Vec<AnalogTrigger>,
+    /// D-pad reported as ABS_HAT0X/ABS_HAT0Y instead of BTN_DPAD_* buttons
+    has_hat_dpad: bool,
+    /// Last hat position, (x, y) in -1..=1
+    hat: (i32, i32),
+}
+
+/// An analog trigger axis that counts as pressed past half travel
+#[cfg(target_os = "linux")]
+struct AnalogTrigger {
+    axis: evdev::AbsoluteAxisCode,
+    threshold: i32,
+    code: &'static str,
+    pressed: bool,
+}
+
+/// Check if a device looks like a keyboard (has letter or common editing keys)
+#[cfg(target_os = "linux")]
+pub(crate) fn is_keyboard_device(device: &evdev::Device) -> bool {
+    use evdev::KeyCode;
+
+    device.supported_keys().is_some_and(|keys| {
+        keys.contains(KeyCode::KEY_A) ||
+            keys.contains(KeyCode::KEY_SPACE) ||
+            keys.contains(KeyCode::KEY_ENTER)
+    })
+}
+
+/// Check if a device looks like a pointer (has mouse buttons or a scroll wheel)
+#[cfg(target_os = "linux")]
+pub(crate) fn is_pointer_device(device: &evdev::Device) -> bool {
+    use evdev::{KeyCode, RelativeAxisCode};
+
+    let has_buttons = device.supported_keys().is_some_and(|keys| keys.contains(KeyCode::BTN_LEFT));
+    let has_wheel = device.supported_relative_axes().is_some_and(|axes| {
+        axes.contains(RelativeAxisCode::REL_WHEEL) ||
+            axes.contains(RelativeAxisCode::REL_WHEEL_HI_RES)
+    });
+
+    has_buttons || has_wheel
+}
+
+/// Check if a device looks like a gamepad or joystick
+#[cfg(target_os = "linux")]
+pub(crate) fn is_gamepad_device(device: &evdev::Device) -> bool {
+    use evdev::KeyCode;
+
+    device.supported_keys().is_some_and(|keys| {
+        keys.contains(KeyCode::BTN_SOUTH) || keys.contains(KeyCode::BTN_TRIGGER)
+    })
+}
+
+/// Start the evdev input listener for keyboards and pointer devices
+/// Keyboard events (and the Ctrl+Alt+M hotkey) go to the keyboard/hotkey channels,
+/// mouse buttons and wheel notches go to the mouse channel.
+/// Devices not in `enabled_keyboards`/`enabled_mice` stay silent (empty list = all devices).
+#[cfg(target_os = "linux")]
+pub fn start_evdev_input_listener(
+    keyboard_tx: Sender<InputEvent>,
+    mouse_tx: Sender<InputEvent>,
     hotkey_tx: Sender<String>,
     _is_focused: Arc<Mutex<bool>>,
 ) {
+    INPUT_LISTENER_STARTED.store(true, Ordering::Relaxed);
     thread::spawn(move || {
-        use evdev::{Device, EventType, Key};
+        use evdev::{EventType, KeyCode, RelativeAxisCode};
 
-        println!("🔍 [evdev] Starting Linux keyboard listener (Wayland/X11 compatible)");
+        println!("🔍 [evdev] Starting Linux input listener (Wayland/X11 compatible)");
 
         // Track modifier keys for hotkey detection
         let mut ctrl_pressed = false;
         let mut alt_pressed = false;
 
-        // Find all keyboard devices
-        let mut keyboards = Vec::new();
-        
-        match evdev::enumerate().map(|t| t.collect::<Vec<_>>()) {
-            Ok(devices) => {
-                for (path, mut device) in devices {
-                    // Check if device has keyboard capabilities
-                    if device.supported_keys().is_some() {
-                        println!("🔍 [evdev] Found keyboard device: {:?} - {}", path.display(), device.name().unwrap_or("Unknown"));
-
-                        // Set device to non-blocking mode to prevent blocking on idle devices
-                        if let Err(e) = device.set_nonblocking(true) {
-                            eprintln!("⚠️ [evdev] Failed to set non-blocking mode for {:?}: {}", path.display(), e);
-                        }
+        // Enabled device lists from config, refreshed periodically so changes apply live
+        let mut device_filter = InputDeviceManager::from_config(&AppConfig::load());
+        let mut last_filter_refresh = Instant::now();
 
-                        keyboards.push(device);
-                    }
-                }
+        // Track last wheel sound per direction for debouncing
+        let mut last_wheel_up = Instant::now() - WHEEL_DEBOUNCE;
+        let mut last_wheel_down = Instant::now() - WHEEL_DEBOUNCE;
+
+        // Find all keyboard and pointer devices
+        let mut sources = Vec::new();
+        let mut keyboard_count = 0;
+        let mut pointer_count = 0;
+
+        for (path, device) in evdev::enumerate() {
+            let is_keyboard = is_keyboard_device(&device);
+            let is_pointer = is_pointer_device(&device);
+
+            if !is_keyboard && !is_pointer {
+                continue;
+            }
+
+            if is_keyboard {
+                keyboard_count += 1;
+                println!("🔍 [evdev] Found keyboard device: {:?} - {}", path.display(), device.name().unwrap_or("Unknown"));
             }
-            Err(e) => {
-                eprintln!("❌ [evdev] Failed to enumerate devices: {}", e);
-                eprintln!("💡 [evdev] Make sure you're in the 'input' group: sudo usermod -a -G input $USER");
-                return;
+            if is_pointer {
+                pointer_count += 1;
+                println!("🔍 [evdev] Found pointer device: {:?} - {}", path.display(), device.name().unwrap_or("Unknown"));
             }
+
+            // Set device to non-blocking mode to prevent blocking on idle devices
+            if let Err(e) = device.set_nonblocking(true) {
+                eprintln!("⚠️ [evdev] Failed to set non-blocking mode for {:?}: {}", path.display(), e);
+            }
+
+            let has_lowres_wheel = device
+                .supported_relative_axes()
+                .is_some_and(|axes| axes.contains(RelativeAxisCode::REL_WHEEL));
+
+            let keyboard_id = is_keyboard.then(|| evdev_device_id(&device, &InputDeviceType::Keyboard));
+            let mouse_id = is_pointer.then(|| evdev_device_id(&device, &InputDeviceType::Mouse));
+
+            sources.push(EvdevSource {
+                device,
+                keyboard_id,
+                mouse_id,
+                has_lowres_wheel,
+                hi_res_accumulator: 0,
+            });
         }
-        
-        if keyboards.is_empty() {
-            eprintln!("❌ [evdev] No keyboard devices found!");
-            eprintln!("💡 [evdev] Make sure you have permission to access /dev/input/event*");
+
+        if sources.is_empty() {
+            eprintln!("❌ [evdev] No keyboard or pointer devices found!");
+            eprintln!("💡 [evdev] Make sure you're in the 'input' group: sudo usermod -a -G input $USER");
             return;
         }
-        
-        println!("🔍 [evdev] Monitoring {} keyboard device(s)", keyboards.len());
-        
-        // Monitor all keyboards in a loop
+
+        println!(
+            "🔍 [evdev] Monitoring {} keyboard device(s) and {} pointer device(s)",
+            keyboard_count,
+            pointer_count
+        );
+
+        // Monitor all devices in a loop
         loop {
-            for device in &mut keyboards {
+            if last_filter_refresh.elapsed() >= DEVICE_FILTER_REFRESH {
+                if let Some(config) = AppConfig::read() {
+                    device_filter = InputDeviceManager::from_config(&config);
+                }
+                last_filter_refresh = Instant::now();
+            }
+
+            for source in &mut sources {
                 // Fetch events (non-blocking)
-                match device.fetch_events() {
-                    Ok(events) => {
-                        for event in events {
-                            if event.event_type() == EventType::KEY {
-                                let key_value = event.value();
-
-                                if let Ok(key) = Key::new(event.code()) {
-                                    let key_code = map_evdev_keycode(key);
-                                    if !key_code.is_empty() {
-                                        // Handle key press (value == 1)
-                                        if key_value == 1 {
-                                            // Track modifier keys for hotkey detection
-                                            match key_code {
-                                                "ControlLeft" | "ControlRight" => {
-                                                    ctrl_pressed = true;
-                                                }
-                                                "AltLeft" | "AltRight" => {
-                                                    alt_pressed = true;
-                                                }
-                                                "KeyM" => {
-                                                    // Check for Ctrl+Alt+M hotkey combination
-                                                    if ctrl_pressed && alt_pressed {
-                                                        println!("🔥 [evdev] Hotkey detected: Ctrl+Alt+M - Toggling global sound");
-                                                        let _ = hotkey_tx.send("TOGGLE_SOUND".to_string());
-                                                        continue; // Don't process this as a regular key event
-                                                    }
-                                                }
-                                                _ => {}
-                                            }
-
-                                            // Send key press event
-                                            let _ = keyboard_tx.send(key_code.to_string());
-                                        }
-                                        // Handle key release (value == 0)
-                                        else if key_value == 0 {
-                                            // Track modifier key releases for hotkey detection
-                                            match key_code {
-                                                "ControlLeft" | "ControlRight" => {
-                                                    ctrl_pressed = false;
-                                                }
-                                                "AltLeft" | "AltRight" => {
-                                                    alt_pressed = false;
-                                                }
-                                                _ => {}
-                                            }
-
-                                            // Send key release event
-                                            let _ = keyboard_tx.send(format!("UP:{}", key_code));
-                                        }
-                                        // Ignore key repeat (value == 2)
-                                    }
-                                }
-                            }
-                        }
-                    }
+                let events: Vec<evdev::InputEvent> = match source.device.fetch_events() {
+                    Ok(events) => events.collect(),
                     Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
                         // No events available, this is normal
+                        continue;
                     }
                     Err(e) => {
                         eprintln!("⚠️ [evdev] Error fetching events: {}", e);
+                        continue;
+                    }
+                };
+
+                // Check the per-device filter once per batch
+                let process_keys = source.keyboard_id
+                    .as_deref()
+                    .is_some_and(|id| device_filter.should_process_device(id, InputDeviceType::Keyboard));
+                let process_mouse = source.mouse_id
+                    .as_deref()
+                    .is_some_and(|id| device_filter.should_process_device(id, InputDeviceType::Mouse));
+
+                for event in events {
+                    if event.event_type() == EventType::KEY {
+                        let key = KeyCode::new(event.code());
+                        let key_value = event.value();
+
+                        // ===== MOUSE BUTTONS =====
+                        let button_code = map_evdev_button(key);
+                        if !button_code.is_empty() {
+                            if !process_mouse {
+                                continue;
+                            }
+                            if key_value == 1 {
+                                let _ = mouse_tx.send(InputEvent::down(button_code).from_device(source.mouse_id.as_deref()));
+                            } else if key_value == 0 {
+                                let _ = mouse_tx.send(InputEvent::up(button_code).from_device(source.mouse_id.as_deref()));
+                            }
+                            continue;
+                        }
+
+                        // ===== KEYBOARD KEYS =====
+                        let key_code = evdev_key_code(key);
+                        if key_code.is_empty() {
+                            continue;
+                        }
+
+                        // Handle key press (value == 1)
+                        if key_value == 1 {
+                            // Track modifier keys for hotkey detection
+                            match key_code {
+                                "ControlLeft" | "ControlRight" => {
+                                    ctrl_pressed = true;
+                                }
+                                "AltLeft" | "AltRight" => {
+                                    alt_pressed = true;
+                                }
+                                "KeyM" => {
+                                    // Check for Ctrl+Alt+M hotkey combination
+                                    if ctrl_pressed && alt_pressed {
+                                        println!("🔥 [evdev] Hotkey detected: Ctrl+Alt+M - Toggling global sound");
+                                        let _ = hotkey_tx.send("TOGGLE_SOUND".to_string());
+                                        continue; // Don't process this as a regular key event
+                                    }
+                                }
+                                _ => {}
+                            }
+
+                            // Send key press event (hotkey still works on silenced keyboards)
+                            if process_keys {
+                                let _ = keyboard_tx.send(InputEvent::down(key_code).from_device(source.keyboard_id.as_deref()));
+                            }
+                        }
+                        // Handle key release (value == 0)
+                        else if key_value == 0 {
+                            // Track modifier key releases for hotkey detection
+                            match key_code {
+                                "ControlLeft" | "ControlRight" => {
+                                    ctrl_pressed = false;
+                                }
+                                "AltLeft" | "AltRight" => {
+                                    alt_pressed = false;
+                                }
+                                _ => {}
+                            }
+
+                            // Send key release event
+                            if process_keys {
+                                let _ = keyboard_tx.send(InputEvent::up(key_code).from_device(source.keyboard_id.as_deref()));
+                            }
+                        }
+                        // Ignore key repeat (value == 2)
+                    } else if event.event_type() == EventType::RELATIVE && process_mouse {
+                        // ===== MOUSE WHEEL =====
+                        let axis = RelativeAxisCode(event.code());
+                        let notches = if axis == RelativeAxisCode::REL_WHEEL {
+                            event.value()
+                        } else if axis == RelativeAxisCode::REL_WHEEL_HI_RES && !source.has_lowres_wheel {
+                            // Only devices without REL_WHEEL need the hi-res axis,
+                            // everything else reports both for the same notch
+                            source.hi_res_accumulator += event.value();
+                            let notches = source.hi_res_accumulator / HI_RES_UNITS_PER_NOTCH;
+                            source.hi_res_accumulator %= HI_RES_UNITS_PER_NOTCH;
+                            notches
+                        } else {
+                            continue;
+                        };
+
+                        let (wheel_code, last_wheel) = if notches > 0 {
+                            ("MouseWheelUp", &mut last_wheel_up)
+                        } else if notches < 0 {
+                            ("MouseWheelDown", &mut last_wheel_down)
+                        } else {
+                            continue;
+                        };
+
+                        // Apply longer debounce for wheel events
+                        let now = Instant::now();
+                        if now.duration_since(*last_wheel) > WHEEL_DEBOUNCE {
+                            *last_wheel = now;
+                            let _ = mouse_tx.send(InputEvent::down(wheel_code).from_device(source.mouse_id.as_deref()));
+                        }
                     }
                 }
             }
-            
+
             // Small sleep to prevent busy-waiting
             thread::sleep(Duration::from_millis(10));
         }
     });
 }
 
+/// Maps an evdev pointer button to its standardized mouse code (same as rdev)
 #[cfg(target_os = "linux")]
-fn map_evdev_keycode(key: evdev::Key) -> &'static str {
-    use evdev::Key::*;
-    
+fn map_evdev_button(key: evdev::KeyCode) -> &'static str {
+    use evdev::KeyCode;
+
     match key {
-        // Letters
-        KEY_A => "KeyA", KEY_B => "KeyB", KEY_C => "KeyC", KEY_D => "KeyD",
-        KEY_E => "KeyE", KEY_F => "KeyF", KEY_G => "KeyG", KEY_H => "KeyH",
-        KEY_I => "KeyI", KEY_J => "KeyJ", KEY_K => "KeyK", KEY_L => "KeyL",
-        KEY_M => "KeyM", KEY_N => "KeyN", KEY_O => "KeyO", KEY_P => "KeyP",
-        KEY_Q => "KeyQ", KEY_R => "KeyR", KEY_S => "KeyS", KEY_T => "KeyT",
-        KEY_U => "KeyU", KEY_V => "KeyV", KEY_W => "KeyW", KEY_X => "KeyX",
-        KEY_Y => "KeyY", KEY_Z => "KeyZ",
-        
-        // Numbers
-        KEY_1 => "Digit1", KEY_2 => "Digit2", KEY_3 => "Digit3", KEY_4 => "Digit4",
-        KEY_5 => "Digit5", KEY_6 => "Digit6", KEY_7 => "Digit7", KEY_8 => "Digit8",
-        KEY_9 => "Digit9", KEY_0 => "Digit0",
-        
-        // Function keys
-        KEY_F1 => "F1", KEY_F2 => "F2", KEY_F3 => "F3", KEY_F4 => "F4",
-        KEY_F5 => "F5", KEY_F6 => "F6", KEY_F7 => "F7", KEY_F8 => "F8",
-        KEY_F9 => "F9", KEY_F10 => "F10", KEY_F11 => "F11", KEY_F12 => "F12",
-        
-        // Special keys
-        KEY_SPACE => "Space",
-        KEY_ENTER => "Enter",
-        KEY_BACKSPACE => "Backspace",
-        KEY_TAB => "Tab",
-        KEY_ESC => "Escape",
-        KEY_CAPSLOCK => "CapsLock",
-        KEY_LEFTSHIFT => "ShiftLeft",
-        KEY_RIGHTSHIFT => "ShiftRight",
-        KEY_LEFTCTRL => "ControlLeft",
-        KEY_RIGHTCTRL => "ControlRight",
-        KEY_LEFTALT => "AltLeft",
-        KEY_RIGHTALT => "AltRight",
-        KEY_LEFTMETA => "MetaLeft",
-        KEY_RIGHTMETA => "MetaRight",
-        
-        // Arrow keys
-        KEY_UP => "ArrowUp",
-        KEY_DOWN => "ArrowDown",
-        KEY_LEFT => "ArrowLeft",
-        KEY_RIGHT => "ArrowRight",
-        
-        // Editing keys
-        KEY_INSERT => "Insert",
-        KEY_DELETE => "Delete",
-        KEY_HOME => "Home",
-        KEY_END => "End",
-        KEY_PAGEUP => "PageUp",
-        KEY_PAGEDOWN => "PageDown",
-        
-        // Punctuation
-        KEY_MINUS => "Minus",
-        KEY_EQUAL => "Equal",
-        KEY_LEFTBRACE => "BracketLeft",
-        KEY_RIGHTBRACE => "BracketRight",
-        KEY_BACKSLASH => "Backslash",
-        KEY_SEMICOLON => "Semicolon",
-        KEY_APOSTROPHE => "Quote",
-        KEY_GRAVE => "Backquote",
-        KEY_COMMA => "Comma",
-        KEY_DOT => "Period",
-        KEY_SLASH => "Slash",
-        
+        KeyCode::BTN_LEFT => "MouseLeft",
+        KeyCode::BTN_RIGHT => "MouseRight",
+        KeyCode::BTN_MIDDLE => "MouseMiddle",
+        KeyCode::BTN_SIDE => "Mouse4", // Back/Previous
+        KeyCode::BTN_EXTRA => "Mouse5", // Forward/Next
         _ => "",
     }
 }
 
+/// Start the evdev listener for gamepads and joysticks
+/// Buttons, analog triggers and the D-pad hat are all sent to the gamepad channel as presses and releases.
+/// Runs next to whichever keyboard/mouse backend is active, since only evdev can see controllers.
+#[cfg(target_os = "linux")]
+pub fn start_evdev_gamepad_listener(gamepad_tx: Sender<InputEvent>) {
+    thread::spawn(move || {
+        use evdev::{AbsoluteAxisCode, EventType, KeyCode};
+
+        let mut sources = Vec::new();
+        for (path, device) in evdev::enumerate() {
+            if !is_gamepad_device(&device) {
+                continue;
+            }
+            println!("🎮 [evdev] Found gamepad device: {:?} - {}", path.display(), device.name().unwrap_or("Unknown"));
+
+            if let Err(e) = device.set_nonblocking(true) {
+                eprintln!("⚠️ [evdev] Failed to set non-blocking mode for {:?}: {}", path.display(), e);
+            }
+
+            let keys = device.supported_keys();
+            let has_key = |key: KeyCode| keys.is_some_and(|keys| keys.contains(key));
+            let is_gamepad = has_key(KeyCode::BTN_SOUTH);
+
+            // On joysticks ABS_Z/ABS_RZ are throttle and twist, only gamepads use them as triggers
+            let mut triggers = Vec::new();
+            if is_gamepad {
+                if let Ok(axes) = device.get_absinfo() {
+                    for (axis, info) in axes {
+                        let code = if axis == AbsoluteAxisCode::ABS_Z && !has_key(KeyCode::BTN_TL2) {
+                            "GamepadL2"
+                        } else if axis == AbsoluteAxisCode::ABS_RZ && !has_key(KeyCode::BTN_TR2) {
+                            "GamepadR2"
+                        } else {
+                            continue;
+                        };
+                        triggers.push(AnalogTrigger {
+                            axis,
+                            threshold: info.minimum() + (info.maximum() - info.minimum()) / 2,
+                            code,
+                            pressed: false,
+                        });
+                    }
+                }
+            }
+
+            let has_hat_dpad = !has_key(KeyCode::BTN_DPAD_UP) &&
+                device
+                    .supported_absolute_axes()
+                    .is_some_and(|axes| axes.contains(AbsoluteAxisCode::ABS_HAT0X));
+
+            sources.push(GamepadSource {
+                id: evdev_device_id(&device, &InputDeviceType::Gamepad),
+                device,
+                triggers,
+                has_hat_dpad,
+                hat: (0, 0),
+            });
+        }
+
+        if sources.is_empty() {
+            println!("🎮 [evdev] No gamepad devices found");
+            return;
+        }
+        println!("🎮 [evdev] Monitoring {} gamepad device(s)", sources.len());
+
+        loop {
+            for source in &mut sources {
+                let events: Vec<evdev::InputEvent> = match source.device.fetch_events() {
+                    Ok(events) => events.collect(),
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        continue;
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️ [evdev] Error fetching gamepad events: {}", e);
+                        continue;
+                    }
+                };
+
+                let device_id = Some(source.id.as_str());
+                let send = |code: &str, is_down: bool| {
+                    let event = if is_down { InputEvent::down(code) } else { InputEvent::up(code) };
+                    let _ = gamepad_tx.send(event.from_device(device_id));
+                };
+
+                for event in events {
+                    match event.event_type() {
+                        EventType::KEY => {
+                            let code = evdev_gamepad_code(KeyCode::new(event.code()));
+                            // Ignore key repeat (value == 2)
+                            if !code.is_empty() && event.value() != 2 {
+                                send(code, event.value() == 1);
+                            }
+                        }
+                        EventType::ABSOLUTE => {
+                            let axis = AbsoluteAxisCode(event.code());
+
+                            // ===== D-PAD HAT =====
+                            if source.has_hat_dpad &&
+                                (axis == AbsoluteAxisCode::ABS_HAT0X || axis == AbsoluteAxisCode::ABS_HAT0Y)
+                            {
+                                let (old, negative, positive) = if axis == AbsoluteAxisCode::ABS_HAT0X {
+                                    (&mut source.hat.0, "GamepadDpadLeft", "GamepadDpadRight")
+                                } else {
+                                    (&mut source.hat.1, "GamepadDpadUp", "GamepadDpadDown")
+                                };
+                                let value = event.value().signum();
+                                if value == *old {
+                                    continue;
+                                }
+                                match *old {
+                                    -1 => send(negative, false),
+                                    1 => send(positive, false),
+                                    _ => {}
+                                }
+                                match value {
+                                    -1 => send(negative, true),
+                                    1 => send(positive, true),
+                                    _ => {}
+                                }
+                                *old = value;
+                                continue;
+                            }
+
+                            // ===== ANALOG TRIGGERS =====
+                            if let Some(trigger) = source.triggers.iter_mut().find(|t| t.axis == axis) {
+                                let pressed = event.value() > trigger.threshold;
+                                if pressed != trigger.pressed {
+                                    trigger.pressed = pressed;
+                                    send(trigger.code, pressed);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            // Small sleep to prevent busy-waiting
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+}
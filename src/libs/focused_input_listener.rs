@@ -1,3 +1,4 @@
+use crate::libs::input_manager::InputEvent;
 use device_query::{ DeviceQuery, DeviceState, Keycode };
 use std::collections::HashSet;
 use std::sync::{ mpsc::Sender, Arc, Mutex };
@@ -5,7 +6,7 @@ use std::thread;
 use std::time::Duration;
 
 /// Maps device_query Keycode to our standardized key code format (same as rdev)
-fn map_device_query_keycode(key: Keycode) -> &'static str {
+pub(crate) fn map_device_query_keycode(key: Keycode) -> &'static str {
     match key {
         // Letters
         Keycode::A => "KeyA",
@@ -53,6 +54,7 @@ fn map_device_query_keycode(key: Keycode) -> &'static str {
         Keycode::Enter => "Enter",
         Keycode::Tab => "Tab",
         Keycode::Escape => "Escape",
+        Keycode::CapsLock => "CapsLock",
         Keycode::Delete => "Delete",
         Keycode::Insert => "Insert",
 
@@ -65,6 +67,10 @@ fn map_device_query_keycode(key: Keycode) -> &'static str {
         Keycode::RAlt => "AltRight",
         Keycode::LMeta => "MetaLeft",
         Keycode::RMeta => "MetaRight",
+        Keycode::Command => "MetaLeft", // macOS
+        Keycode::RCommand => "MetaRight",
+        Keycode::LOption => "AltLeft",
+        Keycode::ROption => "AltRight",
 
         // Arrow keys
         Keycode::Up => "ArrowUp",
@@ -91,6 +97,14 @@ fn map_device_query_keycode(key: Keycode) -> &'static str {
         Keycode::F10 => "F10",
         Keycode::F11 => "F11",
         Keycode::F12 => "F12",
+        Keycode::F13 => "F13",
+        Keycode::F14 => "F14",
+        Keycode::F15 => "F15",
+        Keycode::F16 => "F16",
+        Keycode::F17 => "F17",
+        Keycode::F18 => "F18",
+        Keycode::F19 => "F19",
+        Keycode::F20 => "F20",
 
         // Punctuation
         Keycode::Minus => "Minus",
@@ -116,15 +130,20 @@ fn map_device_query_keycode(key: Keycode) -> &'static str {
         Keycode::Numpad7 => "Numpad7",
         Keycode::Numpad8 => "Numpad8",
         Keycode::Numpad9 => "Numpad9",
-
-        _ => "",
+        Keycode::NumpadAdd => "NumpadAdd",
+        Keycode::NumpadSubtract => "NumpadSubtract",
+        Keycode::NumpadMultiply => "NumpadMultiply",
+        Keycode::NumpadDivide => "NumpadDivide",
+        Keycode::NumpadDecimal => "NumpadDecimal",
+        Keycode::NumpadEnter => "NumpadEnter",
+        Keycode::NumpadEquals => "NumpadEqual",
     }
 }
 
 /// Start the focused keyboard listener (uses device_query polling)
 /// This listener is ONLY active when the window is focused
 pub fn start_focused_keyboard_listener(
-    keyboard_tx: Sender<String>,
+    keyboard_tx: Sender<InputEvent>,
     is_focused: Arc<Mutex<bool>>,
 ) {
     thread::spawn(move || {
@@ -157,7 +176,7 @@ pub fn start_focused_keyboard_listener(
                     let key_code = map_device_query_keycode(*key);
                     if !key_code.is_empty() {
                         // Send key event without logging sensitive keystrokes
-                        let _ = keyboard_tx.send(key_code.to_string());
+                        let _ = keyboard_tx.send(InputEvent::down(key_code));
                     }
                 }
 
@@ -165,7 +184,7 @@ pub fn start_focused_keyboard_listener(
                 for key in prev_keys.difference(&current_keys) {
                     let key_code = map_device_query_keycode(*key);
                     if !key_code.is_empty() {
-                        let _ = keyboard_tx.send(format!("UP:{}", key_code));
+                        let _ = keyboard_tx.send(InputEvent::up(key_code));
                     }
                 }
 
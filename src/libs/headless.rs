@@ -0,0 +1,102 @@
+/// Headless mode: play sounds without creating the Dioxus window
+///
+/// Started with `--headless`, e.g. from a systemd user service or on a machine used over SSH.
+/// Uses the same `AppConfig`, soundpacks and input listeners as the desktop app.
+use crate::libs::control_socket::process_audio_commands;
+use crate::libs::input_filter::filter_event;
+use crate::libs::input_manager::get_input_channels;
+use crate::libs::AudioContext;
+use crate::state::config::AppConfig;
+use crate::{ always_eprint, always_print, debug_print };
+use std::thread;
+use std::time::{ Duration, Instant };
+
+/// How often finished sinks are dropped
+const SINK_CLEANUP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Run the headless event loop, never returns
+pub fn run(start_ambiance: bool) -> ! {
+    always_print!("🎧 Running headless, press Ctrl+C to quit");
+
+    // Loads the configured keyboard, mouse and gamepad soundpacks
+    let ctx = AudioContext::new();
+
+    if start_ambiance {
+        start_saved_ambiance();
+    }
+
+    let channels = get_input_channels();
+    let keyboard_rx = channels.keyboard_rx.lock().unwrap();
+    let mouse_rx = channels.mouse_rx.lock().unwrap();
+    let gamepad_rx = channels.gamepad_rx.lock().unwrap();
+    let hotkey_rx = channels.hotkey_rx.lock().unwrap();
+
+    let mut last_cleanup = Instant::now();
+    loop {
+        let mut idle = true;
+
+        if let Ok(event) = keyboard_rx.try_recv() {
+            idle = false;
+            if filter_event(&event) && !event.code.is_empty() {
+                ctx.play_key_event_sound(&event.code, event.is_down, event.device_id.as_deref());
+            }
+        }
+
+        if let Ok(event) = mouse_rx.try_recv() {
+            idle = false;
+            if filter_event(&event) && !event.code.is_empty() {
+                ctx.play_mouse_event_sound(&event.code, event.is_down, event.device_id.as_deref());
+            }
+        }
+
+        if let Ok(event) = gamepad_rx.try_recv() {
+            idle = false;
+            if filter_event(&event) {
+                ctx.play_gamepad_event_sound(&event.code, event.is_down, event.device_id.as_deref());
+            }
+        }
+
+        if let Ok(hotkey_command) = hotkey_rx.try_recv() {
+            idle = false;
+            if hotkey_command == "TOGGLE_SOUND" {
+                toggle_sound();
+            }
+        }
+
+        // Volume and soundpack changes from `mechaura ctl`
+        process_audio_commands(&ctx);
+
+        if last_cleanup.elapsed() >= SINK_CLEANUP_INTERVAL {
+            ctx.cleanup_finished_sinks();
+            last_cleanup = Instant::now();
+        }
+
+        // Only sleep when every channel was empty so bursts of input are drained quickly
+        if idle {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+/// Same as the Ctrl+Alt+M handler in the UI, minus the tray update
+fn toggle_sound() {
+    let mut config = AppConfig::load();
+    config.enable_sound = !config.enable_sound;
+    config.last_updated = chrono::Utc::now();
+    match config.save() {
+        Ok(_) => always_print!("🔄 Sound {}", if config.enable_sound { "enabled" } else { "muted" }),
+        Err(e) => always_eprint!("❌ Failed to save config after sound toggle: {}", e),
+    }
+}
+
+/// Play the ambiance sounds selected on the Mood page
+fn start_saved_ambiance() {
+    crate::state::ambiance::update_global_ambiance_player_state(|player| {
+        if player.active_sounds.is_empty() {
+            debug_print!("🎵 No ambiance sounds selected, nothing to play");
+            return;
+        }
+        // Ambiance always starts paused, so this starts every selected sound
+        player.toggle_play_pause();
+    });
+}
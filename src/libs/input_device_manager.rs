@@ -1,3 +1,4 @@
+use crate::state::config::AppConfig;
 use std::collections::HashMap;
 use std::ptr::null_mut;
 use std::ffi::OsString;
@@ -34,6 +35,7 @@ pub struct InputDeviceInfo {
 pub enum InputDeviceType {
     Keyboard,
     Mouse,
+    Gamepad,
     Unknown,
 }
 
@@ -52,21 +54,97 @@ impl InputDeviceManager {
         }
     }
 
+    /// Create a manager that uses the enabled device lists from the app config
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            devices: HashMap::new(),
+            enabled_keyboards: config.enabled_keyboards.clone(),
+            enabled_mice: config.enabled_mice.clone(),
+        }
+    }
+
     /// Enumerate all input devices
     pub fn enumerate_devices(&mut self) -> Result<(), String> {
         #[cfg(windows)]
         {
             self.enumerate_windows_devices()
         }
-        #[cfg(not(windows))]
+        #[cfg(target_os = "linux")]
+        {
+            self.enumerate_evdev_devices()
+        }
+        #[cfg(not(any(windows, target_os = "linux")))]
         {
-            // For non-Windows platforms, we'll use a simplified approach
+            // For other platforms, we'll use a simplified approach
             // that just returns the default device
             self.add_default_devices();
             Ok(())
         }
     }
 
+    #[cfg(target_os = "linux")]
+    fn enumerate_evdev_devices(&mut self) -> Result<(), String> {
+        use crate::libs::evdev_input_listener::{
+            is_gamepad_device,
+            is_keyboard_device,
+            is_pointer_device,
+        };
+
+        for (_path, device) in evdev::enumerate() {
+            let input_id = device.input_id();
+            let vendor_id = input_id.vendor();
+            let product_id = input_id.product();
+
+            let name = match device.name() {
+                Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+                _ => {
+                    let vid = format!("{:04X}", vendor_id);
+                    let pid = format!("{:04X}", product_id);
+                    let manufacturer = self.get_manufacturer_name(&vid);
+                    if manufacturer.is_empty() {
+                        format!("Device VID:{} PID:{}", vid, pid)
+                    } else {
+                        format!("{} Device (VID:{} PID:{})", manufacturer, vid, pid)
+                    }
+                }
+            };
+
+            // A single event node can expose both roles (e.g. wireless receivers),
+            // so it gets one entry per role with its own ID
+            let mut roles = Vec::new();
+            if is_keyboard_device(&device) {
+                roles.push(InputDeviceType::Keyboard);
+            }
+            if is_pointer_device(&device) {
+                roles.push(InputDeviceType::Mouse);
+            }
+            if is_gamepad_device(&device) {
+                roles.push(InputDeviceType::Gamepad);
+            }
+
+            for device_type in roles {
+                let id = evdev_device_id(&device, &device_type);
+                let is_enabled = self.should_process_device(&id, device_type.clone());
+                self.devices.insert(id.clone(), InputDeviceInfo {
+                    id,
+                    name: name.clone(),
+                    device_type,
+                    vendor_id,
+                    product_id,
+                    is_enabled,
+                });
+            }
+        }
+
+        if self.devices.is_empty() {
+            return Err(
+                "No input devices found. Make sure you're in the 'input' group: sudo usermod -a -G input $USER".to_string()
+            );
+        }
+
+        Ok(())
+    }
+
     #[cfg(windows)]
     fn enumerate_windows_devices(&mut self) -> Result<(), String> {
         unsafe {
@@ -194,7 +272,7 @@ impl InputDeviceManager {
         })
     }
 
-    #[cfg(not(windows))]
+    #[cfg(not(any(windows, target_os = "linux")))]
     fn add_default_devices(&mut self) {
         // Add default keyboard and mouse for non-Windows platforms
         self.devices.insert("default_keyboard".to_string(), InputDeviceInfo {
@@ -507,11 +585,42 @@ impl InputDeviceManager {
             InputDeviceType::Mouse => {
                 self.enabled_mice.is_empty() || self.enabled_mice.contains(&device_id.to_string())
             }
+            InputDeviceType::Gamepad => true, // Gamepads have no enable list, use enable_gamepad_sound
             _ => false,
         }
     }
 }
 
+/// Build a stable ID for an evdev device from its vendor, product and uniq or phys values
+/// The uniq value (serial number) follows the device across ports, phys is the fallback
+/// The role prefix keeps the keyboard and mouse halves of a combined device apart
+#[cfg(target_os = "linux")]
+pub fn evdev_device_id(device: &evdev::Device, device_type: &InputDeviceType) -> String {
+    use sha2::{ Digest, Sha256 };
+
+    let role = match device_type {
+        InputDeviceType::Keyboard => "kbd",
+        InputDeviceType::Mouse => "mouse",
+        InputDeviceType::Gamepad => "pad",
+        InputDeviceType::Unknown => "unknown",
+    };
+    let input_id = device.input_id();
+
+    // Saved in the config, so the digest must not change between builds
+    let location = device
+        .unique_name()
+        .filter(|uniq| !uniq.trim().is_empty())
+        .map(|uniq| format!("uniq:{}", uniq))
+        .unwrap_or_else(|| format!("phys:{}", device.physical_path().unwrap_or("")));
+    let digest = Sha256::digest(location.as_bytes());
+    let hash: String = digest[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    format!("evdev-{}-{:04x}:{:04x}-{}", role, input_id.vendor(), input_id.product(), hash)
+}
+
 impl Default for InputDeviceManager {
     fn default() -> Self {
         Self::new()
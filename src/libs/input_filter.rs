@@ -0,0 +1,211 @@
+/// Debounce and chatter detection shared by every input backend
+use crate::libs::input_manager::InputEvent;
+use crate::state::config::{ AppConfig, DebounceConfig };
+use std::collections::HashMap;
+use std::sync::{ Mutex, OnceLock };
+use std::time::{ Duration, Instant };
+
+/// How often the debounce settings are re-read from the config
+const CONFIG_REFRESH: Duration = Duration::from_secs(1);
+
+/// Static global filter, fed from the UI event loop
+static INPUT_FILTER: OnceLock<Mutex<InputFilter>> = OnceLock::new();
+
+#[derive(Default)]
+struct KeyTiming {
+    last_down: Option<Instant>, // Last accepted press
+    last_up: Option<Instant>,
+    suppressed: bool, // Current press was debounced, so its release is dropped too
+}
+
+pub struct InputFilter {
+    config: DebounceConfig,
+    config_loaded: Instant,
+    keys: HashMap<String, KeyTiming>, // code@device -> timing
+    chatter: HashMap<String, u32>, // code -> number of suspicious double-fires
+}
+
+impl InputFilter {
+    pub fn new(config: DebounceConfig) -> Self {
+        Self {
+            config,
+            config_loaded: Instant::now(),
+            keys: HashMap::new(),
+            chatter: HashMap::new(),
+        }
+    }
+
+    /// Decide whether an event should make a sound
+    pub fn accept(&mut self, event: &InputEvent) -> bool {
+        // Wheel notches have no release and are rate-limited by the listeners
+        if event.code.starts_with("MouseWheel") {
+            return true;
+        }
+
+        let state_key = match &event.device_id {
+            Some(id) => format!("{}@{}", event.code, id),
+            None => event.code.clone(),
+        };
+        let timing = self.keys.entry(state_key).or_default();
+
+        if !event.is_down {
+            timing.last_up = Some(event.time);
+            return !std::mem::take(&mut timing.suppressed);
+        }
+
+        // Chatter: the switch reports a new press right after it was released
+        if let Some(last_up) = timing.last_up {
+            let gap = event.time.saturating_duration_since(last_up);
+            if gap < Duration::from_millis(self.config.chatter_threshold_ms as u64) {
+                let count = self.chatter.entry(event.code.clone()).or_insert(0);
+                *count += 1;
+                println!(
+                    "⚠️ Possible switch chatter on '{}': pressed again {:.1}ms after release ({}x)",
+                    event.code,
+                    gap.as_secs_f32() * 1000.0,
+                    count
+                );
+            }
+        }
+
+        if self.config.enabled {
+            let debounce = Duration::from_millis(self.config.debounce_ms(&event.code) as u64);
+            if let Some(last_down) = timing.last_down {
+                if event.time.saturating_duration_since(last_down) < debounce {
+                    timing.suppressed = true;
+                    return false;
+                }
+            }
+        }
+
+        timing.last_down = Some(event.time);
+        timing.suppressed = false;
+        true
+    }
+
+    pub fn set_config(&mut self, config: DebounceConfig) {
+        self.config = config;
+        self.config_loaded = Instant::now();
+    }
+}
+
+fn global_filter() -> &'static Mutex<InputFilter> {
+    INPUT_FILTER.get_or_init(|| Mutex::new(InputFilter::new(AppConfig::load().debounce)))
+}
+
+/// Run an event through the global filter, returns false if it should be dropped
+pub fn filter_event(event: &InputEvent) -> bool {
+    let mut filter = global_filter().lock().unwrap();
+    if filter.config_loaded.elapsed() >= CONFIG_REFRESH {
+        // Keep the current settings if the config can't be read right now
+        match AppConfig::read() {
+            Some(config) => filter.set_config(config.debounce),
+            None => filter.config_loaded = Instant::now(),
+        }
+    }
+    filter.accept(event)
+}
+
+/// Keys flagged for chatter since startup, most frequent first
+pub fn chatter_report() -> Vec<(String, u32)> {
+    let filter = global_filter().lock().unwrap();
+    let mut report: Vec<(String, u32)> = filter.chatter
+        .iter()
+        .map(|(code, count)| (code.clone(), *count))
+        .collect();
+    report.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    report
+}
+
+pub fn clear_chatter_report() {
+    global_filter().lock().unwrap().chatter.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::key_codes::KeyClass;
+
+    fn event(code: &str, is_down: bool, device_id: Option<&str>, time: Instant) -> InputEvent {
+        InputEvent {
+            code: code.to_string(),
+            is_down,
+            device_id: device_id.map(str::to_string),
+            time,
+        }
+    }
+
+    fn ms(start: Instant, offset: u64) -> Instant {
+        start + Duration::from_millis(offset)
+    }
+
+    #[test]
+    fn debounced_press_drops_its_release() {
+        let mut filter = InputFilter::new(DebounceConfig::default());
+        let t0 = Instant::now();
+
+        assert!(filter.accept(&event("KeyA", true, None, t0)));
+        assert!(filter.accept(&event("KeyA", false, None, ms(t0, 1))));
+        // Bounce within the 5 ms default, press and release are both dropped
+        assert!(!filter.accept(&event("KeyA", true, None, ms(t0, 2))));
+        assert!(!filter.accept(&event("KeyA", false, None, ms(t0, 3))));
+        // The next real keystroke plays again
+        assert!(filter.accept(&event("KeyA", true, None, ms(t0, 40))));
+        assert!(filter.accept(&event("KeyA", false, None, ms(t0, 60))));
+        // Only the bounce came back within the 30 ms chatter threshold
+        assert_eq!(filter.chatter.get("KeyA"), Some(&1));
+    }
+
+    #[test]
+    fn per_key_beats_class_beats_default() {
+        let mut filter = InputFilter::new(DebounceConfig {
+            enabled: true,
+            default_ms: 5,
+            class_ms: HashMap::from([(KeyClass::Letter, 20)]),
+            key_ms: HashMap::from([("KeyB".to_string(), 50)]),
+            chatter_threshold_ms: 0,
+        });
+        let t0 = Instant::now();
+
+        // Class setting for letters
+        assert!(filter.accept(&event("KeyA", true, None, t0)));
+        assert!(!filter.accept(&event("KeyA", true, None, ms(t0, 10))));
+        assert!(filter.accept(&event("KeyA", true, None, ms(t0, 25))));
+
+        // Per-key setting wins over the letter class
+        assert!(filter.accept(&event("KeyB", true, None, t0)));
+        assert!(!filter.accept(&event("KeyB", true, None, ms(t0, 30))));
+        assert!(filter.accept(&event("KeyB", true, None, ms(t0, 55))));
+
+        // No class setting for digits, the default applies
+        assert!(filter.accept(&event("Digit1", true, None, t0)));
+        assert!(filter.accept(&event("Digit1", true, None, ms(t0, 6))));
+    }
+
+    #[test]
+    fn devices_are_debounced_separately() {
+        let mut filter = InputFilter::new(DebounceConfig::default());
+        let t0 = Instant::now();
+
+        assert!(filter.accept(&event("KeyA", true, Some("kbd-1"), t0)));
+        assert!(filter.accept(&event("KeyA", true, Some("kbd-2"), ms(t0, 1))));
+        assert!(!filter.accept(&event("KeyA", true, Some("kbd-1"), ms(t0, 2))));
+        // Backends without device IDs share one state per key
+        assert!(filter.accept(&event("KeyA", true, None, ms(t0, 3))));
+    }
+
+    #[test]
+    fn wheel_and_disabled_debounce_pass_everything() {
+        let mut filter = InputFilter::new(DebounceConfig { default_ms: 100, ..DebounceConfig::default() });
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            assert!(filter.accept(&event("MouseWheelUp", true, None, t0)));
+        }
+        assert!(filter.accept(&event("MouseLeft", true, None, t0)));
+        assert!(!filter.accept(&event("MouseLeft", true, None, ms(t0, 10))));
+
+        filter.set_config(DebounceConfig { enabled: false, ..DebounceConfig::default() });
+        assert!(filter.accept(&event("KeyA", true, None, t0)));
+        assert!(filter.accept(&event("KeyA", true, None, ms(t0, 1))));
+    }
+}
@@ -1,11 +1,11 @@
+use crate::libs::input_manager::InputEvent;
 use rdev::{ listen, Button, Event, EventType, Key };
 use std::collections::HashSet;
 use std::sync::{ mpsc::Sender, Arc, Mutex };
 use std::thread;
-use std::time::{ Duration, Instant };
 
 // Maps a keyboard key to its standardized code
-fn map_key_to_code(key: Key) -> &'static str {
+pub(crate) fn map_key_to_code(key: Key) -> &'static str {
     match key {
         // Common keys across all platforms
         Key::Space => "Space",
@@ -37,7 +37,9 @@ fn map_key_to_code(key: Key) -> &'static str {
         Key::End => "End",
         Key::PageUp => "PageUp",
         Key::PageDown => "PageDown",
-        Key::Insert => "Insert", // Function keys F1-F12 (rdev 0.5.3 only supports F1-F12)
+        Key::Insert => "Insert",
+
+        // Function keys F1-F12 (F13-F24 arrive as Key::Unknown, see below)
         Key::F1 => "F1",
         Key::F2 => "F2",
         Key::F3 => "F3",
@@ -130,11 +132,36 @@ fn map_key_to_code(key: Key) -> &'static str {
         Key::Pause => "Pause",
         Key::Function => "Fn", // Special function key on some keyboards
 
-        // Unknown or unmapped keys
-        Key::Unknown(_) => "", // Handle unknown keys gracefully
+        // Keys without a named rdev variant (F13-F24, media, Intl/Lang, ContextMenu...)
+        Key::Unknown(code) => map_native_keycode(code),
+    }
+}
+
+// Maps a platform scancode that rdev could not name to its standardized code
+#[cfg(target_os = "linux")]
+fn map_native_keycode(code: u32) -> &'static str {
+    // rdev reports X11 keycodes, which are evdev codes offset by 8
+    match code.checked_sub(8).and_then(|code| u16::try_from(code).ok()) {
+        Some(code) => crate::libs::key_codes::evdev_key_code(evdev::KeyCode::new(code)),
+        None => "",
     }
 }
 
+#[cfg(windows)]
+fn map_native_keycode(code: u32) -> &'static str {
+    crate::libs::key_codes::windows_vk_key_code(code)
+}
+
+#[cfg(target_os = "macos")]
+fn map_native_keycode(code: u32) -> &'static str {
+    crate::libs::key_codes::macos_key_code(code)
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+fn map_native_keycode(_code: u32) -> &'static str {
+    ""
+}
+
 // Maps a mouse button to its standardized code
 fn map_button_to_code(button: Button) -> &'static str {
     match button {
@@ -161,8 +188,8 @@ fn map_button_to_code(button: Button) -> &'static str {
 /// When is_focused is provided, keyboard events are only sent when the window is UNFOCUSED
 /// to avoid duplicate events with the focused_input_listener
 pub fn start_unified_input_listener(
-    keyboard_tx: Sender<String>,
-    mouse_tx: Sender<String>,
+    keyboard_tx: Sender<InputEvent>,
+    mouse_tx: Sender<InputEvent>,
     hotkey_tx: Sender<String>,
     is_focused: Option<Arc<Mutex<bool>>>,
 ) {
@@ -172,8 +199,7 @@ pub fn start_unified_input_listener(
         println!("🎮 Unified input listener thread started");
 
         // Separate state tracking for keyboard and mouse
-        let keyboard_last_press = Arc::new(Mutex::new(Instant::now()));
-        let mouse_last_press = Arc::new(Mutex::new(Instant::now()));
+        // (debouncing happens in input_filter, shared with the other backends)
         let pressed_keys = Arc::new(Mutex::new(HashSet::<String>::new()));
         let pressed_buttons = Arc::new(Mutex::new(HashSet::<String>::new()));
 
@@ -223,20 +249,9 @@ pub fn start_unified_input_listener(
                             return; // Key already pressed, ignore
                         }
                         pressed.insert(key_code.to_string());
-                        drop(pressed); // Apply debounce and detect rapid key events
-                        let now = Instant::now();
-                        let mut last = keyboard_last_press.lock().unwrap();
-                        let time_since_last = now.duration_since(*last);
-
-                        // Special handling for Backspace key - skip if too rapid (< 10ms)
-                        if key_code == "Backspace" && time_since_last < Duration::from_millis(10) {
-                            return; // Skip this Backspace event entirely
-                        }
+                        drop(pressed);
 
-                        if time_since_last > Duration::from_millis(1) {
-                            *last = now;
-                            let _ = keyboard_tx.send(key_code.to_string());
-                        }
+                        let _ = keyboard_tx.send(InputEvent::down(key_code));
                     }
                 }
                 EventType::KeyRelease(key) => {
@@ -265,7 +280,7 @@ pub fn start_unified_input_listener(
                         pressed.remove(&key_code.to_string());
                         drop(pressed);
 
-                        let _ = keyboard_tx.send(format!("UP:{}", key_code));
+                        let _ = keyboard_tx.send(InputEvent::up(key_code));
                     }
                 }
 
@@ -282,27 +297,9 @@ pub fn start_unified_input_listener(
                             return; // Button already pressed, ignore
                         }
                         pressed.insert(button_code.to_string());
-                        drop(pressed); // Apply debounce and detect rapid mouse events
-                        let now = Instant::now();
-                        let mut last = mouse_last_press.lock().unwrap();
-                        let time_since_last = now.duration_since(*last);
-
-                        // General rapid event detection (< 60ms) - log but still process
-                        if
-                            time_since_last < Duration::from_millis(60) &&
-                            time_since_last > Duration::from_millis(1)
-                        {
-                            println!(
-                                "⚡ RAPID MOUSE EVENT detected: '{}' fired {:.1}ms after previous mouse event",
-                                button_code,
-                                time_since_last.as_millis()
-                            );
-                        }
+                        drop(pressed);
 
-                        if time_since_last > Duration::from_millis(1) {
-                            *last = now;
-                            let _ = mouse_tx.send(button_code.to_string());
-                        }
+                        let _ = mouse_tx.send(InputEvent::down(button_code));
                     }
                 }
                 EventType::ButtonRelease(button) => {
@@ -315,7 +312,7 @@ pub fn start_unified_input_listener(
                         pressed.remove(&button_code.to_string());
                         drop(pressed);
 
-                        let _ = mouse_tx.send(format!("UP:{}", button_code));
+                        let _ = mouse_tx.send(InputEvent::up(button_code));
                     }
                 }
                 // Skip mouse wheel events for now
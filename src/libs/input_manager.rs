@@ -1,5 +1,6 @@
 /// Global input manager to handle input channels between main and UI
 use std::sync::{ mpsc, Arc, Mutex, OnceLock };
+use std::time::Instant;
 
 /// Static global holder for input channels
 static INPUT_CHANNELS: OnceLock<InputChannels> = OnceLock::new();
@@ -7,28 +8,68 @@ static INPUT_CHANNELS: OnceLock<InputChannels> = OnceLock::new();
 /// Static global holder for window focus state
 static WINDOW_FOCUS_STATE: OnceLock<Arc<Mutex<bool>>> = OnceLock::new();
 
+/// A key, mouse button or gamepad button event sent from an input listener to the UI
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputEvent {
+    pub code: String, // Standardized code, e.g. "KeyA" or "MouseLeft"
+    pub is_down: bool,
+    pub device_id: Option<String>, // InputDeviceManager ID, only set by backends that can tell devices apart
+    pub time: Instant, // When the backend saw the event, used for debouncing
+}
+
+impl InputEvent {
+    /// Key or button press
+    pub fn down(code: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            is_down: true,
+            device_id: None,
+            time: Instant::now(),
+        }
+    }
+
+    /// Key or button release
+    pub fn up(code: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            is_down: false,
+            device_id: None,
+            time: Instant::now(),
+        }
+    }
+
+    /// Attach the source device ID
+    pub fn from_device(mut self, device_id: Option<&str>) -> Self {
+        self.device_id = device_id.map(|id| id.to_string());
+        self
+    }
+}
+
 /// Struct to hold input event channels
 pub struct InputChannels {
-    pub keyboard_rx: Arc<Mutex<mpsc::Receiver<String>>>,
-    pub mouse_rx: Arc<Mutex<mpsc::Receiver<String>>>,
+    pub keyboard_rx: Arc<Mutex<mpsc::Receiver<InputEvent>>>,
+    pub mouse_rx: Arc<Mutex<mpsc::Receiver<InputEvent>>>,
+    pub gamepad_rx: Arc<Mutex<mpsc::Receiver<InputEvent>>>,
     pub hotkey_rx: Arc<Mutex<mpsc::Receiver<String>>>,
-    pub keyboard_tx: Arc<Mutex<mpsc::Sender<String>>>,
-    pub mouse_tx: Arc<Mutex<mpsc::Sender<String>>>,
+    pub keyboard_tx: Arc<Mutex<mpsc::Sender<InputEvent>>>,
+    pub mouse_tx: Arc<Mutex<mpsc::Sender<InputEvent>>>,
     pub hotkey_tx: Arc<Mutex<mpsc::Sender<String>>>,
 }
 
 /// Initialize input channels (called from main)
 pub fn init_input_channels(
-    keyboard_rx: mpsc::Receiver<String>,
-    mouse_rx: mpsc::Receiver<String>,
+    keyboard_rx: mpsc::Receiver<InputEvent>,
+    mouse_rx: mpsc::Receiver<InputEvent>,
+    gamepad_rx: mpsc::Receiver<InputEvent>,
     hotkey_rx: mpsc::Receiver<String>,
-    keyboard_tx: mpsc::Sender<String>,
-    mouse_tx: mpsc::Sender<String>,
+    keyboard_tx: mpsc::Sender<InputEvent>,
+    mouse_tx: mpsc::Sender<InputEvent>,
     hotkey_tx: mpsc::Sender<String>
 ) {
     let channels = InputChannels {
         keyboard_rx: Arc::new(Mutex::new(keyboard_rx)),
         mouse_rx: Arc::new(Mutex::new(mouse_rx)),
+        gamepad_rx: Arc::new(Mutex::new(gamepad_rx)),
         hotkey_rx: Arc::new(Mutex::new(hotkey_rx)),
         keyboard_tx: Arc::new(Mutex::new(keyboard_tx)),
         mouse_tx: Arc::new(Mutex::new(mouse_tx)),
@@ -0,0 +1,605 @@
+// Canonical key codes shared by every input backend.
+//
+// Soundpack definitions are keyed by W3C `KeyboardEvent.code` names, so each backend
+// (rdev, device_query, evdev) maps its native key identifiers onto this table.
+// The native scancode tables below are also used by rdev for keys it reports as `Key::Unknown`.
+
+use serde::{ Deserialize, Serialize };
+
+/// Every key code a backend may emit, using W3C `KeyboardEvent.code` names
+pub const KEY_CODES: &[&str] = &[
+    // Alphanumeric section
+    "Backquote", "Backslash", "BracketLeft", "BracketRight", "Comma", "Equal", "Minus", "Period",
+    "Quote", "Semicolon", "Slash", "IntlBackslash", "IntlRo", "IntlYen",
+    "Digit0", "Digit1", "Digit2", "Digit3", "Digit4", "Digit5", "Digit6", "Digit7", "Digit8", "Digit9",
+    "KeyA", "KeyB", "KeyC", "KeyD", "KeyE", "KeyF", "KeyG", "KeyH", "KeyI", "KeyJ", "KeyK", "KeyL", "KeyM",
+    "KeyN", "KeyO", "KeyP", "KeyQ", "KeyR", "KeyS", "KeyT", "KeyU", "KeyV", "KeyW", "KeyX", "KeyY", "KeyZ",
+
+    // Functional keys in the alphanumeric section
+    "AltLeft", "AltRight", "Backspace", "CapsLock", "ContextMenu", "ControlLeft", "ControlRight",
+    "Enter", "MetaLeft", "MetaRight", "ShiftLeft", "ShiftRight", "Space", "Tab",
+    "Convert", "KanaMode", "NonConvert", "Lang1", "Lang2", "Lang3", "Lang4", "Lang5",
+
+    // Control pad and arrow pad
+    "Delete", "End", "Home", "Insert", "PageDown", "PageUp",
+    "ArrowDown", "ArrowLeft", "ArrowRight", "ArrowUp",
+
+    // Numpad
+    "NumLock", "Numpad0", "Numpad1", "Numpad2", "Numpad3", "Numpad4", "Numpad5", "Numpad6", "Numpad7",
+    "Numpad8", "Numpad9", "NumpadAdd", "NumpadComma", "NumpadDecimal", "NumpadDivide", "NumpadEnter",
+    "NumpadEqual", "NumpadMultiply", "NumpadParenLeft", "NumpadParenRight", "NumpadSubtract",
+
+    // Function section
+    "Escape", "Fn", "PrintScreen", "ScrollLock", "Pause",
+    "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
+    "F13", "F14", "F15", "F16", "F17", "F18", "F19", "F20", "F21", "F22", "F23", "F24",
+
+    // Media keys
+    "AudioVolumeDown", "AudioVolumeMute", "AudioVolumeUp", "Eject", "MediaPlayPause", "MediaSelect",
+    "MediaStop", "MediaTrackNext", "MediaTrackPrevious", "LaunchApp1", "LaunchApp2", "LaunchMail",
+    "BrowserBack", "BrowserFavorites", "BrowserForward", "BrowserHome", "BrowserRefresh",
+    "BrowserSearch", "BrowserStop", "Power", "Sleep", "WakeUp",
+
+    // Legacy editing keys (Sun keyboards)
+    "Again", "Copy", "Cut", "Find", "Help", "Open", "Paste", "Props", "Select", "Undo",
+];
+
+/// Check whether a code is part of the canonical key code table
+pub fn is_key_code(code: &str) -> bool {
+    KEY_CODES.contains(&code)
+}
+
+/// Full-size ANSI keyboard, row by row, left to right
+///
+/// Pack authors usually record their keys in this order, and it is the default key list
+/// for automatic segmentation.
+pub const ANSI_LAYOUT: &[&[&str]] = &[
+    &[
+        "Escape", "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8", "F9", "F10", "F11", "F12",
+        "PrintScreen", "ScrollLock", "Pause",
+    ],
+    &[
+        "Backquote", "Digit1", "Digit2", "Digit3", "Digit4", "Digit5", "Digit6", "Digit7", "Digit8",
+        "Digit9", "Digit0", "Minus", "Equal", "Backspace", "Insert", "Home", "PageUp", "NumLock",
+        "NumpadDivide", "NumpadMultiply", "NumpadSubtract",
+    ],
+    &[
+        "Tab", "KeyQ", "KeyW", "KeyE", "KeyR", "KeyT", "KeyY", "KeyU", "KeyI", "KeyO", "KeyP",
+        "BracketLeft", "BracketRight", "Backslash", "Delete", "End", "PageDown", "Numpad7", "Numpad8",
+        "Numpad9", "NumpadAdd",
+    ],
+    &[
+        "CapsLock", "KeyA", "KeyS", "KeyD", "KeyF", "KeyG", "KeyH", "KeyJ", "KeyK", "KeyL", "Semicolon",
+        "Quote", "Enter", "Numpad4", "Numpad5", "Numpad6",
+    ],
+    &[
+        "ShiftLeft", "KeyZ", "KeyX", "KeyC", "KeyV", "KeyB", "KeyN", "KeyM", "Comma", "Period", "Slash",
+        "ShiftRight", "ArrowUp", "Numpad1", "Numpad2", "Numpad3", "NumpadEnter",
+    ],
+    &[
+        "ControlLeft", "MetaLeft", "AltLeft", "Space", "AltRight", "MetaRight", "ContextMenu",
+        "ControlRight", "ArrowLeft", "ArrowDown", "ArrowRight", "Numpad0", "NumpadDecimal",
+    ],
+];
+
+/// Every gamepad and joystick button code the evdev backend may emit
+///
+/// Analog triggers and the D-pad hat are reported as button presses too.
+pub const GAMEPAD_CODES: &[&str] = &[
+    // Face buttons, named by position so they match across controller brands
+    "GamepadSouth", "GamepadEast", "GamepadNorth", "GamepadWest", "GamepadC", "GamepadZ",
+
+    // Shoulders and triggers
+    "GamepadL1", "GamepadR1", "GamepadL2", "GamepadR2",
+
+    // Center buttons and stick clicks
+    "GamepadSelect", "GamepadStart", "GamepadMode", "GamepadThumbL", "GamepadThumbR",
+
+    // D-pad
+    "GamepadDpadUp", "GamepadDpadDown", "GamepadDpadLeft", "GamepadDpadRight",
+
+    // Generic joystick buttons (BTN_TRIGGER..BTN_BASE6)
+    "Joystick1", "Joystick2", "Joystick3", "Joystick4", "Joystick5", "Joystick6", "Joystick7",
+    "Joystick8", "Joystick9", "Joystick10", "Joystick11", "Joystick12",
+];
+
+/// Check whether a code is part of the gamepad code table
+pub fn is_gamepad_code(code: &str) -> bool {
+    GAMEPAD_CODES.contains(&code)
+}
+
+/// Group of keys that share debounce settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyClass {
+    Letter,
+    Digit,
+    Modifier,
+    Editing, // Space, Enter, Backspace, Tab
+    Navigation,
+    Function,
+    Punctuation,
+    Numpad,
+    Media,
+    Mouse,
+    Gamepad,
+    Other,
+}
+
+impl KeyClass {
+    pub const ALL: [KeyClass; 12] = [
+        KeyClass::Letter,
+        KeyClass::Digit,
+        KeyClass::Modifier,
+        KeyClass::Editing,
+        KeyClass::Navigation,
+        KeyClass::Function,
+        KeyClass::Punctuation,
+        KeyClass::Numpad,
+        KeyClass::Media,
+        KeyClass::Mouse,
+        KeyClass::Gamepad,
+        KeyClass::Other,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyClass::Letter => "Letters",
+            KeyClass::Digit => "Digits",
+            KeyClass::Modifier => "Modifiers",
+            KeyClass::Editing => "Space, Enter, Backspace, Tab",
+            KeyClass::Navigation => "Arrows and navigation",
+            KeyClass::Function => "Function keys",
+            KeyClass::Punctuation => "Punctuation",
+            KeyClass::Numpad => "Numpad",
+            KeyClass::Media => "Media keys",
+            KeyClass::Mouse => "Mouse buttons",
+            KeyClass::Gamepad => "Gamepad buttons",
+            KeyClass::Other => "Other keys",
+        }
+    }
+}
+
+/// Classify a key, mouse button or gamepad button code
+pub fn key_class(code: &str) -> KeyClass {
+    match code {
+        "Space" | "Enter" | "Backspace" | "Tab" => KeyClass::Editing,
+        "CapsLock" | "Fn" => KeyClass::Modifier,
+        "Escape" | "PrintScreen" | "ScrollLock" | "Pause" => KeyClass::Function,
+        "Insert" | "Delete" | "Home" | "End" | "PageUp" | "PageDown" => KeyClass::Navigation,
+        "Backquote" | "Backslash" | "BracketLeft" | "BracketRight" | "Comma" | "Equal" | "Minus"
+        | "Period" | "Quote" | "Semicolon" | "Slash" => KeyClass::Punctuation,
+        "Eject" | "Power" | "Sleep" | "WakeUp" => KeyClass::Media,
+        _ if code.starts_with("Mouse") => KeyClass::Mouse,
+        _ if code.starts_with("Gamepad") || code.starts_with("Joystick") => KeyClass::Gamepad,
+        _ if code.starts_with("Key") => KeyClass::Letter,
+        _ if code.starts_with("Digit") => KeyClass::Digit,
+        _ if code.starts_with("Numpad") || code == "NumLock" => KeyClass::Numpad,
+        _ if code.starts_with("Arrow") => KeyClass::Navigation,
+        _ if code.starts_with("Intl") => KeyClass::Punctuation,
+        _ if
+            code.starts_with("Shift") ||
+            code.starts_with("Control") ||
+            code.starts_with("Alt") ||
+            code.starts_with("Meta")
+        => KeyClass::Modifier,
+        _ if code.len() > 1 && code.starts_with('F') && code[1..].parse::<u8>().is_ok() =>
+            KeyClass::Function,
+        _ if
+            code.starts_with("Audio") ||
+            code.starts_with("Media") ||
+            code.starts_with("Browser") ||
+            code.starts_with("Launch")
+        => KeyClass::Media,
+        _ => KeyClass::Other,
+    }
+}
+
+/// Map a Linux evdev key to its canonical code (empty string for keys without one)
+#[cfg(target_os = "linux")]
+pub fn evdev_key_code(key: evdev::KeyCode) -> &'static str {
+    use evdev::KeyCode;
+
+    match key {
+        // Letters
+        KeyCode::KEY_A => "KeyA", KeyCode::KEY_B => "KeyB", KeyCode::KEY_C => "KeyC", KeyCode::KEY_D => "KeyD",
+        KeyCode::KEY_E => "KeyE", KeyCode::KEY_F => "KeyF", KeyCode::KEY_G => "KeyG", KeyCode::KEY_H => "KeyH",
+        KeyCode::KEY_I => "KeyI", KeyCode::KEY_J => "KeyJ", KeyCode::KEY_K => "KeyK", KeyCode::KEY_L => "KeyL",
+        KeyCode::KEY_M => "KeyM", KeyCode::KEY_N => "KeyN", KeyCode::KEY_O => "KeyO", KeyCode::KEY_P => "KeyP",
+        KeyCode::KEY_Q => "KeyQ", KeyCode::KEY_R => "KeyR", KeyCode::KEY_S => "KeyS", KeyCode::KEY_T => "KeyT",
+        KeyCode::KEY_U => "KeyU", KeyCode::KEY_V => "KeyV", KeyCode::KEY_W => "KeyW", KeyCode::KEY_X => "KeyX",
+        KeyCode::KEY_Y => "KeyY", KeyCode::KEY_Z => "KeyZ",
+
+        // Numbers
+        KeyCode::KEY_1 => "Digit1", KeyCode::KEY_2 => "Digit2", KeyCode::KEY_3 => "Digit3", KeyCode::KEY_4 => "Digit4",
+        KeyCode::KEY_5 => "Digit5", KeyCode::KEY_6 => "Digit6", KeyCode::KEY_7 => "Digit7", KeyCode::KEY_8 => "Digit8",
+        KeyCode::KEY_9 => "Digit9", KeyCode::KEY_0 => "Digit0",
+
+        // Function keys
+        KeyCode::KEY_F1 => "F1", KeyCode::KEY_F2 => "F2", KeyCode::KEY_F3 => "F3", KeyCode::KEY_F4 => "F4",
+        KeyCode::KEY_F5 => "F5", KeyCode::KEY_F6 => "F6", KeyCode::KEY_F7 => "F7", KeyCode::KEY_F8 => "F8",
+        KeyCode::KEY_F9 => "F9", KeyCode::KEY_F10 => "F10", KeyCode::KEY_F11 => "F11", KeyCode::KEY_F12 => "F12",
+        KeyCode::KEY_F13 => "F13", KeyCode::KEY_F14 => "F14", KeyCode::KEY_F15 => "F15", KeyCode::KEY_F16 => "F16",
+        KeyCode::KEY_F17 => "F17", KeyCode::KEY_F18 => "F18", KeyCode::KEY_F19 => "F19", KeyCode::KEY_F20 => "F20",
+        KeyCode::KEY_F21 => "F21", KeyCode::KEY_F22 => "F22", KeyCode::KEY_F23 => "F23", KeyCode::KEY_F24 => "F24",
+        KeyCode::KEY_FN => "Fn",
+
+        // Special keys
+        KeyCode::KEY_SPACE => "Space",
+        KeyCode::KEY_ENTER => "Enter",
+        KeyCode::KEY_BACKSPACE => "Backspace",
+        KeyCode::KEY_TAB => "Tab",
+        KeyCode::KEY_ESC => "Escape",
+        KeyCode::KEY_CAPSLOCK => "CapsLock",
+        KeyCode::KEY_COMPOSE => "ContextMenu",
+        KeyCode::KEY_LEFTSHIFT => "ShiftLeft",
+        KeyCode::KEY_RIGHTSHIFT => "ShiftRight",
+        KeyCode::KEY_LEFTCTRL => "ControlLeft",
+        KeyCode::KEY_RIGHTCTRL => "ControlRight",
+        KeyCode::KEY_LEFTALT => "AltLeft",
+        KeyCode::KEY_RIGHTALT => "AltRight",
+        KeyCode::KEY_LEFTMETA => "MetaLeft",
+        KeyCode::KEY_RIGHTMETA => "MetaRight",
+
+        // Arrow keys
+        KeyCode::KEY_UP => "ArrowUp",
+        KeyCode::KEY_DOWN => "ArrowDown",
+        KeyCode::KEY_LEFT => "ArrowLeft",
+        KeyCode::KEY_RIGHT => "ArrowRight",
+
+        // Editing keys
+        KeyCode::KEY_INSERT => "Insert",
+        KeyCode::KEY_DELETE => "Delete",
+        KeyCode::KEY_HOME => "Home",
+        KeyCode::KEY_END => "End",
+        KeyCode::KEY_PAGEUP => "PageUp",
+        KeyCode::KEY_PAGEDOWN => "PageDown",
+
+        // System keys
+        KeyCode::KEY_SYSRQ => "PrintScreen",
+        KeyCode::KEY_SCROLLLOCK => "ScrollLock",
+        KeyCode::KEY_PAUSE => "Pause",
+
+        // Punctuation
+        KeyCode::KEY_MINUS => "Minus",
+        KeyCode::KEY_EQUAL => "Equal",
+        KeyCode::KEY_LEFTBRACE => "BracketLeft",
+        KeyCode::KEY_RIGHTBRACE => "BracketRight",
+        KeyCode::KEY_BACKSLASH => "Backslash",
+        KeyCode::KEY_SEMICOLON => "Semicolon",
+        KeyCode::KEY_APOSTROPHE => "Quote",
+        KeyCode::KEY_GRAVE => "Backquote",
+        KeyCode::KEY_COMMA => "Comma",
+        KeyCode::KEY_DOT => "Period",
+        KeyCode::KEY_SLASH => "Slash",
+
+        // International keys
+        KeyCode::KEY_102ND => "IntlBackslash",
+        KeyCode::KEY_RO => "IntlRo",
+        KeyCode::KEY_YEN => "IntlYen",
+        KeyCode::KEY_HENKAN => "Convert",
+        KeyCode::KEY_MUHENKAN => "NonConvert",
+        KeyCode::KEY_KATAKANAHIRAGANA => "KanaMode",
+        KeyCode::KEY_HANGEUL => "Lang1",
+        KeyCode::KEY_HANJA => "Lang2",
+        KeyCode::KEY_KATAKANA => "Lang3",
+        KeyCode::KEY_HIRAGANA => "Lang4",
+        KeyCode::KEY_ZENKAKUHANKAKU => "Lang5",
+
+        // Numpad
+        KeyCode::KEY_NUMLOCK => "NumLock",
+        KeyCode::KEY_KP0 => "Numpad0", KeyCode::KEY_KP1 => "Numpad1", KeyCode::KEY_KP2 => "Numpad2",
+        KeyCode::KEY_KP3 => "Numpad3", KeyCode::KEY_KP4 => "Numpad4", KeyCode::KEY_KP5 => "Numpad5",
+        KeyCode::KEY_KP6 => "Numpad6", KeyCode::KEY_KP7 => "Numpad7", KeyCode::KEY_KP8 => "Numpad8",
+        KeyCode::KEY_KP9 => "Numpad9",
+        KeyCode::KEY_KPPLUS => "NumpadAdd",
+        KeyCode::KEY_KPMINUS => "NumpadSubtract",
+        KeyCode::KEY_KPASTERISK => "NumpadMultiply",
+        KeyCode::KEY_KPSLASH => "NumpadDivide",
+        KeyCode::KEY_KPDOT => "NumpadDecimal",
+        KeyCode::KEY_KPCOMMA => "NumpadComma",
+        KeyCode::KEY_KPENTER => "NumpadEnter",
+        KeyCode::KEY_KPEQUAL => "NumpadEqual",
+        KeyCode::KEY_KPLEFTPAREN => "NumpadParenLeft",
+        KeyCode::KEY_KPRIGHTPAREN => "NumpadParenRight",
+
+        // Media keys
+        KeyCode::KEY_MUTE => "AudioVolumeMute",
+        KeyCode::KEY_VOLUMEDOWN => "AudioVolumeDown",
+        KeyCode::KEY_VOLUMEUP => "AudioVolumeUp",
+        KeyCode::KEY_PLAYPAUSE => "MediaPlayPause",
+        KeyCode::KEY_STOPCD => "MediaStop",
+        KeyCode::KEY_NEXTSONG => "MediaTrackNext",
+        KeyCode::KEY_PREVIOUSSONG => "MediaTrackPrevious",
+        KeyCode::KEY_EJECTCD => "Eject",
+        KeyCode::KEY_MEDIA => "MediaSelect",
+        KeyCode::KEY_COMPUTER => "LaunchApp1",
+        KeyCode::KEY_CALC => "LaunchApp2",
+        KeyCode::KEY_MAIL => "LaunchMail",
+        KeyCode::KEY_BACK => "BrowserBack",
+        KeyCode::KEY_BOOKMARKS => "BrowserFavorites",
+        KeyCode::KEY_FORWARD => "BrowserForward",
+        KeyCode::KEY_HOMEPAGE => "BrowserHome",
+        KeyCode::KEY_REFRESH => "BrowserRefresh",
+        KeyCode::KEY_SEARCH => "BrowserSearch",
+        KeyCode::KEY_STOP => "BrowserStop",
+        KeyCode::KEY_POWER => "Power",
+        KeyCode::KEY_SLEEP => "Sleep",
+        KeyCode::KEY_WAKEUP => "WakeUp",
+
+        // Legacy editing keys
+        KeyCode::KEY_AGAIN => "Again",
+        KeyCode::KEY_COPY => "Copy",
+        KeyCode::KEY_CUT => "Cut",
+        KeyCode::KEY_FIND => "Find",
+        KeyCode::KEY_HELP => "Help",
+        KeyCode::KEY_OPEN => "Open",
+        KeyCode::KEY_PASTE => "Paste",
+        KeyCode::KEY_PROPS => "Props",
+        KeyCode::KEY_SELECT => "Select",
+        KeyCode::KEY_UNDO => "Undo",
+
+        _ => "",
+    }
+}
+
+/// Map a Linux evdev gamepad or joystick button to its gamepad code (empty string for other keys)
+#[cfg(target_os = "linux")]
+pub fn evdev_gamepad_code(key: evdev::KeyCode) -> &'static str {
+    use evdev::KeyCode;
+
+    match key {
+        KeyCode::BTN_SOUTH => "GamepadSouth",
+        KeyCode::BTN_EAST => "GamepadEast",
+        KeyCode::BTN_NORTH => "GamepadNorth",
+        KeyCode::BTN_WEST => "GamepadWest",
+        KeyCode::BTN_C => "GamepadC",
+        KeyCode::BTN_Z => "GamepadZ",
+        KeyCode::BTN_TL => "GamepadL1",
+        KeyCode::BTN_TR => "GamepadR1",
+        KeyCode::BTN_TL2 => "GamepadL2",
+        KeyCode::BTN_TR2 => "GamepadR2",
+        KeyCode::BTN_SELECT => "GamepadSelect",
+        KeyCode::BTN_START => "GamepadStart",
+        KeyCode::BTN_MODE => "GamepadMode",
+        KeyCode::BTN_THUMBL => "GamepadThumbL",
+        KeyCode::BTN_THUMBR => "GamepadThumbR",
+        KeyCode::BTN_DPAD_UP => "GamepadDpadUp",
+        KeyCode::BTN_DPAD_DOWN => "GamepadDpadDown",
+        KeyCode::BTN_DPAD_LEFT => "GamepadDpadLeft",
+        KeyCode::BTN_DPAD_RIGHT => "GamepadDpadRight",
+
+        KeyCode::BTN_TRIGGER => "Joystick1",
+        KeyCode::BTN_THUMB => "Joystick2",
+        KeyCode::BTN_THUMB2 => "Joystick3",
+        KeyCode::BTN_TOP => "Joystick4",
+        KeyCode::BTN_TOP2 => "Joystick5",
+        KeyCode::BTN_PINKIE => "Joystick6",
+        KeyCode::BTN_BASE => "Joystick7",
+        KeyCode::BTN_BASE2 => "Joystick8",
+        KeyCode::BTN_BASE3 => "Joystick9",
+        KeyCode::BTN_BASE4 => "Joystick10",
+        KeyCode::BTN_BASE5 => "Joystick11",
+        KeyCode::BTN_BASE6 => "Joystick12",
+
+        _ => "",
+    }
+}
+
+/// Map a Windows virtual-key code to its canonical code
+///
+/// Only covers keys that rdev reports as `Key::Unknown`; everything else has a named rdev variant.
+#[cfg(any(windows, test))]
+pub fn windows_vk_key_code(vk: u32) -> &'static str {
+    match vk {
+        // F13-F24 (VK_F13..VK_F24)
+        0x7c => "F13", 0x7d => "F14", 0x7e => "F15", 0x7f => "F16", 0x80 => "F17", 0x81 => "F18",
+        0x82 => "F19", 0x83 => "F20", 0x84 => "F21", 0x85 => "F22", 0x86 => "F23", 0x87 => "F24",
+
+        0x5d => "ContextMenu", // VK_APPS
+        0x6c => "NumpadComma", // VK_SEPARATOR
+        0xc2 => "NumpadComma", // VK_ABNT_C2 (Brazilian keypad)
+        0xc1 => "IntlRo", // VK_ABNT_C1
+        0x15 => "Lang1", // VK_HANGUL / VK_KANA
+        0x19 => "Lang2", // VK_HANJA
+        0x1c => "Convert", // VK_CONVERT
+        0x1d => "NonConvert", // VK_NONCONVERT
+
+        // Media and browser keys
+        0xa6 => "BrowserBack",
+        0xa7 => "BrowserForward",
+        0xa8 => "BrowserRefresh",
+        0xa9 => "BrowserStop",
+        0xaa => "BrowserSearch",
+        0xab => "BrowserFavorites",
+        0xac => "BrowserHome",
+        0xad => "AudioVolumeMute",
+        0xae => "AudioVolumeDown",
+        0xaf => "AudioVolumeUp",
+        0xb0 => "MediaTrackNext",
+        0xb1 => "MediaTrackPrevious",
+        0xb2 => "MediaStop",
+        0xb3 => "MediaPlayPause",
+        0xb4 => "LaunchMail",
+        0xb5 => "MediaSelect",
+        0xb6 => "LaunchApp1",
+        0xb7 => "LaunchApp2",
+        0x5f => "Sleep", // VK_SLEEP
+
+        _ => "",
+    }
+}
+
+/// Map a macOS virtual key code (`kVK_*`) to its canonical code
+///
+/// Only covers keys that rdev reports as `Key::Unknown`; everything else has a named rdev variant.
+#[cfg(any(target_os = "macos", test))]
+pub fn macos_key_code(code: u32) -> &'static str {
+    match code {
+        // F13-F20
+        105 => "F13", 107 => "F14", 113 => "F15", 106 => "F16",
+        64 => "F17", 79 => "F18", 80 => "F19", 90 => "F20",
+
+        110 => "ContextMenu",
+        114 => "Insert", // kVK_Help sits where Insert is on PC keyboards
+        71 => "NumLock", // kVK_ANSI_KeypadClear
+        81 => "NumpadEqual",
+        95 => "NumpadComma", // kVK_JIS_KeypadComma
+        93 => "IntlYen", // kVK_JIS_Yen
+        94 => "IntlRo", // kVK_JIS_Underscore
+        104 => "Lang1", // kVK_JIS_Kana
+        102 => "Lang2", // kVK_JIS_Eisu
+
+        // Media keys
+        72 => "AudioVolumeUp",
+        73 => "AudioVolumeDown",
+        74 => "AudioVolumeMute",
+
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::libs::focused_input_listener::map_device_query_keycode;
+    use crate::libs::input_listener::map_key_to_code;
+    use device_query::Keycode;
+    use rdev::Key;
+    use std::collections::HashSet;
+
+    #[test]
+    fn key_codes_are_unique() {
+        let unique: HashSet<&str> = KEY_CODES.iter().copied().collect();
+        assert_eq!(unique.len(), KEY_CODES.len());
+    }
+
+    #[test]
+    fn ansi_layout_uses_known_unique_codes() {
+        let mut seen = HashSet::new();
+        for code in ANSI_LAYOUT.iter().flat_map(|row| row.iter()) {
+            assert!(is_key_code(code), "layout has unknown code {}", code);
+            assert!(seen.insert(*code), "layout lists {} twice", code);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn evdev_table_matches_key_codes() {
+        // 0x2ff is KEY_MAX in linux/input-event-codes.h
+        let mapped: HashSet<&str> = (0..=0x2ffu16)
+            .map(|code| evdev_key_code(evdev::KeyCode::new(code)))
+            .filter(|code| !code.is_empty())
+            .collect();
+
+        for code in &mapped {
+            assert!(is_key_code(code), "evdev maps to unknown code {}", code);
+        }
+        for code in KEY_CODES {
+            assert!(mapped.contains(code), "evdev has no key for {}", code);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn evdev_gamepad_table_matches_gamepad_codes() {
+        let mapped: HashSet<&str> = (0..=0x2ffu16)
+            .map(|code| evdev_gamepad_code(evdev::KeyCode::new(code)))
+            .filter(|code| !code.is_empty())
+            .collect();
+
+        assert_eq!(mapped.len(), GAMEPAD_CODES.len());
+        for code in GAMEPAD_CODES {
+            assert!(mapped.contains(code), "evdev has no button for {}", code);
+            assert!(!is_key_code(code), "{} is also a key code", code);
+            assert_eq!(key_class(code), KeyClass::Gamepad);
+        }
+    }
+
+    #[test]
+    fn native_tables_use_key_codes() {
+        for vk in 0..=0xff {
+            let code = windows_vk_key_code(vk);
+            assert!(code.is_empty() || is_key_code(code), "VK {:#x} maps to {}", vk, code);
+        }
+        for kvk in 0..=0x7f {
+            let code = macos_key_code(kvk);
+            assert!(code.is_empty() || is_key_code(code), "kVK {} maps to {}", kvk, code);
+        }
+    }
+
+    #[test]
+    fn rdev_and_device_query_agree() {
+        // Physical keys that both backends can name
+        let shared = [
+            (Key::KeyA, Keycode::A), (Key::KeyB, Keycode::B), (Key::KeyC, Keycode::C),
+            (Key::KeyD, Keycode::D), (Key::KeyE, Keycode::E), (Key::KeyF, Keycode::F),
+            (Key::KeyG, Keycode::G), (Key::KeyH, Keycode::H), (Key::KeyI, Keycode::I),
+            (Key::KeyJ, Keycode::J), (Key::KeyK, Keycode::K), (Key::KeyL, Keycode::L),
+            (Key::KeyM, Keycode::M), (Key::KeyN, Keycode::N), (Key::KeyO, Keycode::O),
+            (Key::KeyP, Keycode::P), (Key::KeyQ, Keycode::Q), (Key::KeyR, Keycode::R),
+            (Key::KeyS, Keycode::S), (Key::KeyT, Keycode::T), (Key::KeyU, Keycode::U),
+            (Key::KeyV, Keycode::V), (Key::KeyW, Keycode::W), (Key::KeyX, Keycode::X),
+            (Key::KeyY, Keycode::Y), (Key::KeyZ, Keycode::Z),
+            (Key::Num0, Keycode::Key0), (Key::Num1, Keycode::Key1), (Key::Num2, Keycode::Key2),
+            (Key::Num3, Keycode::Key3), (Key::Num4, Keycode::Key4), (Key::Num5, Keycode::Key5),
+            (Key::Num6, Keycode::Key6), (Key::Num7, Keycode::Key7), (Key::Num8, Keycode::Key8),
+            (Key::Num9, Keycode::Key9),
+            (Key::F1, Keycode::F1), (Key::F2, Keycode::F2), (Key::F3, Keycode::F3),
+            (Key::F4, Keycode::F4), (Key::F5, Keycode::F5), (Key::F6, Keycode::F6),
+            (Key::F7, Keycode::F7), (Key::F8, Keycode::F8), (Key::F9, Keycode::F9),
+            (Key::F10, Keycode::F10), (Key::F11, Keycode::F11), (Key::F12, Keycode::F12),
+            (Key::Escape, Keycode::Escape), (Key::Space, Keycode::Space), (Key::Tab, Keycode::Tab),
+            (Key::Return, Keycode::Enter), (Key::Backspace, Keycode::Backspace),
+            (Key::CapsLock, Keycode::CapsLock), (Key::Delete, Keycode::Delete),
+            (Key::Insert, Keycode::Insert), (Key::Home, Keycode::Home), (Key::End, Keycode::End),
+            (Key::PageUp, Keycode::PageUp), (Key::PageDown, Keycode::PageDown),
+            (Key::UpArrow, Keycode::Up), (Key::DownArrow, Keycode::Down),
+            (Key::LeftArrow, Keycode::Left), (Key::RightArrow, Keycode::Right),
+            (Key::ShiftLeft, Keycode::LShift), (Key::ShiftRight, Keycode::RShift),
+            (Key::ControlLeft, Keycode::LControl), (Key::ControlRight, Keycode::RControl),
+            (Key::Alt, Keycode::LAlt), (Key::AltGr, Keycode::RAlt),
+            (Key::MetaLeft, Keycode::LMeta), (Key::MetaRight, Keycode::RMeta),
+            (Key::Minus, Keycode::Minus), (Key::Equal, Keycode::Equal),
+            (Key::LeftBracket, Keycode::LeftBracket), (Key::RightBracket, Keycode::RightBracket),
+            (Key::BackSlash, Keycode::BackSlash), (Key::SemiColon, Keycode::Semicolon),
+            (Key::Quote, Keycode::Apostrophe), (Key::BackQuote, Keycode::Grave),
+            (Key::Comma, Keycode::Comma), (Key::Dot, Keycode::Dot), (Key::Slash, Keycode::Slash),
+            (Key::Kp0, Keycode::Numpad0), (Key::Kp1, Keycode::Numpad1), (Key::Kp2, Keycode::Numpad2),
+            (Key::Kp3, Keycode::Numpad3), (Key::Kp4, Keycode::Numpad4), (Key::Kp5, Keycode::Numpad5),
+            (Key::Kp6, Keycode::Numpad6), (Key::Kp7, Keycode::Numpad7), (Key::Kp8, Keycode::Numpad8),
+            (Key::Kp9, Keycode::Numpad9),
+            (Key::KpPlus, Keycode::NumpadAdd), (Key::KpMinus, Keycode::NumpadSubtract),
+            (Key::KpMultiply, Keycode::NumpadMultiply), (Key::KpDivide, Keycode::NumpadDivide),
+            (Key::KpReturn, Keycode::NumpadEnter), (Key::KpDelete, Keycode::NumpadDecimal),
+        ];
+        for (rdev_key, dq_key) in shared {
+            let rdev_code = map_key_to_code(rdev_key);
+            assert!(is_key_code(rdev_code), "rdev {:?} maps to {:?}", rdev_key, rdev_code);
+            assert_eq!(rdev_code, map_device_query_keycode(dq_key), "{:?} / {:?}", rdev_key, dq_key);
+        }
+
+        // Keys only one of the backends can name
+        let rdev_only = [
+            Key::IntlBackslash, Key::NumLock, Key::ScrollLock, Key::PrintScreen, Key::Pause, Key::Function,
+        ];
+        for key in rdev_only {
+            assert!(is_key_code(map_key_to_code(key)), "rdev {:?}", key);
+        }
+        let device_query_only = [
+            Keycode::F13, Keycode::F14, Keycode::F15, Keycode::F16, Keycode::F17, Keycode::F18,
+            Keycode::F19, Keycode::F20, Keycode::NumpadEquals, Keycode::Command, Keycode::RCommand,
+            Keycode::LOption, Keycode::ROption,
+        ];
+        for key in device_query_only {
+            assert!(is_key_code(map_device_query_keycode(key)), "device_query {:?}", key);
+        }
+    }
+
+    #[test]
+    fn v1_converter_uses_key_codes() {
+        for code in crate::utils::config_converter::create_iohook_to_web_key_mapping().values() {
+            assert!(is_key_code(code), "V1 converter maps to {}", code);
+        }
+    }
+}
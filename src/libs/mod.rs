@@ -1,11 +1,18 @@
 pub mod audio;
+pub mod control_socket;
 pub mod device_manager;
 pub mod focused_input_listener;
+pub mod headless;
 pub mod input_device_manager;
+pub mod input_filter;
 pub mod input_listener;
 pub mod input_manager;
+pub mod key_codes;
+pub mod pack_cli;
 pub mod protocol;
 pub mod routes;
+pub mod single_instance;
+pub mod soundpack_watcher;
 pub mod theme;
 pub mod tray;
 pub mod tray_service;
@@ -0,0 +1,848 @@
+/// Soundpack tooling for pack authors and CI: `mechaura pack <command>`
+///
+/// Every command prints one JSON report on stdout and exits with
+/// 0 on success, 1 when the pack is invalid or the operation failed, 2 on bad arguments.
+use crate::libs::key_codes::{ is_gamepad_code, is_key_code, ANSI_LAYOUT };
+use crate::state::soundpack::{ SoundpackCache, SoundpackType };
+use crate::utils::key_segmentation::{ build_config, segment_recording, SegmentOptions };
+use crate::utils::soundpack_export::export_soundpack_zip;
+use crate::utils::soundpack_installer::determine_soundpack_type;
+use crate::utils::soundpack_validator::{
+    check_timings,
+    validate_soundpack_config,
+    SoundpackValidationStatus,
+};
+use crate::utils::{ config_converter, soundpack_installer };
+use serde_json::{ json, Value };
+use std::collections::HashMap;
+use std::path::{ Path, PathBuf };
+use uuid::Uuid;
+
+const EXIT_OK: i32 = 0;
+const EXIT_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str =
+    "Usage: mechaura pack <command> [options]
+
+Commands:
+  validate <dir|zip>                  Check config.json, timings and referenced files
+  info <dir|zip>                      Print name, type, key count and files
+  convert <dir|zip> --out <zip>       Convert V1 and V2 multi-file packs to V2 single-file
+  install <dir|zip> [--force]         Install into the custom soundpacks folder
+  export <dir|type/id> <out.zip>      Pack a folder or an installed soundpack into a ZIP
+  segment <recording> [options]       Cut one long recording into key timings, writes config.json
+      --keys ansi|KeyA,KeyB,...|@file  Keys in the order they were recorded (default: ansi)
+      --name <name> --author <name>    Pack details for the generated config
+      --out <dir>                      Output folder (default: next to the recording)
+      --sensitivity <dB>               Click threshold above the noise floor (default: 12)
+      --max-hold <ms>                  Longest press-to-release gap (default: 350)
+      --force                          Overwrite an existing config.json
+
+Exit codes: 0 ok, 1 invalid pack or failed operation, 2 bad arguments";
+
+/// Options that take a value, e.g. `--out new.zip`
+const VALUE_OPTIONS: &[&str] = &["--out", "--keys", "--name", "--author", "--sensitivity", "--max-hold"];
+
+/// Parsed command line: positional arguments, `--flag`s and `--option value`s
+struct Args<'a> {
+    positional: Vec<&'a str>,
+    flags: Vec<&'a str>,
+    options: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Args<'a> {
+    fn parse(args: &'a [String]) -> Result<Self, String> {
+        let mut parsed = Args { positional: Vec::new(), flags: Vec::new(), options: HashMap::new() };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if VALUE_OPTIONS.contains(&arg.as_str()) {
+                let value = iter.next().ok_or_else(|| format!("{} needs a value", arg))?;
+                parsed.options.insert(arg, value);
+            } else if arg.starts_with("--") {
+                parsed.flags.push(arg);
+            } else {
+                parsed.positional.push(arg);
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.contains(&name)
+    }
+
+    fn option(&self, name: &str) -> Option<&'a str> {
+        self.options.get(name).copied()
+    }
+}
+
+/// Entry point, returns the process exit code
+pub fn run_pack(args: &[String]) -> i32 {
+    let args = match Args::parse(args) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("mechaura pack: {}", e);
+            return EXIT_USAGE;
+        }
+    };
+    let positional = &args.positional;
+
+    let result = match (positional.first().copied(), positional.len()) {
+        (Some("validate"), 2) => validate(positional[1]),
+        (Some("info"), 2) => info(positional[1]),
+        (Some("convert"), 2) => convert(positional[1], args.option("--out")),
+        (Some("install"), 2) => install(positional[1], args.flag("--force")),
+        (Some("export"), 3) => export(positional[1], positional[2]),
+        (Some("segment"), 2) => segment(positional[1], &args),
+        (Some("help"), _) => {
+            println!("{}", USAGE);
+            return EXIT_OK;
+        }
+        _ if args.flag("--help") => {
+            println!("{}", USAGE);
+            return EXIT_OK;
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    let (report, code) = match result {
+        Ok(report) => {
+            let code = if report["ok"].as_bool().unwrap_or(true) { EXIT_OK } else { EXIT_FAILED };
+            (report, code)
+        }
+        Err(PackError::Usage(message)) => {
+            eprintln!("mechaura pack: {}", message);
+            return EXIT_USAGE;
+        }
+        Err(PackError::Failed(message)) => (json!({ "ok": false, "error": message }), EXIT_FAILED),
+    };
+    println!("{}", serde_json::to_string_pretty(&report).unwrap_or_default());
+    code
+}
+
+enum PackError {
+    Usage(String),
+    Failed(String),
+}
+
+impl From<String> for PackError {
+    fn from(message: String) -> Self {
+        PackError::Failed(message)
+    }
+}
+
+type PackResult = Result<Value, PackError>;
+
+// ===== SOURCES =====
+
+/// Temporary folder a ZIP is unpacked into, removed on drop
+struct Staging(PathBuf);
+
+impl Staging {
+    fn new() -> Result<Self, String> {
+        let dir = std::env::temp_dir().join(format!("mechaura-pack-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create temp folder: {}", e))?;
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A pack folder on disk, unpacked from a ZIP if needed
+struct PackSource {
+    root: PathBuf,
+    _staging: Option<Staging>,
+}
+
+fn is_zip(input: &str) -> bool {
+    input.to_lowercase().ends_with(".zip")
+}
+
+fn open_source(input: &str) -> Result<PackSource, PackError> {
+    let path = Path::new(input);
+    if path.is_dir() {
+        return Ok(PackSource { root: path.to_path_buf(), _staging: None });
+    }
+    if path.is_file() && is_zip(input) {
+        let staging = Staging::new()?;
+        let root = unpack_zip(path, &staging.0)?;
+        return Ok(PackSource { root, _staging: Some(staging) });
+    }
+
+    // Installed soundpack, e.g. "keyboard/cherrymx-blue-abs"
+    let installed = PathBuf::from(crate::state::paths::soundpacks::soundpack_dir(input));
+    if input.contains('/') && installed.is_dir() {
+        return Ok(PackSource { root: installed, _staging: None });
+    }
+
+    Err(PackError::Usage(format!("'{}' is not a folder, a .zip file or an installed soundpack", input)))
+}
+
+/// Unpack a ZIP and return the folder that holds config.json
+fn unpack_zip(zip_path: &Path, target: &Path) -> Result<PathBuf, String> {
+    let files = soundpack_installer::extract_zip_safely(zip_path, target)?;
+    // The shallowest config.json is the pack root
+    soundpack_installer
+        ::find_pack_root(&files)
+        .map(|root| target.join(root))
+        .ok_or_else(|| "No config.json found in ZIP file".to_string())
+}
+
+fn read_config(root: &Path) -> Result<Value, String> {
+    let content = std::fs
+        ::read_to_string(root.join("config.json"))
+        .map_err(|e| format!("Failed to read config.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse config.json: {}", e))
+}
+
+// ===== VALIDATE =====
+
+fn validate(input: &str) -> PackResult {
+    let source = open_source(input)?;
+    let mut report = validation_report(&source.root);
+    report["path"] = json!(input);
+    Ok(report)
+}
+
+/// Structural validation plus checks against the files that are actually in the folder
+fn validation_report(root: &Path) -> Value {
+    let config_path = root.join("config.json");
+    let validation = validate_soundpack_config(&config_path.to_string_lossy());
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut timing_issues: Vec<Value> = Vec::new();
+
+    match &validation.status {
+        SoundpackValidationStatus::Valid => {}
+        SoundpackValidationStatus::VersionOneNeedsConversion => {
+            errors.push(format!("{} (run `mechaura pack convert`)", validation.message));
+        }
+        _ => errors.push(validation.message.clone()),
+    }
+
+    if validation.status == SoundpackValidationStatus::Valid {
+        if let Ok(config) = read_config(root) {
+            check_files(root, &config, &mut errors, &mut warnings);
+            // Decoding needs the audio, missing files are already reported above
+            if errors.is_empty() {
+                match check_timings(root, &config) {
+                    Ok(report) => {
+                        if !report.issues.is_empty() {
+                            warnings.push(
+                                format!(
+                                    "{} of {} segments do not fit the audio, {} can be clamped",
+                                    report.issues.len(),
+                                    report.segments_checked,
+                                    report.fixable_count()
+                                )
+                            );
+                        }
+                        timing_issues = report.issues
+                            .iter()
+                            .map(|issue| {
+                                json!({
+                                "key": issue.key,
+                                "segment": if issue.index == 0 { "down" } else { "up" },
+                                "kind": issue.kind.as_str(),
+                                "message": issue.message,
+                                "clamped_end_ms": issue.clamped_end_ms,
+                            })
+                            })
+                            .collect();
+                    }
+                    Err(e) => errors.push(e),
+                }
+            }
+        }
+    }
+
+    json!({
+        "ok": errors.is_empty(),
+        "status": validation.status.as_str(),
+        "config_version": validation.config_version,
+        "can_be_converted": validation.can_be_converted,
+        "errors": errors,
+        "warnings": warnings,
+        "timing_issues": timing_issues,
+    })
+}
+
+fn check_files(root: &Path, config: &Value, errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+    let exists = |name: &str| root.join(name.trim_start_matches("./")).is_file();
+
+    if let Some(audio_file) = config.get("audio_file").and_then(|v| v.as_str()) {
+        if !exists(audio_file) {
+            errors.push(format!("Audio file '{}' not found", audio_file));
+        }
+    }
+    if let Some(icon) = config.get("icon").and_then(|v| v.as_str()) {
+        if !icon.is_empty() && !exists(icon) {
+            warnings.push(format!("Icon '{}' not found", icon));
+        }
+    }
+
+    let soundpack_type = determine_soundpack_type(config);
+    let Some(definitions) = config.get("definitions").and_then(|v| v.as_object()) else {
+        return;
+    };
+    if definitions.is_empty() {
+        errors.push("No key definitions".to_string());
+    }
+
+    let mut unknown_codes = Vec::new();
+    for (code, definition) in definitions {
+        let known = match soundpack_type {
+            SoundpackType::Keyboard => is_key_code(code),
+            SoundpackType::Gamepad => is_gamepad_code(code),
+            // Mouse button names come straight from the listeners, nothing to check against
+            SoundpackType::Mouse => true,
+        };
+        if !known {
+            unknown_codes.push(code.clone());
+        }
+
+        if let Some(audio_file) = definition.get("audio_file").and_then(|v| v.as_str()) {
+            if !exists(audio_file) {
+                errors.push(format!("Audio file '{}' for '{}' not found", audio_file, code));
+            }
+        }
+        let timings = definition.get("timing").and_then(|v| v.as_array());
+        for (i, timing) in timings.into_iter().flatten().enumerate() {
+            let start = timing.get(0).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let end = timing.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            if start < 0.0 || end <= start {
+                errors.push(format!("Timing '{}[{}]' must satisfy 0 <= start < end, got [{}, {}]", code, i, start, end));
+            }
+        }
+    }
+
+    if !unknown_codes.is_empty() {
+        warnings.push(format!("Unknown key codes, these will never play: {}", unknown_codes.join(", ")));
+    }
+}
+
+// ===== INFO =====
+
+fn info(input: &str) -> PackResult {
+    let source = open_source(input)?;
+    let config = read_config(&source.root)?;
+    let validation = validate_soundpack_config(&source.root.join("config.json").to_string_lossy());
+
+    let mut keys: Vec<&String> = config
+        .get("definitions")
+        .or_else(|| config.get("defs"))
+        .or_else(|| config.get("defines"))
+        .and_then(|v| v.as_object())
+        .map(|defs| defs.keys().collect())
+        .unwrap_or_default();
+    keys.sort();
+
+    let mut files = Vec::new();
+    let mut size_bytes = 0;
+    collect_files(&source.root, &source.root, &mut files, &mut size_bytes);
+    files.sort();
+
+    let field = |name: &str| config.get(name).cloned().unwrap_or(Value::Null);
+    let valid = validation.status == SoundpackValidationStatus::Valid;
+    let mut report =
+        json!({
+        "ok": valid,
+        "path": input,
+        "id": field("id"),
+        "name": field("name"),
+        "author": config.get("author").or_else(|| config.get("m_author")).cloned(),
+        "version": field("version"),
+        "description": field("description"),
+        "tags": field("tags"),
+        "type": determine_soundpack_type(&config).folder_name(),
+        "config_version": validation.config_version,
+        "status": validation.status.as_str(),
+        "definition_method": field("definition_method"),
+        "audio_file": field("audio_file"),
+        "icon": field("icon"),
+        "key_count": keys.len(),
+        "keys": keys,
+        "files": files,
+        "size_bytes": size_bytes,
+    });
+    // Only the structure is checked here, `validate` also decodes the audio
+    if !valid {
+        report["error"] = json!(validation.message);
+    }
+    Ok(report)
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>, size_bytes: &mut u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, files, size_bytes);
+        } else if let Ok(metadata) = entry.metadata() {
+            *size_bytes += metadata.len();
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
+
+// ===== CONVERT =====
+
+fn convert(input: &str, out: Option<&str>) -> PackResult {
+    // The source is never touched, the converted pack only goes to the output ZIP
+    let Some(out) = out else {
+        return Err(PackError::Usage("convert needs --out <zip>".to_string()));
+    };
+    let source = open_source(input)?;
+    let staging = Staging::new()?;
+    let root = staging.0.join("pack");
+    copy_dir(&source.root, &root).map_err(|e| format!("Failed to copy the soundpack: {}", e))?;
+    let config_path = root.join("config.json").to_string_lossy().to_string();
+    let mut steps = Vec::new();
+    let mut warnings = Vec::new();
+
+    let validation = validate_soundpack_config(&config_path);
+    if validation.status == SoundpackValidationStatus::VersionOneNeedsConversion {
+        config_converter
+            ::convert_v1_to_v2(&config_path, &config_path, Some(&root.to_string_lossy()))
+            .map_err(|e| format!("Failed to convert V1 to V2: {}", e))?;
+        steps.push("v1_to_v2");
+    }
+
+    let config = read_config(&root)?;
+    if config.get("definition_method").and_then(|v| v.as_str()) == Some("multi") {
+        config_converter
+            ::convert_v2_multi_to_single(&config_path, &root.to_string_lossy())
+            .map_err(|e| format!("Failed to convert multi to single: {}", e))?;
+        steps.push("multi_to_single");
+
+        // Only keys sharing the most used audio file survive the merge
+        let converted = read_config(&root)?;
+        let kept = definition_keys(&converted);
+        let dropped: Vec<&str> = definition_keys(&config)
+            .into_iter()
+            .filter(|key| !kept.contains(key))
+            .collect();
+        if !dropped.is_empty() {
+            warnings.push(
+                format!("Dropped {} keys whose audio is not in the shared file: {}", dropped.len(), dropped.join(", "))
+            );
+        }
+    }
+
+    let mut report = validation_report(&root);
+    report["path"] = json!(input);
+    report["steps"] = json!(steps);
+    if let Some(existing) = report["warnings"].as_array_mut() {
+        existing.extend(warnings.into_iter().map(Value::from));
+    }
+
+    export_soundpack_zip(&root, Path::new(out))?;
+    report["output"] = json!(out);
+    Ok(report)
+}
+
+fn definition_keys(config: &Value) -> Vec<&str> {
+    config
+        .get("definitions")
+        .and_then(|v| v.as_object())
+        .map(|definitions| definitions.keys().map(|key| key.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+// ===== INSTALL =====
+
+fn install(input: &str, force: bool) -> PackResult {
+    let source = open_source(input)?;
+
+    let report = validation_report(&source.root);
+    let convertible = report["status"] == "v1_needs_conversion";
+    if !report["ok"].as_bool().unwrap_or(false) && !convertible {
+        let mut report = report;
+        report["path"] = json!(input);
+        report["error"] = json!("Soundpack is not valid, nothing was installed");
+        return Ok(report);
+    }
+
+    // The installer works on ZIPs, so folders are packed first
+    let staging = Staging::new()?;
+    let zip_path = if is_zip(input) {
+        PathBuf::from(input)
+    } else {
+        let zip_path = staging.0.join("soundpack.zip");
+        export_soundpack_zip(&source.root, &zip_path)?;
+        zip_path
+    };
+    let zip_path_str = zip_path.to_string_lossy().to_string();
+
+    let id = soundpack_installer::get_soundpack_id_from_zip(&zip_path_str)?;
+    let custom_dir = crate::state::paths::soundpacks::get_custom_soundpacks_dir();
+    let existing = SoundpackType::ALL.into_iter().find(|t| custom_dir.join(t.folder_name()).join(&id).exists());
+    if let Some(existing) = existing {
+        if !force {
+            return Err(
+                PackError::Failed(format!("Soundpack '{}/{}' is already installed, use --force to replace it", existing.folder_name(), id))
+            );
+        }
+    }
+
+    // The installer swaps out an installed copy in the same folder only once the new one is ready
+    let installed = soundpack_installer::extract_and_install_soundpack(&zip_path_str)?;
+    let folder_path = SoundpackType::ALL.into_iter()
+        .map(|t| format!("{}/{}", t.folder_name(), installed.id))
+        .find(|folder| custom_dir.join(folder).exists())
+        .unwrap_or_else(|| installed.id.clone());
+    if let Some(existing) = existing {
+        // Installed under another type before, drop that copy
+        let previous = format!("{}/{}", existing.folder_name(), installed.id);
+        if previous != folder_path {
+            std::fs
+                ::remove_dir_all(custom_dir.join(&previous))
+                .map_err(|e| format!("Failed to remove the previous copy: {}", e))?;
+        }
+    }
+
+    // Make the new pack show up in the app and in `mechaura ctl list_soundpacks`
+    let mut cache = SoundpackCache::load();
+    cache.refresh_from_directory();
+    cache.save();
+
+    Ok(
+        json!({
+        "ok": true,
+        "path": input,
+        "id": folder_path,
+        "name": installed.name,
+        "installed_to": custom_dir.join(&folder_path),
+        "converted": convertible,
+    })
+    )
+}
+
+// ===== EXPORT =====
+
+fn export(input: &str, output: &str) -> PackResult {
+    if !is_zip(output) {
+        return Err(PackError::Usage("the output file must end in .zip".to_string()));
+    }
+    let source = open_source(input)?;
+    let files = export_soundpack_zip(&source.root, Path::new(output))?;
+    Ok(json!({
+        "ok": true,
+        "path": input,
+        "output": output,
+        "files": files,
+    }))
+}
+
+// ===== SEGMENT =====
+
+fn segment(recording: &str, args: &Args) -> PackResult {
+    let recording_path = Path::new(recording);
+    if !recording_path.is_file() {
+        return Err(PackError::Usage(format!("'{}' is not an audio file", recording)));
+    }
+    let keys = parse_key_list(args.option("--keys").unwrap_or("ansi"))?;
+
+    let mut options = SegmentOptions::default();
+    if let Some(value) = args.option("--sensitivity") {
+        options.sensitivity_db = parse_number(value, "--sensitivity")?;
+    }
+    if let Some(value) = args.option("--max-hold") {
+        options.max_hold_ms = parse_number(value, "--max-hold")?;
+    }
+
+    let out_dir = match args.option("--out") {
+        Some(out) => PathBuf::from(out),
+        None => recording_path.parent().map(Path::to_path_buf).unwrap_or_default(),
+    };
+    let config_path = out_dir.join("config.json");
+    if config_path.exists() && !args.flag("--force") {
+        return Err(PackError::Failed(format!("{} already exists, use --force to replace it", config_path.display())));
+    }
+
+    let keystrokes = segment_recording(recording, &options)?;
+    if keystrokes.is_empty() {
+        return Err(PackError::Failed("No keystrokes found, try a lower --sensitivity".to_string()));
+    }
+
+    // The recording has to sit next to config.json
+    let audio_file = recording_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "sound.ogg".to_string());
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("Failed to create {}: {}", out_dir.display(), e))?;
+    let target_audio = out_dir.join(&audio_file);
+    let same_file = match (recording_path.canonicalize(), target_audio.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    if !same_file {
+        std::fs::copy(recording_path, &target_audio).map_err(|e| format!("Failed to copy the recording: {}", e))?;
+    }
+
+    let name = match args.option("--name") {
+        Some(name) => name.to_string(),
+        None => recording_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "New soundpack".to_string()),
+    };
+    let config = build_config(&name, args.option("--author").unwrap_or("Unknown"), &audio_file, &keys, &keystrokes);
+    let content = serde_json::to_string_pretty(&config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    std::fs::write(&config_path, content).map_err(|e| format!("Failed to write {}: {}", config_path.display(), e))?;
+
+    let without_release: Vec<&String> = keys
+        .iter()
+        .zip(&keystrokes)
+        .filter(|(_, keystroke)| keystroke.up.is_none())
+        .map(|(key, _)| key)
+        .collect();
+    let mut warnings = Vec::new();
+    if keystrokes.len() < keys.len() {
+        warnings.push(format!("Found {} keystrokes for {} keys, the last {} keys have no sound", keystrokes.len(), keys.len(), keys.len() - keystrokes.len()));
+    } else if keystrokes.len() > keys.len() {
+        warnings.push(format!("Found {} keystrokes for {} keys, {} extra keystrokes were dropped", keystrokes.len(), keys.len(), keystrokes.len() - keys.len()));
+    }
+    if !without_release.is_empty() {
+        warnings.push(format!("No release sound found for {} keys", without_release.len()));
+    }
+
+    Ok(json!({
+        "ok": true,
+        "path": recording,
+        "output": config_path,
+        "keystrokes": keystrokes.len(),
+        "assigned": keys.len().min(keystrokes.len()),
+        "unassigned_keys": keys.iter().skip(keystrokes.len()).collect::<Vec<_>>(),
+        "without_release": without_release,
+        "warnings": warnings,
+    }))
+}
+
+/// `ansi`, a comma separated list, or `@file` with one key per line
+fn parse_key_list(value: &str) -> Result<Vec<String>, PackError> {
+    let keys: Vec<String> = if value == "ansi" {
+        ANSI_LAYOUT.iter()
+            .flat_map(|row| row.iter())
+            .map(|key| key.to_string())
+            .collect()
+    } else if let Some(file) = value.strip_prefix('@') {
+        std::fs
+            ::read_to_string(file)
+            .map_err(|e| PackError::Usage(format!("Failed to read key list {}: {}", file, e)))?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    } else {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+
+    if keys.is_empty() {
+        return Err(PackError::Usage("the key list is empty".to_string()));
+    }
+    if let Some(unknown) = keys.iter().find(|key| !is_key_code(key) && !is_gamepad_code(key) && !key.starts_with("Mouse")) {
+        return Err(PackError::Usage(format!("unknown key code '{}'", unknown)));
+    }
+    Ok(keys)
+}
+
+fn parse_number(value: &str, name: &str) -> Result<f32, PackError> {
+    value.parse::<f32>().map_err(|_| PackError::Usage(format!("{} expects a number, got '{}'", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[&str]) -> i32 {
+        let args: Vec<String> = args
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        run_pack(&args)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("mechaura-pack-cli-{}-{}", name, Uuid::new_v4()))
+    }
+
+    /// 200 ms of tone, long enough for every timing below
+    fn write_wav(path: &Path) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 48_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for i in 0..9_600 {
+            writer.write_sample(if i % 2 == 0 { 16_000i16 } else { -16_000 }).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    fn write_pack(dir: &Path, config: Value) -> String {
+        std::fs::create_dir_all(dir).unwrap();
+        write_wav(&dir.join("sound.wav"));
+        write_wav(&dir.join("other.wav"));
+        std::fs::write(dir.join("config.json"), config.to_string()).unwrap();
+        dir.to_string_lossy().to_string()
+    }
+
+    fn single_pack(id: &str) -> Value {
+        json!({
+            "id": id,
+            "name": "CLI test",
+            "author": "Tester",
+            "config_version": "2",
+            "audio_file": "sound.wav",
+            "definition_method": "single",
+            "definitions": {
+                "KeyA": { "timing": [[0.0, 50.0], [60.0, 100.0]] },
+                "KeyB": { "timing": [[100.0, 150.0]] }
+            }
+        })
+    }
+
+    #[test]
+    fn validate_reports_through_the_exit_code() {
+        let dir = temp_dir("validate");
+        let valid = write_pack(&dir.join("valid"), single_pack("valid"));
+        assert_eq!(run(&["validate", &valid]), EXIT_OK);
+        assert_eq!(validate(&valid).ok().unwrap()["errors"], json!([]));
+
+        let mut config = single_pack("invalid");
+        config["definitions"]["KeyA"]["timing"] = json!([[50.0, 10.0]]);
+        config["audio_file"] = json!("missing.wav");
+        let invalid = write_pack(&dir.join("invalid"), config);
+        assert_eq!(run(&["validate", &invalid]), EXIT_FAILED);
+        let report = validate(&invalid).ok().unwrap();
+        assert_eq!(report["ok"], json!(false));
+        assert!(report["errors"].as_array().unwrap().iter().any(|e| e.as_str().unwrap().contains("missing.wav")));
+
+        assert_eq!(run(&["validate", &dir.join("nothing").to_string_lossy()]), EXIT_USAGE);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn info_follows_the_validation() {
+        let dir = temp_dir("info");
+        let valid = write_pack(&dir.join("valid"), single_pack("valid"));
+        assert_eq!(run(&["info", &valid]), EXIT_OK);
+        assert_eq!(info(&valid).ok().unwrap()["key_count"], json!(2));
+
+        let mut config = single_pack("invalid");
+        config.as_object_mut().unwrap().remove("author");
+        let invalid = write_pack(&dir.join("invalid"), config);
+        assert_eq!(run(&["info", &invalid]), EXIT_FAILED);
+        assert_eq!(info(&invalid).ok().unwrap()["ok"], json!(false));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn convert_needs_out_and_reports_dropped_keys() {
+        let dir = temp_dir("convert");
+        let config =
+            json!({
+            "id": "multi",
+            "name": "Multi",
+            "author": "Tester",
+            "config_version": "2",
+            "definition_method": "multi",
+            "definitions": {
+                "KeyA": { "audio_file": "sound.wav", "timing": [[0.0, 50.0]] },
+                "KeyB": { "audio_file": "sound.wav", "timing": [[60.0, 100.0]] },
+                "KeyC": { "audio_file": "other.wav", "timing": [[0.0, 50.0]] }
+            }
+        });
+        let pack = write_pack(&dir.join("multi"), config);
+        let original = std::fs::read_to_string(dir.join("multi").join("config.json")).unwrap();
+
+        assert_eq!(run(&["convert", &pack]), EXIT_USAGE);
+
+        let out = dir.join("converted.zip").to_string_lossy().to_string();
+        assert_eq!(run(&["convert", &pack, "--out", &out]), EXIT_OK);
+        let report = convert(&pack, Some(&out)).ok().unwrap();
+        assert_eq!(report["steps"], json!(["multi_to_single"]));
+        let warnings = report["warnings"].as_array().unwrap();
+        assert!(warnings.iter().any(|w| w.as_str().unwrap().ends_with(": KeyC")));
+
+        // The source folder is left as it was, the ZIP holds the single-file pack
+        assert_eq!(std::fs::read_to_string(dir.join("multi").join("config.json")).unwrap(), original);
+        let unpacked = dir.join("unpacked");
+        let root = unpack_zip(Path::new(&out), &unpacked).unwrap();
+        let converted = read_config(&root).unwrap();
+        assert_eq!(converted["definition_method"], json!("single"));
+        assert!(converted["definitions"].get("KeyC").is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn export_writes_a_zip() {
+        let dir = temp_dir("export");
+        let pack = write_pack(&dir.join("pack"), single_pack("export"));
+        let out = dir.join("pack.zip").to_string_lossy().to_string();
+        assert_eq!(run(&["export", &pack, &out]), EXIT_OK);
+        let root = unpack_zip(Path::new(&out), &dir.join("unpacked")).unwrap();
+        assert!(root.join("sound.wav").is_file());
+
+        assert_eq!(run(&["export", &pack, &dir.join("pack.tar").to_string_lossy()]), EXIT_USAGE);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Installs go to the user's data folder, point it at a temp folder first
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn install_needs_force_to_replace() {
+        let dir = temp_dir("install");
+        // SAFETY: no other test reads the XDG folders, only installs write there
+        unsafe {
+            std::env::set_var("XDG_DATA_HOME", dir.join("data"));
+            std::env::set_var("XDG_CACHE_HOME", dir.join("cache"));
+        }
+        let pack = write_pack(&dir.join("pack"), single_pack("cli-install"));
+
+        assert_eq!(run(&["install", &pack]), EXIT_OK);
+        let installed = dir.join("data/mechaura/soundpacks/keyboard/cli-install");
+        assert!(installed.join("sound.wav").is_file());
+
+        assert_eq!(run(&["install", &pack]), EXIT_FAILED);
+        std::fs::write(installed.join("edited.txt"), b"local change").unwrap();
+        assert_eq!(run(&["install", &pack, "--force"]), EXIT_OK);
+        assert!(installed.join("config.json").is_file());
+        assert!(!installed.join("edited.txt").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,12 +1,55 @@
-use std::env;
+use std::collections::{ HashMap, VecDeque };
 use std::process::Command;
-use crate::utils::constants::{ APP_PROTOCOL, APP_PROTOCOL_URL, APP_NAME, APP_NAME_LOWERCASE };
+use std::sync::Mutex;
+use crate::libs::window_manager::WINDOW_MANAGER;
+use crate::state::config::AppConfig;
+use crate::utils::constants::{ APP_PROTOCOL, APP_PROTOCOL_URL, APP_NAME };
+use crate::utils::soundpack_catalog::{ fetch_catalog, sha256_hex, CatalogEntry };
+use once_cell::sync::Lazy;
+
+/// Where an install-soundpack link gets its pack from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstallSource {
+    /// `mechaura://install-soundpack/<id>`, looked up in the configured catalog
+    Catalog {
+        id: String,
+    },
+    /// `mechaura://install-soundpack?url=<zip>&sha256=<hex>[&size=<bytes>][&name=<name>]`
+    Zip {
+        url: String,
+        sha256: String,
+        size: u64,
+        name: Option<String>,
+    },
+    /// A ZIP file passed on the command line
+    File {
+        path: String,
+    },
+}
+
+// Install links wait here until the window asks the user about them
+static PENDING_INSTALLS: Lazy<Mutex<VecDeque<InstallSource>>> = Lazy::new(||
+    Mutex::new(VecDeque::new())
+);
+
+/// Ask the user about an install and bring the window up for it
+pub fn queue_install(source: InstallSource) {
+    if let Ok(mut pending) = PENDING_INSTALLS.lock() {
+        pending.push_back(source);
+    }
+    focus_window();
+}
+
+/// Next install link that still needs confirmation
+pub fn take_pending_install() -> Option<InstallSource> {
+    PENDING_INSTALLS.lock().ok()?.pop_front()
+}
 
 #[allow(dead_code)]
 /// Register the mechaura:// protocol for the application
 #[cfg(target_os = "windows")]
 pub fn register_protocol() -> Result<(), Box<dyn std::error::Error>> {
-    let exe_path = env::current_exe()?;
+    let exe_path = std::env::current_exe()?;
     let exe_path_str = exe_path.to_string_lossy();
     println!("🔗 Registering {}// protocol... {}", APP_PROTOCOL, exe_path_str); // Store formatted strings to avoid temporary value issues
     let icon_path = format!("\"{}\"", exe_path_str);
@@ -60,42 +103,46 @@ pub fn register_protocol() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Register the mechaura:// handler through an XDG desktop entry and `xdg-mime`
+///
+/// Does nothing when the entry already points at this executable, so it can run on every start.
 #[cfg(target_os = "linux")]
 pub fn register_protocol() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::utils::desktop_entry;
     use std::fs;
 
-    let home = env::var("HOME")?;
-    let desktop_file_path = format!(
-        "{}/.local/share/applications/{}.desktop",
-        home,
-        APP_NAME_LOWERCASE
+    let exe_path = desktop_entry::launcher_path()?;
+    let mime_type = format!("x-scheme-handler/{}", APP_PROTOCOL);
+    let desktop_content = desktop_entry::render(
+        &desktop_entry::exec_line(&exe_path, &["%u"]),
+        &[
+            ("Terminal", "false"),
+            ("MimeType", &format!("{};", mime_type)),
+            ("Categories", "AudioVideo;Utility;"),
+        ]
     );
-    let exe_path = env::current_exe()?;
 
-    println!("🐧 Registering {}// protocol on Linux...", APP_PROTOCOL);
+    let apps_dir = desktop_entry::applications_dir()?;
+    let desktop_file_path = apps_dir.join(desktop_entry::file_name());
+    if fs::read_to_string(&desktop_file_path).is_ok_and(|content| content == desktop_content) {
+        return Ok(());
+    }
 
-    let desktop_content = format!(
-        r#"[Desktop Entry]
-Name={}
-Comment=Mechanical keyboard sound simulator
-Exec={} %u
-Icon={}
-Type=Application
-MimeType=x-scheme-handler/{};
-Categories=AudioVideo;Utility;
-"#,
-        APP_NAME,
-        exe_path.to_string_lossy(),
-        APP_NAME_LOWERCASE,
-        APP_PROTOCOL
-    );
+    println!("🐧 Registering {}// protocol on Linux...", APP_PROTOCOL);
 
     // Ensure the applications directory exists
-    let apps_dir = format!("{}/.local/share/applications", home);
     fs::create_dir_all(&apps_dir)?;
     fs::write(&desktop_file_path, desktop_content)?;
 
-    // Update desktop database
+    // Make it the default handler, then refresh the MIME cache for launchers
+    let output = Command::new("xdg-mime")
+        .args(["default", &desktop_entry::file_name(), &mime_type])
+        .output()?;
+    if !output.status.success() {
+        return Err(
+            format!("xdg-mime failed: {}", String::from_utf8_lossy(&output.stderr).trim()).into()
+        );
+    }
     let _output = Command::new("update-desktop-database").arg(&apps_dir).output();
 
     println!("✅ Protocol {}// registered successfully", APP_PROTOCOL);
@@ -118,10 +165,11 @@ pub fn handle_protocol_url(url: &str) -> Result<(), Box<dyn std::error::Error>>
             // The app is already opening, so we just need to ensure it's focused
             focus_window();
         }
-        path if path.starts_with("install-soundpack/") => {
-            let soundpack_name = &path[18..];
-            println!("🔊 Installing soundpack from protocol: {}", soundpack_name);
-            install_soundpack_from_protocol(soundpack_name)?;
+        path if path.starts_with("install-soundpack") => {
+            let source = parse_install_link(url)?;
+            println!("🔊 Soundpack install requested from protocol: {:?}", source);
+            // Nothing is downloaded until the user confirms it in the window
+            queue_install(source);
         }
         path if path.starts_with("import-theme/") => {
             let theme_data = &path[13..];
@@ -137,131 +185,170 @@ pub fn handle_protocol_url(url: &str) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
-/// Focus the application window (platform-specific)
-#[cfg(target_os = "windows")]
-fn focus_window() {
-    // On Windows, the window should automatically focus when the protocol is triggered
-    println!("🪟 Focusing window on Windows");
-}
-
-#[cfg(not(target_os = "windows"))]
+/// Focus the application window, a no-op until the window controller is listening
 fn focus_window() {
-    println!("🖥️ Window focus handling for this platform not implemented");
+    WINDOW_MANAGER.show();
 }
 
-fn install_soundpack_from_protocol(soundpack_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-    use crate::state::config::AppConfig;
-    use std::fs;
-    use std::path::Path;
-
-    println!("📥 Installing soundpack: {}", soundpack_name);
+/// Parse an install-soundpack link, ZIP links must carry a checksum and use http(s)
+pub fn parse_install_link(url: &str) -> Result<InstallSource, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid link: {}", e))?;
+    if parsed.scheme() != APP_PROTOCOL || parsed.host_str() != Some("install-soundpack") {
+        return Err(format!("Not an install-soundpack link: {}", url));
+    }
+    let query: HashMap<String, String> = parsed.query_pairs().into_owned().collect();
 
-    // In a real implementation, this would download the soundpack from a remote source
-    // For testing purposes, we'll just check if it exists locally and add it to config
+    if let Some(zip_url) = query.get("url") {
+        if !zip_url.starts_with("https://") && !zip_url.starts_with("http://") {
+            return Err("Soundpack links can only download over http(s)".to_string());
+        }
+        let sha256 = query
+            .get("sha256")
+            .map(|sha256| sha256.to_lowercase())
+            .filter(|sha256| sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit()))
+            .ok_or_else(|| "Soundpack links need a valid sha256 checksum".to_string())?;
+        let size = match query.get("size") {
+            Some(size) => size.parse::<u64>().map_err(|_| format!("Invalid size: {}", size))?,
+            None => 0,
+        };
+        return Ok(InstallSource::Zip {
+            url: zip_url.clone(),
+            sha256,
+            size,
+            name: query.get("name").cloned(),
+        });
+    }
 
-    let app_root = std::env::current_dir()?;
-    let soundpacks_dir = app_root.join("soundpacks");
-    let soundpack_path = soundpacks_dir.join(soundpack_name);
+    let id = query
+        .get("id")
+        .cloned()
+        .unwrap_or_else(|| parsed.path().trim_matches('/').to_string());
+    let valid_id =
+        !id.is_empty() &&
+        id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') &&
+        id != "." &&
+        id != "..";
+    if !valid_id {
+        return Err(format!("Invalid soundpack id in link: {}", id));
+    }
+    Ok(InstallSource::Catalog { id })
+}
 
-    if Path::new(&soundpack_path).exists() {
-        // Add to config
-        let mut config = AppConfig::load();
-        config.keyboard_soundpack = soundpack_name.to_string();
-        if let Err(e) = config.save() {
-            eprintln!("❌ Failed to save config with new soundpack: {}", e);
-            return Err(e.into());
+/// Look up what an install link points to, so it can be shown before downloading
+pub async fn resolve_install_source(source: &InstallSource) -> Result<CatalogEntry, String> {
+    match source {
+        InstallSource::Catalog { id } => {
+            let catalog = fetch_catalog(&AppConfig::load().catalog_url).await?;
+            catalog.soundpacks
+                .into_iter()
+                .find(|entry| &entry.id == id)
+                .ok_or_else(|| format!("'{}' is not in the soundpack catalog", id))
         }
-        println!("✅ Installed and activated soundpack: {}", soundpack_name);
-    } else {
-        // For real implementation, we would download it here
-        println!("⚠️ Soundpack not found locally: {}. Would download in production.", soundpack_name);
-        // Create a placeholder for testing
-        fs::create_dir_all(&soundpack_path)?;
-        fs::write(
-            soundpack_path.join("config.json"),
-            format!(r#"{{
-  "name": "Test Soundpack - {}",
-  "author": "Protocol Test",
-  "version": "1.0.0",
-  "key_define": {{
-    "default": "sound.ogg"
-  }}
-}}"#, soundpack_name)
-        )?;
-
-        // Create a placeholder sound file by copying from an existing soundpack
-        let source_sound = app_root.join("soundpacks").join("oreo").join("oreo.ogg");
-        let target_sound = soundpack_path.join("sound.ogg");
-
-        if source_sound.exists() {
-            fs::copy(source_sound, target_sound)?;
-        } else {
-            // Create an empty sound file if source doesn't exist
-            fs::write(soundpack_path.join("sound.ogg"), &[0u8; 1024])?;
+        InstallSource::Zip { url, sha256, size, name } => {
+            let file_name = url
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .split(['?', '#'])
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches(".zip")
+                .to_string();
+            Ok(CatalogEntry {
+                id: file_name.clone(),
+                name: name.clone().unwrap_or(file_name),
+                author: None,
+                description: None,
+                tags: Vec::new(),
+                preview: None,
+                zip_url: url.clone(),
+                sha256: sha256.clone(),
+                size: *size,
+            })
         }
-
-        // Update config to use the new soundpack
-        let mut config = AppConfig::load();
-        config.keyboard_soundpack = soundpack_name.to_string();
-        if let Err(e) = config.save() {
-            eprintln!("❌ Failed to save config with new soundpack: {}", e);
-            return Err(e.into());
+        InstallSource::File { path } => {
+            let file_path = std::path::PathBuf::from(path);
+            let bytes = tokio::fs
+                ::read(&file_path).await
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            let name = file_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "soundpack".to_string());
+            Ok(CatalogEntry {
+                id: name.clone(),
+                name,
+                author: None,
+                description: None,
+                tags: Vec::new(),
+                preview: None,
+                zip_url: path.clone(),
+                sha256: sha256_hex(&bytes),
+                size: bytes.len() as u64,
+            })
         }
-
-        println!("✅ Created and activated placeholder soundpack: {}", soundpack_name);
     }
-
-    Ok(())
 }
 
-/// Import a theme from protocol URL (base64 encoded theme data)
+/// Import a theme from protocol URL (base64url theme payload)
 fn import_theme_from_protocol(theme_data: &str) -> Result<(), Box<dyn std::error::Error>> {
     use crate::libs::theme::Theme;
-    use crate::state::config::AppConfig;
-    use crate::state::themes::CustomThemeData;
-    use crate::utils::theme::get_themes_config;
-    use chrono::Utc;
-    use std::time::{ SystemTime, UNIX_EPOCH };
+    use crate::utils::theme::import_shared_theme;
 
     println!("📥 Importing theme from protocol data");
-
-    // In a real implementation, this would decode the base64 data
-    // For testing purposes, we'll create a simple theme
-
-    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-
-    let theme_id = format!("imported-{}", timestamp);
-    let theme_name = if theme_data.is_empty() { "Imported Theme" } else { theme_data };
-
-    let mut themes_config = get_themes_config();
-
-    // Add new theme
-    let new_theme = CustomThemeData {
-        id: theme_id.clone(),
-        name: theme_name.to_string(),
-        description: "Imported via protocol URL".to_string(),
-        css: ".app-container { background-color: #202020; color: #ffffff; }".to_string(),
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
-        is_built_in: false, // Mark as custom theme
-    };
-
-    // Add theme to custom_themes map
-    themes_config.custom_themes.insert(theme_id.clone(), new_theme);
-
-    // Save the themes config
-    if let Err(e) = themes_config.save() {
-        return Err(format!("Failed to save imported theme: {}", e).into());
-    }
+    let theme_id = import_shared_theme(theme_data).map_err(|e|
+        format!("Failed to import theme: {}", e)
+    )?;
 
     // Set as current theme
     let mut config = AppConfig::load();
     config.theme = Theme::Custom(theme_id.clone());
+    config.last_updated = chrono::Utc::now();
 
     if let Err(e) = config.save() {
         return Err(format!("Failed to apply imported theme: {}", e).into());
     }
 
-    println!("✅ Theme imported and applied: {}", theme_name);
+    println!("✅ Theme imported and applied: {}", theme_id);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_install_links() {
+        assert_eq!(
+            parse_install_link("mechaura://install-soundpack/nk-cream").unwrap(),
+            InstallSource::Catalog { id: "nk-cream".to_string() }
+        );
+        assert_eq!(
+            parse_install_link("mechaura://install-soundpack?id=nk-cream").unwrap(),
+            InstallSource::Catalog { id: "nk-cream".to_string() }
+        );
+
+        let sha256 = "AB".repeat(32);
+        let link = format!(
+            "mechaura://install-soundpack?url=https%3A%2F%2Fexample.com%2Fcream.zip&sha256={}&size=42&name=NK%20Cream",
+            sha256
+        );
+        assert_eq!(parse_install_link(&link).unwrap(), InstallSource::Zip {
+            url: "https://example.com/cream.zip".to_string(),
+            sha256: sha256.to_lowercase(),
+            size: 42,
+            name: Some("NK Cream".to_string()),
+        });
+
+        // No checksum, local files and path tricks are refused
+        assert!(parse_install_link("mechaura://install-soundpack?url=https://example.com/a.zip").is_err());
+        assert!(
+            parse_install_link(
+                &format!("mechaura://install-soundpack?url=file:///etc/passwd&sha256={}", sha256)
+            ).is_err()
+        );
+        assert!(parse_install_link("mechaura://install-soundpack/..").is_err());
+        assert!(parse_install_link("mechaura://install-soundpack/a%2Fb").is_err());
+        assert!(parse_install_link("mechaura://install-soundpack/").is_err());
+    }
+}
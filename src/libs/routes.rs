@@ -7,6 +7,7 @@ pub enum Route {
     #[layout(Layout)] #[route("/")] Home {},
     #[route("/customize")] Customize {},
     #[route("/soundpacks")] Soundpacks {},
+    #[route("/soundpacks/edit/:..segments")] SoundpackEditor { segments: Vec<String> },
     #[route("/mood")] Mood {},
     #[route("/settings")] Settings {},
 }
@@ -86,6 +87,15 @@ pub fn Soundpacks() -> Element {
     }
 }
 
+#[component]
+pub fn SoundpackEditor(segments: Vec<String>) -> Element {
+    // Soundpack IDs contain a slash ("keyboard/Pack Name"), so they span several segments
+    let soundpack_id = segments.join("/");
+    rsx! {
+      crate::components::pages::SoundpackEditorPage { key: "{soundpack_id}", soundpack_id }
+    }
+}
+
 #[component]
 pub fn Mood() -> Element {
     rsx! {
@@ -0,0 +1,91 @@
+/// Single-instance lock
+///
+/// The first launch holds an exclusive lock on a file next to the control socket for as long
+/// as it runs. A second launch can't take it, so instead of starting its own input hooks (and
+/// playing every key twice) it forwards its arguments over the control socket and exits.
+use crate::libs::protocol::{ handle_protocol_url, queue_install, InstallSource };
+use crate::libs::window_manager::WINDOW_MANAGER;
+use crate::utils::constants::APP_PROTOCOL_URL;
+use crate::{ always_eprint, debug_print };
+use std::fs::{ File, OpenOptions, TryLockError };
+use std::path::{ Path, PathBuf };
+
+/// Held by the running instance, the lock is released when the process exits
+pub struct InstanceLock {
+    _file: File,
+}
+
+#[cfg(unix)]
+fn lock_path() -> PathBuf {
+    let mut path = crate::libs::control_socket::socket_path().into_os_string();
+    path.push(".lock");
+    PathBuf::from(path)
+}
+
+#[cfg(not(unix))]
+fn lock_path() -> PathBuf {
+    std::env::temp_dir().join("mechaura.lock")
+}
+
+/// Take the instance lock, None when another instance already holds it
+pub fn acquire() -> Result<Option<InstanceLock>, String> {
+    let path = lock_path();
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    match file.try_lock() {
+        Ok(()) => {
+            debug_print!("🔒 Holding instance lock {}", path.display());
+            Ok(Some(InstanceLock { _file: file }))
+        }
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(e)) => Err(format!("Failed to lock {}: {}", path.display(), e)),
+    }
+}
+
+/// Arguments to hand to the running instance, with file paths made absolute
+///
+/// The running instance has its own working directory, so relative paths would point elsewhere.
+pub fn forwardable_args(args: &[String]) -> Vec<String> {
+    args.iter()
+        .map(|arg| {
+            if arg.starts_with('-') || arg.starts_with(APP_PROTOCOL_URL) {
+                return arg.clone();
+            }
+            std::fs
+                ::canonicalize(arg)
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_else(|_| arg.clone())
+        })
+        .collect()
+}
+
+fn is_zip_path(arg: &str) -> bool {
+    let path = Path::new(arg);
+    path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("zip")) && path.is_file()
+}
+
+/// Act on launch arguments: deep links, soundpack ZIPs and, for forwarded launches, the window
+///
+/// `args` includes the program name, like `std::env::args()`.
+pub fn handle_args(args: &[String], forwarded: bool) {
+    for arg in args.iter().skip(1) {
+        if arg.starts_with(APP_PROTOCOL_URL) {
+            if let Err(e) = handle_protocol_url(arg) {
+                always_eprint!("❌ Failed to handle {}: {}", arg, e);
+            }
+        } else if is_zip_path(arg) {
+            println!("📦 Soundpack ZIP passed on the command line: {}", arg);
+            queue_install(InstallSource::File { path: arg.clone() });
+        }
+    }
+
+    // Launching the app again is how users look for its window
+    if forwarded && !args.iter().any(|arg| arg == "--minimized") {
+        WINDOW_MANAGER.show();
+    }
+}
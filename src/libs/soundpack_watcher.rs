@@ -0,0 +1,135 @@
+/// Live reload of soundpacks while their files are edited
+///
+/// Polls the directories of the active keyboard, mouse and gamepad packs and the custom
+/// soundpacks root, which works the same on every platform and filesystem. Once changes have
+/// settled for `DEBOUNCE`, active packs are reloaded through the audio command queue (so the
+/// window and headless mode both pick them up) and the soundpack cache is refreshed, which
+/// lets the selector show added and removed packs.
+use crate::libs::control_socket::{ send_audio_command, AudioCommand };
+use crate::state::config::AppConfig;
+use crate::state::paths;
+use crate::state::soundpack::SoundpackType;
+use crate::{ always_eprint, debug_print };
+use std::collections::{ BTreeMap, HashMap };
+use std::path::{ Path, PathBuf };
+use std::time::{ Duration, Instant, SystemTime };
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Editors and exports write in bursts, wait for a quiet moment before reloading
+const DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/// Modification time and size of every file seen
+type Snapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+fn add_file(path: &Path, snapshot: &mut Snapshot) {
+    if let Ok(metadata) = std::fs::metadata(path) {
+        snapshot.insert(path.to_path_buf(), (metadata.modified().ok(), metadata.len()));
+    }
+}
+
+/// Every file in a pack directory
+fn snapshot_pack(dir: &Path, snapshot: &mut Snapshot) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            snapshot_pack(&path, snapshot);
+        } else {
+            add_file(&path, snapshot);
+        }
+    }
+}
+
+/// Pack folders under the custom root and their config.json, enough to see packs come and go
+fn snapshot_packs_root(root: &Path) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for type_dir in ["keyboard", "mouse", "gamepad"] {
+        let Ok(entries) = std::fs::read_dir(root.join(type_dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let pack_dir = entry.path();
+            snapshot.insert(pack_dir.clone(), (None, 0));
+            add_file(&pack_dir.join("config.json"), &mut snapshot);
+        }
+    }
+    snapshot
+}
+
+/// Active pack per type, as stored in the config
+fn active_packs(config: &AppConfig) -> Vec<(SoundpackType, String)> {
+    [
+        (SoundpackType::Keyboard, &config.keyboard_soundpack),
+        (SoundpackType::Mouse, &config.mouse_soundpack),
+        (SoundpackType::Gamepad, &config.gamepad_soundpack),
+    ]
+        .into_iter()
+        .filter(|(_, id)| !id.is_empty())
+        .map(|(soundpack_type, id)| (soundpack_type, id.clone()))
+        .collect()
+}
+
+/// Start watching on a background thread
+pub fn start_soundpack_watcher() {
+    std::thread::spawn(|| {
+        let packs_root = paths::soundpacks::get_custom_soundpacks_dir();
+        let mut root_snapshot = snapshot_packs_root(&packs_root);
+        // Keyed by pack ID, so switching packs only takes a new baseline
+        let mut pack_snapshots: HashMap<String, Snapshot> = HashMap::new();
+
+        let mut pending_reloads: Vec<(SoundpackType, String)> = Vec::new();
+        let mut pending_refresh = false;
+        let mut last_change = Instant::now();
+        let mut active: Vec<(SoundpackType, String)> = Vec::new();
+
+        debug_print!("👀 Watching soundpacks for changes in {}", packs_root.display());
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            // Only read, a config caught mid-save keeps the packs from the last poll
+            if let Some(config) = AppConfig::read() {
+                active = active_packs(&config);
+            }
+            pack_snapshots.retain(|id, _| active.iter().any(|(_, active_id)| active_id == id));
+            for (soundpack_type, id) in active.iter().cloned() {
+                let mut snapshot = Snapshot::new();
+                snapshot_pack(Path::new(&paths::soundpacks::soundpack_dir(&id)), &mut snapshot);
+                match pack_snapshots.insert(id.clone(), snapshot) {
+                    Some(previous) if previous != pack_snapshots[&id] => {
+                        debug_print!("👀 Soundpack {} changed on disk", id);
+                        if !pending_reloads.iter().any(|(_, pending_id)| pending_id == &id) {
+                            pending_reloads.push((soundpack_type, id));
+                        }
+                        last_change = Instant::now();
+                    }
+                    _ => {}
+                }
+            }
+
+            let snapshot = snapshot_packs_root(&packs_root);
+            if snapshot != root_snapshot {
+                root_snapshot = snapshot;
+                pending_refresh = true;
+                last_change = Instant::now();
+            }
+
+            if (pending_reloads.is_empty() && !pending_refresh) || last_change.elapsed() < DEBOUNCE {
+                continue;
+            }
+
+            for (soundpack_type, id) in pending_reloads.drain(..) {
+                println!("🔁 Reloading soundpack {} after changes on disk", id);
+                let command = AudioCommand::ReloadSoundpack(soundpack_type, id.clone());
+                if let Err(e) = send_audio_command(command) {
+                    always_eprint!("❌ Failed to reload soundpack {}: {}", id, e);
+                }
+            }
+            // Reloads update cache entries too, so refresh after them either way
+            pending_refresh = false;
+            crate::state::app::refresh_cache_in_background();
+        }
+    });
+}
@@ -1,7 +1,10 @@
 use crate::components::window_controller::WindowController;
 use crate::components::header::Header;
+use crate::components::ui::InstallLinkModal;
+use crate::libs::control_socket::process_audio_commands;
 use crate::libs::routes::Route;
 use crate::libs::tray_service::request_tray_update;
+use crate::libs::input_filter::filter_event;
 use crate::libs::input_manager::{ get_input_channels, set_window_focus };
 use crate::libs::AudioContext;
 use crate::state::keyboard::KeyboardState;
@@ -72,6 +75,7 @@ pub fn app() -> Element {
     // Extract receivers from input channels
     let keyboard_rx = input_channels.keyboard_rx.clone();
     let mouse_rx = input_channels.mouse_rx.clone();
+    let gamepad_rx = input_channels.gamepad_rx.clone();
     let hotkey_rx = input_channels.hotkey_rx.clone();
 
     // ===== WINDOW FOCUS TRACKING =====
@@ -104,19 +108,19 @@ pub fn app() -> Element {
             async move {
                 loop {
                     if let Ok(receiver) = keyboard_rx.try_lock() {
-                        if let Ok(keycode) = receiver.try_recv() {
-                            if keycode.starts_with("UP:") {
-                                let key = &keycode[3..];
-                                ctx.play_key_event_sound(key, false);
+                        if let Some(event) = receiver.try_recv().ok().filter(filter_event) {
+                            let device_id = event.device_id.as_deref();
+                            if !event.is_down {
+                                ctx.play_key_event_sound(&event.code, false, device_id);
 
                                 // Update keyboard state - key released
                                 keyboard_state.write().key_pressed = false;
-                            } else if !keycode.is_empty() {
-                                ctx.play_key_event_sound(&keycode, true);
+                            } else if !event.code.is_empty() {
+                                ctx.play_key_event_sound(&event.code, true, device_id);
                                 // Update keyboard state - key pressed
                                 let mut state = keyboard_state.write();
                                 state.key_pressed = true;
-                                state.last_key = keycode.clone();
+                                state.last_key = event.code.clone();
                             }
                         }
                     }
@@ -138,12 +142,13 @@ pub fn app() -> Element {
             async move {
                 loop {
                     if let Ok(receiver) = mouse_rx.try_lock() {
-                        if let Ok(button_code) = receiver.try_recv() {
-                            if button_code.starts_with("UP:") {
-                                let button = &button_code[3..];
-                                ctx.play_mouse_event_sound(button, false);
-                            } else if !button_code.is_empty() {
-                                ctx.play_mouse_event_sound(&button_code, true);
+                        if let Some(event) = receiver.try_recv().ok().filter(filter_event) {
+                            if !event.code.is_empty() {
+                                ctx.play_mouse_event_sound(
+                                    &event.code,
+                                    event.is_down,
+                                    event.device_id.as_deref()
+                                );
                             }
                         }
                     }
@@ -151,6 +156,60 @@ pub fn app() -> Element {
                 }
             }
         });
+    }
+
+    // Process gamepad events and play sounds
+    {
+        let ctx = audio_context.clone();
+        let gamepad_rx = gamepad_rx.clone();
+
+        use_future(move || {
+            let ctx = ctx.clone();
+            let gamepad_rx = gamepad_rx.clone();
+
+            async move {
+                loop {
+                    if let Ok(receiver) = gamepad_rx.try_lock() {
+                        if let Some(event) = receiver.try_recv().ok().filter(filter_event) {
+                            ctx.play_gamepad_event_sound(
+                                &event.code,
+                                event.is_down,
+                                event.device_id.as_deref()
+                            );
+                        }
+                    }
+                    delay::Delay::key_event().await;
+                }
+            }
+        });
+    } // Apply volume and soundpack changes requested over the control socket
+    {
+        let ctx = audio_context.clone();
+
+        use_future(move || {
+            let ctx = ctx.clone();
+            async move {
+                loop {
+                    process_audio_commands(&ctx);
+                    delay::Delay::ms(50).await;
+                }
+            }
+        });
+    } // Show cache refreshes made outside the UI, e.g. by the soundpack watcher
+    {
+        let mut update_signal = update_signal;
+
+        use_future(move || async move {
+            let mut seen = crate::state::app::cache_generation();
+            loop {
+                let current = crate::state::app::cache_generation();
+                if current != seen {
+                    seen = current;
+                    update_signal += 1;
+                }
+                delay::Delay::ms(250).await;
+            }
+        });
     } // Process hotkey Ctrl+Alt+M to toggle global sound
     {
         let hotkey_rx = hotkey_rx.clone();
@@ -419,6 +478,8 @@ pub fn app() -> Element {
         WindowController {}
         // prettier-ignore
         Header {}
+        // prettier-ignore
+        InstallLinkModal {}
 
         Router::<Route> {}
     }
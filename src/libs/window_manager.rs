@@ -3,7 +3,6 @@ use std::sync::{ Arc, Mutex, mpsc };
 
 #[derive(Debug, Clone)]
 pub enum WindowAction {
-    #[allow(dead_code)]
     Show,
     Hide,
 }
@@ -42,6 +41,11 @@ impl WindowManager {
         }
     }
 
+    pub fn show(&self) {
+        // Visibility is recorded by the window controller once the window is really shown
+        self.send_action(WindowAction::Show);
+    }
+
     pub fn hide(&self) {
         self.set_visible(false);
         self.send_action(WindowAction::Hide);
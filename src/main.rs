@@ -13,14 +13,11 @@ use libs::ui;
 use libs::window_manager::{ WindowAction, WINDOW_MANAGER };
 use libs::input_listener::start_unified_input_listener;
 use libs::focused_input_listener::start_focused_keyboard_listener;
-use libs::input_manager::{ init_input_channels, init_window_focus_state_with_value, get_window_focus_state };
+use libs::input_manager::{ init_input_channels, init_window_focus_state_with_value, get_window_focus_state, InputEvent };
 use std::sync::mpsc;
 
 #[cfg(target_os = "linux")]
-use libs::evdev_input_listener::start_evdev_keyboard_listener;
-
-#[cfg(target_os = "linux")]
-use std::sync::{Arc, Mutex};
+use libs::evdev_input_listener::{ start_evdev_gamepad_listener, start_evdev_input_listener };
 
 // Use .ico format for better Windows compatibility
 const EMBEDDED_ICON: &[u8] = include_bytes!("../assets/icon.ico");
@@ -65,13 +62,47 @@ fn load_icon() -> Option<dioxus::desktop::tao::window::Icon> {
 }
 
 fn main() {
+    // Subcommands print JSON and exit, so they run before any log output
+    // `mechaura ctl ...` talks to the running instance, `mechaura pack ...` works on soundpack files
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("ctl") => std::process::exit(libs::control_socket::run_ctl(&args[2..])),
+        Some("pack") => std::process::exit(libs::pack_cli::run_pack(&args[2..])),
+        _ => {}
+    }
+
     // Initialize debug logging first
     utils::logger::init_debug_logging();
 
     env_logger::init();
 
+    // Only one instance may run input hooks, a second launch hands its arguments over and exits
+    let _instance_lock = match libs::single_instance::acquire() {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            let forwarded = libs::single_instance::forwardable_args(&args);
+            match libs::control_socket::forward_args(&forwarded) {
+                Ok(()) => {
+                    always_print!("👋 {} is already running, handed over to it", APP_NAME);
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    always_eprint!("❌ {} is already running but did not respond: {}", APP_NAME, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Err(e) => {
+            always_eprint!("⚠️ Single-instance check failed, starting anyway: {}", e);
+            None
+        }
+    };
+
     debug_print!("🚀 Initializing {}...", APP_NAME);
 
+    // Older versions kept everything in data/ next to the executable
+    state::paths::migrate_legacy_data();
+
     // Initialize app manifest first
     let _manifest = state::manifest::AppManifest::load();
 
@@ -81,19 +112,27 @@ fn main() {
     }
 
     // Check for command line arguments (protocol handling and startup options)
-    let args: Vec<String> = std::env::args().collect();
     debug_print!("🔍 Command line args: {:?}", args);
 
+    // Deep links and soundpack ZIPs, e.g. `mechaura mechaura://install-soundpack/<id>`
+    libs::single_instance::handle_args(&args, false);
+
+    // Headless mode plays sounds without creating the window (tiling WMs, SSH, systemd)
+    let headless = args.contains(&"--headless".to_string());
+
     // Check if we should start minimized (from auto-startup)
     let should_start_minimized =
         args.contains(&"--minimized".to_string()) ||
         (state::config::AppConfig::load().auto_start &&
             state::config::AppConfig::load().start_minimized);
 
-    // Register protocol on first run
-    // if let Err(e) = protocol::register_protocol() {
-    //     eprintln!("Warning: Failed to register MechAura:// protocol: {}", e);
-    // }    // Initialize global app state before rendering
+    // Keep the mechaura:// handler pointing at this executable
+    #[cfg(target_os = "linux")]
+    if let Err(e) = libs::protocol::register_protocol() {
+        debug_eprint!("⚠️ Failed to register {} protocol: {}", utils::constants::APP_PROTOCOL_URL, e);
+    }
+
+    // Initialize global app state before rendering
     state::app::init_app_state();
     state::app::init_update_state();
 
@@ -112,8 +151,9 @@ fn main() {
     // to ensure proper Dioxus runtime context
 
     // Create input event channels for communication between input listener and UI
-    let (keyboard_tx, keyboard_rx) = mpsc::channel::<String>();
-    let (mouse_tx, mouse_rx) = mpsc::channel::<String>();
+    let (keyboard_tx, keyboard_rx) = mpsc::channel::<InputEvent>();
+    let (mouse_tx, mouse_rx) = mpsc::channel::<InputEvent>();
+    let (gamepad_tx, gamepad_rx) = mpsc::channel::<InputEvent>();
     let (hotkey_tx, hotkey_rx) = mpsc::channel::<String>();
 
     // Clone senders for global access (for window-level keyboard events)
@@ -122,11 +162,20 @@ fn main() {
     let hotkey_tx_clone = hotkey_tx.clone();
 
     // Initialize global input channels for UI to access (including senders for window events)
-    init_input_channels(keyboard_rx, mouse_rx, hotkey_rx, keyboard_tx_clone, mouse_tx_clone, hotkey_tx_clone);
+    init_input_channels(
+        keyboard_rx,
+        mouse_rx,
+        gamepad_rx,
+        hotkey_rx,
+        keyboard_tx_clone,
+        mouse_tx_clone,
+        hotkey_tx_clone
+    );
 
     // Initialize window focus state
     // If window starts visible (not minimized), it will be focused
-    let initial_focus_state = !should_start_minimized;
+    // Headless never has a focused window, so rdev handles all keyboard input there
+    let initial_focus_state = !should_start_minimized && !headless;
     init_window_focus_state_with_value(initial_focus_state);
     debug_print!("🔍 Initial window focus state: {}", if initial_focus_state { "FOCUSED" } else { "UNFOCUSED" });
 
@@ -140,18 +189,20 @@ fn main() {
     // Start input listeners based on platform and display server
     #[cfg(target_os = "linux")]
     {
-        if display_server == "wayland" {
-            // On Wayland, use evdev for keyboard input (works both focused and unfocused)
-            // evdev also handles hotkey detection (Ctrl+Alt+M)
-            debug_print!("🎮 Starting evdev keyboard listener (Wayland mode)...");
+        // Per-device filtering and soundpacks need evdev, rdev and device_query can't tell devices apart
+        let startup_config = state::config::AppConfig::load();
+        let uses_device_filter =
+            !startup_config.enabled_keyboards.is_empty() ||
+            !startup_config.enabled_mice.is_empty() ||
+            !startup_config.device_soundpacks.is_empty();
+
+        if display_server == "wayland" || uses_device_filter || headless {
+            // On Wayland, use evdev for keyboard and mouse input (works both focused and unfocused)
+            // rdev cannot see global input on Wayland, so evdev also handles hotkey detection (Ctrl+Alt+M)
+            // Headless mode may run without any display server (SSH, systemd), which only evdev can handle
+            debug_print!("🎮 Starting evdev input listener (display server: {}, device filter: {}, headless: {})...", display_server, uses_device_filter, headless);
             let focus_state = get_window_focus_state();
-            start_evdev_keyboard_listener(keyboard_tx.clone(), hotkey_tx.clone(), focus_state);
-
-            // Use rdev for mouse events only (no keyboard/hotkeys on Wayland)
-            // Pass "always focused" state to prevent rdev from sending keyboard events
-            debug_print!("🎮 Starting unified input listener for mouse events (Wayland mode)...");
-            let always_focused = Arc::new(Mutex::new(true));
-            start_unified_input_listener(keyboard_tx, mouse_tx, hotkey_tx, Some(always_focused));
+            start_evdev_input_listener(keyboard_tx, mouse_tx, hotkey_tx, focus_state);
         } else {
             // On X11, use the hybrid approach (rdev + device_query)
             // rdev handles keyboard when unfocused, device_query when focused
@@ -163,6 +214,10 @@ fn main() {
             debug_print!("🎮 Starting focused keyboard listener (X11 mode - focused)...");
             start_focused_keyboard_listener(keyboard_tx, focus_state);
         }
+
+        // Gamepads are only visible through evdev, on X11 and Wayland alike
+        debug_print!("🎮 Starting evdev gamepad listener...");
+        start_evdev_gamepad_listener(gamepad_tx);
     }
 
     // On Windows and macOS, use the hybrid approach (rdev + device_query)
@@ -176,6 +231,19 @@ fn main() {
 
         debug_print!("🎮 Starting focused keyboard listener (focused)...");
         start_focused_keyboard_listener(keyboard_tx, focus_state);
+
+        // Gamepad capture needs evdev, so there is no gamepad backend here yet
+        drop(gamepad_tx);
+    }
+
+    // Local control socket for scripts and status bars (`mechaura ctl`)
+    libs::control_socket::start_control_server();
+
+    // Reload soundpacks while their authors edit them
+    libs::soundpack_watcher::start_soundpack_watcher();
+
+    if headless {
+        libs::headless::run(args.contains(&"--ambiance".to_string()));
     }
 
     // Create window action channel
@@ -1,8 +1,10 @@
 // Event-driven App State Manager
-use crate::state::soundpack::SoundpackCache;
+use crate::state::soundpack::{ scan_progress, ScanProgress, SoundpackCache };
+use crate::utils::soundpack_doctor::DoctorReport;
 use crate::{ debug_print, always_eprint };
 use dioxus::prelude::*;
 use once_cell::sync::OnceCell;
+use std::sync::atomic::{ AtomicU32, Ordering };
 use std::sync::{ Arc, Mutex };
 
 // Global app state for sharing between components
@@ -31,19 +33,67 @@ impl AppState {
     pub fn get_soundpacks(&self) -> Vec<crate::state::soundpack::SoundpackMetadata> {
         self.optimized_cache.soundpacks.values().cloned().collect()
     }
-    pub fn refresh_cache(&mut self) {
-        debug_print!("🔄 Refreshing soundpack cache...");
-        let mut fresh_cache = SoundpackCache::load();
-        fresh_cache.refresh_from_directory();
-        fresh_cache.save();
-        self.optimized_cache = Arc::new(fresh_cache);
+    fn set_cache(&mut self, cache: SoundpackCache) {
+        self.optimized_cache = Arc::new(cache);
         self.last_updated = std::time::Instant::now();
     }
 }
 
+// One scan at a time, they all read and write the same cache file
+static SCAN_LOCK: Mutex<()> = Mutex::new(());
+
+fn scan_cache() -> SoundpackCache {
+    let _scan = SCAN_LOCK.lock();
+    debug_print!("🔄 Refreshing soundpack cache...");
+    let mut fresh_cache = SoundpackCache::load();
+    fresh_cache.refresh_from_directory();
+    fresh_cache.save();
+    fresh_cache
+}
+
 // Global state instance
 static GLOBAL_APP_STATE: OnceCell<Mutex<AppState>> = OnceCell::new();
 
+// Bumped by cache refreshes from outside the UI, which polls it
+static CACHE_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+/// Refresh the soundpack cache from a background thread, the UI follows on its next poll
+///
+/// The state stays readable while packs are scanned, only the finished cache is swapped in.
+pub fn refresh_cache_in_background() {
+    let fresh_cache = scan_cache();
+    if let Some(global_state) = GLOBAL_APP_STATE.get() {
+        if let Ok(mut state) = global_state.lock() {
+            state.set_cache(fresh_cache);
+        }
+    }
+    CACHE_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Save a doctor report into the cache, under the scan lock so a running rescan keeps it
+pub fn store_doctor_report(soundpack_id: &str, report: DoctorReport) {
+    let cache = {
+        let _scan = SCAN_LOCK.lock();
+        let mut cache = SoundpackCache::load();
+        let Some(metadata) = cache.soundpacks.get_mut(soundpack_id) else {
+            return;
+        };
+        metadata.doctor = Some(report);
+        cache.save();
+        cache
+    };
+    if let Some(global_state) = GLOBAL_APP_STATE.get() {
+        if let Ok(mut state) = global_state.lock() {
+            state.set_cache(cache);
+        }
+    }
+    CACHE_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+pub fn cache_generation() -> u32 {
+    CACHE_GENERATION.load(Ordering::SeqCst)
+}
+
 // Simple hook for read-only access
 pub fn use_app_state() -> AppState {
     let update_signal: Signal<u32> = use_context();
@@ -63,24 +113,49 @@ pub fn use_app_state() -> AppState {
     result
 }
 
-// Hook to trigger state updates
+/// Rescan the soundpacks and wait for it, for callers that read `SoundpackCache::load()` next
+///
+/// The UI picks up the new cache through `cache_generation` like any other background refresh.
+pub async fn refresh_cache() {
+    if let Err(e) = tokio::task::spawn_blocking(refresh_cache_in_background).await {
+        always_eprint!("❌ Soundpack cache refresh failed: {}", e);
+    }
+}
+
+// Hook to trigger state updates, the scan runs off the UI thread (see `use_scan_progress`)
+//
+// Calling it only schedules the scan, the cache on disk and in `use_app_state` is still the old
+// one when it returns. Code that needs the new packs right away awaits `refresh_cache` instead.
 pub fn use_state_trigger() -> Callback<()> {
     let mut update_signal: Signal<u32> = use_context();
     use_callback(move |_| {
-        // Refresh cache and trigger UI update
-        if let Some(global_state) = GLOBAL_APP_STATE.get() {
-            if let Ok(mut state) = global_state.lock() {
-                println!("🔄 Triggering cache refresh...");
-                state.refresh_cache();
+        println!("🔄 Triggering cache refresh...");
+        spawn(async move {
+            // Refresh cache and trigger UI update
+            refresh_cache().await;
+            // Trigger UI update by incrementing the signal value
+            let current_value = {
+                let val = update_signal.read();
+                *val
+            };
+            update_signal.set(current_value + 1);
+        });
+    })
+}
+
+// Hook to follow a running soundpack scan, None when idle
+pub fn use_scan_progress() -> Signal<Option<ScanProgress>> {
+    let mut progress = use_signal(scan_progress);
+    use_future(move || async move {
+        loop {
+            let current = scan_progress();
+            if *progress.peek() != current {
+                progress.set(current);
             }
+            futures_timer::Delay::new(std::time::Duration::from_millis(200)).await;
         }
-        // Trigger UI update by incrementing the signal value
-        let current_value = {
-            let val = update_signal.read();
-            *val
-        };
-        update_signal.set(current_value + 1);
-    })
+    });
+    progress
 }
 
 // Reload the current soundpacks from configuration
@@ -127,7 +202,34 @@ pub fn reload_current_soundpacks(audio_ctx: &crate::libs::audio::AudioContext) {
             config.mouse_soundpack = "".to_string();
             config_changed = true;
         }
-    } // Save config if any changes were made
+    } // Load gamepad soundpack
+    match
+        crate::libs::audio::soundpack_loader::load_gamepad_soundpack_with_cache_control(
+            audio_ctx,
+            &config.gamepad_soundpack,
+            false
+        )
+    {
+        Ok(_) =>
+            debug_print!("✅ Gamepad soundpack '{}' reloaded successfully", config.gamepad_soundpack),
+        Err(e) => {
+            always_eprint!(
+                "❌ Failed to reload gamepad soundpack '{}': {}. Clearing selection.",
+                config.gamepad_soundpack,
+                e
+            );
+            config.gamepad_soundpack = "".to_string();
+            config_changed = true;
+        }
+    }
+    // Per-device soundpacks: drop cached audio so edited packs are re-read from disk
+    if let Ok(mut device_packs) = audio_ctx.device_packs.lock() {
+        device_packs.clear();
+    }
+    if let Err(e) = crate::libs::audio::soundpack_loader::load_device_soundpacks(audio_ctx) {
+        always_eprint!("❌ Failed to reload per-device soundpacks: {}", e);
+    }
+    // Save config if any changes were made
     if config_changed {
         let _ = config.save();
         debug_print!("💾 Config updated due to failed soundpack loads");
@@ -1,3 +1,4 @@
+use crate::libs::key_codes::{ key_class, KeyClass };
 use crate::libs::theme::{ BuiltInTheme, Theme };
 use crate::state::paths;
 use crate::utils::{ data, path };
@@ -27,6 +28,100 @@ impl Default for MusicPlayerConfig {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DebounceConfig {
+    pub enabled: bool,
+    pub default_ms: u32, // Minimum time between two presses of the same key
+    pub class_ms: HashMap<KeyClass, u32>, // Per key class, overrides default_ms
+    pub key_ms: HashMap<String, u32>, // Per key code, overrides class_ms
+    pub chatter_threshold_ms: u32, // A press this soon after a release is flagged as switch chatter
+}
+
+impl Default for DebounceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_ms: 5,
+            class_ms: HashMap::new(),
+            key_ms: HashMap::from([("Backspace".to_string(), 10)]), // Backspace is prone to double-fires
+            chatter_threshold_ms: 30,
+        }
+    }
+}
+
+impl DebounceConfig {
+    /// Debounce time for a key code: per-key setting, then its class, then the default
+    pub fn debounce_ms(&self, code: &str) -> u32 {
+        self.key_ms
+            .get(code)
+            .or_else(|| self.class_ms.get(&key_class(code)))
+            .copied()
+            .unwrap_or(self.default_ms)
+    }
+}
+
+/// Most recently used packs kept for sorting
+const MAX_RECENT_SOUNDPACKS: usize = 30;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum SoundpackSort {
+    #[default]
+    Recent,
+    Name,
+    Author,
+}
+
+impl SoundpackSort {
+    pub const ALL: [SoundpackSort; 3] = [SoundpackSort::Recent, SoundpackSort::Name, SoundpackSort::Author];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SoundpackSort::Recent => "Recently used",
+            SoundpackSort::Name => "Name",
+            SoundpackSort::Author => "Author",
+        }
+    }
+}
+
+/// How the soundpack selectors list packs, shared by all of them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct SoundpackBrowserConfig {
+    pub favorites: Vec<String>, // Pinned soundpack IDs, listed first
+    pub tags: Vec<String>, // Selected tag chips, packs need all of them
+    pub sort: SoundpackSort,
+    pub recent: Vec<String>, // Soundpack IDs, most recently used first
+}
+
+impl SoundpackBrowserConfig {
+    pub fn is_favorite(&self, soundpack_id: &str) -> bool {
+        self.favorites.iter().any(|id| id == soundpack_id)
+    }
+
+    pub fn toggle_favorite(&mut self, soundpack_id: &str) {
+        if self.is_favorite(soundpack_id) {
+            self.favorites.retain(|id| id != soundpack_id);
+        } else {
+            self.favorites.push(soundpack_id.to_string());
+        }
+    }
+
+    pub fn toggle_tag(&mut self, tag: &str) {
+        if self.tags.iter().any(|t| t == tag) {
+            self.tags.retain(|t| t != tag);
+        } else {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    pub fn record_use(&mut self, soundpack_id: &str) {
+        self.recent.retain(|id| id != soundpack_id);
+        self.recent.insert(0, soundpack_id.to_string());
+        self.recent.truncate(MAX_RECENT_SOUNDPACKS);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LogoCustomization {
     pub border_color: String,
@@ -75,6 +170,20 @@ impl Default for BackgroundCustomization {
     }
 }
 
+// Default functions for fields added after the first release
+fn default_gamepad_volume() -> f32 {
+    1.0
+}
+
+fn default_enable_gamepad_sound() -> bool {
+    true
+}
+
+// No catalog is published yet, the user sets one on the Catalog tab
+fn default_catalog_url() -> String {
+    String::new()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     // Metadata
@@ -84,16 +193,26 @@ pub struct AppConfig {
     // Audio settings
     pub keyboard_soundpack: String,
     pub mouse_soundpack: String,
+    #[serde(default)]
+    pub gamepad_soundpack: String, // Empty = no gamepad sounds
     pub volume: f32,
     pub mouse_volume: f32, // Separate volume for mouse sounds
+    #[serde(default = "default_gamepad_volume")]
+    pub gamepad_volume: f32, // Separate volume for gamepad sounds
     pub enable_volume_boost: bool, // Enable/disable volume boost to 200%
     pub enable_sound: bool,
     pub enable_keyboard_sound: bool, // Enable/disable keyboard sounds specifically
     pub enable_mouse_sound: bool, // Enable/disable mouse sounds specifically
+    #[serde(default = "default_enable_gamepad_sound")]
+    pub enable_gamepad_sound: bool, // Enable/disable gamepad sounds specifically
     // Device settings
     pub selected_audio_device: Option<String>, // Selected audio output device
     pub enabled_keyboards: Vec<String>, // Enabled physical keyboards (by device instance ID)
     pub enabled_mice: Vec<String>, // Enabled physical mice (by device instance ID)
+    #[serde(default)]
+    pub device_soundpacks: HashMap<String, String>, // device ID -> soundpack ID (unassigned devices use the global soundpack)
+    #[serde(default)]
+    pub debounce: DebounceConfig, // Key chatter filtering
     // UI settings
     pub theme: Theme,
     pub custom_css: String, // Legacy field for existing custom CSS
@@ -112,6 +231,10 @@ pub struct AppConfig {
     pub start_minimized: bool, // Start minimized to tray when auto-starting with Windows
     pub landscape_mode: bool, // Enable/disable landscape mode layout
     pub auto_update: AutoUpdateConfig, // Auto-update settings
+    #[serde(default = "default_catalog_url")]
+    pub catalog_url: String, // Soundpack catalog index, a URL or local path
+    #[serde(default)]
+    pub soundpack_browser: SoundpackBrowserConfig, // Selector favorites, tag filter and sorting
 }
 
 impl AppConfig {
@@ -128,11 +251,11 @@ impl AppConfig {
         // Load config from file, falling back to defaults if it doesn't exist or is invalid
         match data::load_json_from_file::<AppConfig>(&config_path) {
             Ok(mut config) => {
-                // Sync auto_start with actual registry state
+                // Sync auto_start with the actual startup entry (registry or XDG autostart)
                 let actual_auto_start = crate::utils::auto_startup::get_auto_startup_state();
                 if config.auto_start != actual_auto_start {
                     println!(
-                        "🔄 Syncing auto_start config with registry: {} -> {}",
+                        "🔄 Syncing auto_start config with startup entry: {} -> {}",
                         config.auto_start,
                         actual_auto_start
                     );
@@ -150,6 +273,13 @@ impl AppConfig {
         }
     }
 
+    /// Read config.json as it is, without writing defaults or syncing auto start
+    ///
+    /// For threads that poll the config, they keep their previous values when this fails.
+    pub fn read() -> Option<Self> {
+        data::load_json_from_file::<AppConfig>(&paths::data::config_json()).ok()
+    }
+
     pub fn save(&self) -> Result<(), String> {
         let config_path = paths::data::config_json();
         data::save_json_to_file(self, &config_path)
@@ -164,15 +294,20 @@ impl Default for AppConfig {
             commit: option_env!("GIT_HASH").map(|s| s.to_string()),
             keyboard_soundpack: "oreo".to_string(),
             mouse_soundpack: "test-mouse".to_string(),
+            gamepad_soundpack: String::new(), // No built-in gamepad soundpacks
             volume: 1.0,
             mouse_volume: 1.0, // Default mouse volume to 100%
+            gamepad_volume: default_gamepad_volume(),
             enable_volume_boost: false, // Default volume boost disabled
             enable_sound: true,
             enable_keyboard_sound: true, // Default keyboard sounds enabled
             enable_mouse_sound: true, // Default mouse sounds enabled
+            enable_gamepad_sound: default_enable_gamepad_sound(),
             selected_audio_device: None, // Default to system default audio device
             enabled_keyboards: Vec::new(), // Default to no keyboards enabled (all keyboards will work)
             enabled_mice: Vec::new(), // Default to no mice enabled (all mice will work)
+            device_soundpacks: HashMap::new(),
+            debounce: DebounceConfig::default(),
             theme: Theme::BuiltIn(BuiltInTheme::System), // Default to System theme
             custom_css: String::new(),
             logo_customization: LogoCustomization::default(),
@@ -188,6 +323,8 @@ impl Default for AppConfig {
             start_minimized: false, // Default to not starting minimized
             landscape_mode: false, // Default landscape mode disabled
             auto_update: AutoUpdateConfig::default(), // Default auto-update settings
+            catalog_url: default_catalog_url(),
+            soundpack_browser: SoundpackBrowserConfig::default(),
         }
     }
 }
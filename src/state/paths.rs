@@ -1,13 +1,18 @@
 /// Centralized path definitions
 ///
 /// ## Path Structure
-/// - `data/` - Application data and configuration files (relative to app root)
-/// - `soundpacks/` - Built-in soundpack directories (relative to app root)
-/// - Custom soundpacks - Stored in system app data directory (e.g., %APPDATA%/mechaura/soundpacks)
-/// - Custom images - Stored in system app data directory (e.g., %APPDATA%/mechaura/custom_images)
+/// - Config (config.json, themes.json, manifest.json) - System config directory
+///   (e.g., $XDG_CONFIG_HOME/mechaura, %APPDATA%/mechaura)
+/// - Caches (soundpack_cache.json, music.json) - System cache directory
+///   (e.g., $XDG_CACHE_HOME/mechaura, %LOCALAPPDATA%/mechaura)
+/// - Custom soundpacks and images - System app data directory
+///   (e.g., $XDG_DATA_HOME/mechaura/soundpacks, %APPDATA%/mechaura/soundpacks)
+/// - `soundpacks/` - Built-in soundpack directories (relative to app root, or `../share/mechaura`)
 ///
-/// All paths are relative to the application executable directory unless specified otherwise.
-use std::path::PathBuf;
+/// In portable mode (a `portable` file next to the executable, or `$MECHAURA_PORTABLE=1`)
+/// everything except the built-in soundpacks lives in `data/` next to the executable instead.
+use crate::debug_print;
+use std::path::{ Path, PathBuf };
 use std::sync::OnceLock;
 
 /// Get the application root directory (where the executable is located)
@@ -23,30 +28,76 @@ fn get_app_root() -> &'static PathBuf {
                 if exe_path_str.contains("target\\dx\\") || exe_path_str.contains("target/dx/") {
                     // In dev mode, use current working directory (project root)
                     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-                    println!("📂 App root (dev mode - from cwd): {}", cwd.display());
+                    debug_print!("📂 App root (dev mode - from cwd): {}", cwd.display());
                     return cwd;
                 }
 
-                println!("📂 App root (from exe): {}", exe_dir.display());
+                debug_print!("📂 App root (from exe): {}", exe_dir.display());
                 return exe_dir.to_path_buf();
             }
         }
 
         // Fallback to current working directory (for development)
         let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        println!("📂 App root (fallback - from cwd): {}", cwd.display());
+        debug_print!("📂 App root (fallback - from cwd): {}", cwd.display());
         cwd
     })
 }
 
+/// Whether all user data is kept next to the executable
+pub fn is_portable() -> bool {
+    static PORTABLE: OnceLock<bool> = OnceLock::new();
+    *PORTABLE.get_or_init(|| {
+        let from_env = std::env
+            ::var("MECHAURA_PORTABLE")
+            .is_ok_and(|value| !value.is_empty() && value != "0");
+        let portable = from_env || get_app_root().join("portable").exists();
+        if portable {
+            debug_print!("🧳 Portable mode, data stays in {}", get_portable_data_dir().display());
+        }
+        portable
+    })
+}
+
+/// `data/` next to the executable, the only location in portable mode and the legacy one otherwise
+fn get_portable_data_dir() -> PathBuf {
+    get_app_root().join("data")
+}
+
+/// Get the system config directory for mechaura ($XDG_CONFIG_HOME/mechaura on Linux)
+fn get_config_dir() -> PathBuf {
+    if is_portable() {
+        return get_portable_data_dir();
+    }
+    directories::BaseDirs
+        ::new()
+        .map(|base_dirs| base_dirs.config_dir().join("mechaura"))
+        .unwrap_or_else(get_portable_data_dir)
+}
+
+/// Get the system cache directory for mechaura ($XDG_CACHE_HOME/mechaura on Linux)
+fn get_cache_dir() -> PathBuf {
+    if is_portable() {
+        return get_portable_data_dir();
+    }
+    directories::BaseDirs
+        ::new()
+        .map(|base_dirs| base_dirs.cache_dir().join("mechaura"))
+        .unwrap_or_else(get_portable_data_dir)
+}
+
 /// Get the system app data directory for mechaura
 /// Returns platform-specific app data directory:
 /// - Windows: %APPDATA%/mechaura
 /// - macOS: ~/Library/Application Support/mechaura
 /// - Linux: ~/.local/share/mechaura
+/// - Portable mode: data/ next to the executable
 fn get_system_app_data_dir() -> PathBuf {
     use directories::BaseDirs;
 
+    if is_portable() {
+        return get_portable_data_dir();
+    }
     if let Some(base_dirs) = BaseDirs::new() {
         #[cfg(target_os = "windows")]
         {
@@ -70,33 +121,76 @@ fn get_system_app_data_dir() -> PathBuf {
         }
     } else {
         // Fallback to app root if system directories not available
-        get_app_root().join("data")
+        get_portable_data_dir()
+    }
+}
+
+/// Move files from the `data/` next to the executable, used by older versions, to the
+/// system directories
+///
+/// Only touches files that exist in the old location and not yet in the new one, so it is
+/// safe to run on every start. Files a read-only install won't let go of are copied.
+pub fn migrate_legacy_data() {
+    if is_portable() {
+        return;
+    }
+
+    let legacy_dir = get_portable_data_dir();
+    let files = [
+        ("config.json", data::config_json()),
+        ("manifest.json", data::manifest_json()),
+        ("themes.json", data::themes_json()),
+        ("soundpack_cache.json", data::soundpack_cache_json()),
+        ("music.json", get_cache_dir().join("music.json")),
+    ];
+    for (name, target) in files {
+        let source = legacy_dir.join(name);
+        if !source.is_file() || target.exists() || source == target {
+            continue;
+        }
+        match migrate_file(&source, &target) {
+            Ok(()) => println!("📦 Moved {} to {}", source.display(), target.display()),
+            Err(e) => eprintln!("❌ Failed to move {} to {}: {}", source.display(), target.display(), e),
+        }
     }
 }
 
+fn migrate_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    // Another filesystem or a read-only source, copy and drop the original when allowed
+    std::fs::copy(source, target)?;
+    let _ = std::fs::remove_file(source);
+    Ok(())
+}
+
 /// Application data directory paths
 pub mod data {
-    use super::{get_app_root, get_system_app_data_dir};
+    use super::{get_cache_dir, get_config_dir, get_system_app_data_dir};
     use std::path::PathBuf;
 
     /// Application configuration file
     pub fn config_json() -> PathBuf {
-        get_app_root().join("data").join("config.json")
+        get_config_dir().join("config.json")
     }
 
     /// Application manifest file
     pub fn manifest_json() -> PathBuf {
-        get_app_root().join("data").join("manifest.json")
+        get_config_dir().join("manifest.json")
     }
 
     /// Custom themes configuration file
     pub fn themes_json() -> PathBuf {
-        get_app_root().join("data").join("themes.json")
+        get_config_dir().join("themes.json")
     }
 
-    /// Soundpack cache file
+    /// Soundpack cache file, the music cache sits next to it
     pub fn soundpack_cache_json() -> PathBuf {
-        get_app_root().join("data").join("soundpack_cache.json")
+        get_cache_dir().join("soundpack_cache.json")
     }
 
     /// Custom images directory for user-uploaded images
@@ -136,9 +230,18 @@ pub mod soundpacks {
         BUILTIN_SOUNDPACKS.contains(&soundpack_id)
     }
 
-    /// Get the base soundpacks directory for built-in soundpacks (app root)
+    /// Get the base soundpacks directory for built-in soundpacks
+    /// Next to the executable, or in `../share/mechaura` for /usr/bin installs and AppImages
     pub fn get_builtin_soundpacks_dir() -> PathBuf {
-        get_app_root().join("soundpacks")
+        let next_to_exe = get_app_root().join("soundpacks");
+        if next_to_exe.exists() {
+            return next_to_exe;
+        }
+        get_app_root()
+            .parent()
+            .map(|prefix| prefix.join("share").join("mechaura").join("soundpacks"))
+            .filter(|shared| shared.exists())
+            .unwrap_or(next_to_exe)
     }
 
     /// Get the base soundpacks directory for custom soundpacks (system app data)
@@ -189,7 +292,7 @@ pub mod soundpacks {
             .to_string()
     }
 
-    /// Get the base soundpacks directory (containing keyboard/ and mouse/ folders)
+    /// Get the base soundpacks directory (containing keyboard/, mouse/ and gamepad/ folders)
     /// Returns built-in soundpacks directory
     pub fn get_soundpacks_dir() -> String {
         get_builtin_soundpacks_dir().to_string_lossy().to_string()
@@ -227,44 +330,52 @@ pub mod soundpacks {
             .to_string()
     }
 
-    /// Ensure soundpack directories exist (keyboard and mouse)
+    /// Ensure soundpack directories exist (keyboard, mouse and gamepad)
     /// Creates the directories if they don't exist
     pub fn ensure_soundpack_directories() -> Result<(), std::io::Error> {
         use std::fs;
 
         // Ensure built-in soundpack directories exist
-        let builtin_soundpacks_dir = get_builtin_soundpacks_dir();
-        let builtin_keyboard_dir = builtin_soundpacks_dir.join("keyboard");
-        let builtin_mouse_dir = builtin_soundpacks_dir.join("mouse");
-
-        if !builtin_soundpacks_dir.exists() {
-            fs::create_dir_all(&builtin_soundpacks_dir)?;
-            crate::debug_print!(
-                "📁 Created built-in soundpacks directory: {}",
-                builtin_soundpacks_dir.display()
-            );
-        }
+        // They can be read-only (/usr/share, AppImage), which only matters for development
+        let ensure_builtin = || -> Result<(), std::io::Error> {
+            let builtin_soundpacks_dir = get_builtin_soundpacks_dir();
+            let builtin_keyboard_dir = builtin_soundpacks_dir.join("keyboard");
+            let builtin_mouse_dir = builtin_soundpacks_dir.join("mouse");
+
+            if !builtin_soundpacks_dir.exists() {
+                fs::create_dir_all(&builtin_soundpacks_dir)?;
+                crate::debug_print!(
+                    "📁 Created built-in soundpacks directory: {}",
+                    builtin_soundpacks_dir.display()
+                );
+            }
 
-        if !builtin_keyboard_dir.exists() {
-            fs::create_dir_all(&builtin_keyboard_dir)?;
-            crate::debug_print!(
-                "⌨️ Created built-in keyboard soundpacks directory: {}",
-                builtin_keyboard_dir.display()
-            );
-        }
+            if !builtin_keyboard_dir.exists() {
+                fs::create_dir_all(&builtin_keyboard_dir)?;
+                crate::debug_print!(
+                    "⌨️ Created built-in keyboard soundpacks directory: {}",
+                    builtin_keyboard_dir.display()
+                );
+            }
 
-        if !builtin_mouse_dir.exists() {
-            fs::create_dir_all(&builtin_mouse_dir)?;
-            crate::debug_print!(
-                "🖱️ Created built-in mouse soundpacks directory: {}",
-                builtin_mouse_dir.display()
-            );
+            if !builtin_mouse_dir.exists() {
+                fs::create_dir_all(&builtin_mouse_dir)?;
+                crate::debug_print!(
+                    "🖱️ Created built-in mouse soundpacks directory: {}",
+                    builtin_mouse_dir.display()
+                );
+            }
+            Ok(())
+        };
+        if let Err(e) = ensure_builtin() {
+            crate::debug_print!("⚠️ Could not create built-in soundpack directories: {}", e);
         }
 
         // Ensure custom soundpack directories exist
         let custom_soundpacks_dir = get_custom_soundpacks_dir();
         let custom_keyboard_dir = custom_soundpacks_dir.join("keyboard");
         let custom_mouse_dir = custom_soundpacks_dir.join("mouse");
+        let custom_gamepad_dir = custom_soundpacks_dir.join("gamepad");
 
         if !custom_soundpacks_dir.exists() {
             fs::create_dir_all(&custom_soundpacks_dir)?;
@@ -290,6 +401,14 @@ pub mod soundpacks {
             );
         }
 
+        if !custom_gamepad_dir.exists() {
+            fs::create_dir_all(&custom_gamepad_dir)?;
+            crate::debug_print!(
+                "🎮 Created custom gamepad soundpacks directory: {}",
+                custom_gamepad_dir.display()
+            );
+        }
+
         Ok(())
     }
 }
@@ -1,8 +1,12 @@
+use crate::debug_print;
 use crate::state::paths;
 use crate::utils::{ data, path, soundpack };
+use crate::utils::soundpack_doctor::DoctorReport;
 use serde::{ Deserialize, Serialize };
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{ Path, PathBuf };
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::sync::Mutex;
 
 // ===== SOUNDPACK TYPES =====
 
@@ -10,6 +14,33 @@ use std::path::Path;
 pub enum SoundpackType {
     Keyboard,
     Mouse,
+    Gamepad,
+}
+
+impl SoundpackType {
+    pub const ALL: [SoundpackType; 3] = [
+        SoundpackType::Keyboard,
+        SoundpackType::Mouse,
+        SoundpackType::Gamepad,
+    ];
+
+    /// Folder name under the soundpacks directory, also the soundpack ID prefix
+    pub fn folder_name(&self) -> &'static str {
+        match self {
+            SoundpackType::Keyboard => "keyboard",
+            SoundpackType::Mouse => "mouse",
+            SoundpackType::Gamepad => "gamepad",
+        }
+    }
+
+    /// Detect the type from a soundpack ID such as "mouse/Soundpack Name"
+    pub fn from_soundpack_id(soundpack_id: &str) -> Self {
+        let prefix = soundpack_id.split(['/', '\\']).next().unwrap_or_default();
+        Self::ALL
+            .into_iter()
+            .find(|soundpack_type| soundpack_type.folder_name() == prefix)
+            .unwrap_or(SoundpackType::Keyboard) // Default to keyboard for backwards compatibility
+    }
 }
 
 // Default function for config_version field
@@ -29,6 +60,16 @@ pub struct SoundpackOptions {
     pub recommended_volume: f32,
     #[serde(default = "default_random_pitch")]
     pub random_pitch: bool,
+    // Short ramps at segment edges so cuts on a loud sample don't tick, off unless the pack opts in
+    #[serde(default)]
+    pub micro_fades: bool,
+    #[serde(default = "default_fade_in_ms")]
+    pub fade_in_ms: f32,
+    #[serde(default = "default_fade_out_ms")]
+    pub fade_out_ms: f32,
+    // Move segment edges onto the nearest zero crossing when the pack loads
+    #[serde(default)]
+    pub snap_to_zero_crossings: bool,
 }
 
 fn default_recommended_volume() -> f32 {
@@ -39,11 +80,23 @@ fn default_random_pitch() -> bool {
     false
 }
 
+fn default_fade_in_ms() -> f32 {
+    1.0
+}
+
+fn default_fade_out_ms() -> f32 {
+    3.0
+}
+
 impl Default for SoundpackOptions {
     fn default() -> Self {
         Self {
             recommended_volume: 1.0,
             random_pitch: false,
+            micro_fades: false,
+            fade_in_ms: 1.0,
+            fade_out_ms: 3.0,
+            snap_to_zero_crossings: false,
         }
     }
 }
@@ -114,13 +167,23 @@ pub struct SoundpackMetadata {
     pub is_valid_v2: bool,
     pub validation_status: String,
     pub can_be_converted: bool,
+    // "single" or "multi" as read during the scan, None when the config could not be loaded
+    #[serde(default)]
+    pub definition_method: Option<String>,
     // Error tracking
     #[serde(default)]
     pub last_error: Option<String>,
+    // Last soundpack doctor run, kept across rescans while config.json is unchanged
+    #[serde(default)]
+    pub doctor: Option<DoctorReport>,
 }
 
 // ===== SOUNDPACK CACHE =====
 
+/// Version 5 added fingerprints, version 6 the definition method, version 7 audio files in the
+/// fingerprints, older caches are rescanned once
+pub const CACHE_VERSION: u32 = 7;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SoundpackCache {
     pub soundpacks: HashMap<String, SoundpackMetadata>,
@@ -128,12 +191,133 @@ pub struct SoundpackCache {
     pub cache_version: u32, // Add version to force regeneration when format changes
     #[serde(default)]
     pub count: SoundpackCount, // Count of soundpacks by type
+    // What each entry was built from, rescans skip packs whose fingerprint did not change
+    #[serde(default)]
+    pub fingerprints: HashMap<String, PackFingerprint>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SoundpackCount {
     pub keyboard: usize,
     pub mouse: usize,
+    #[serde(default)]
+    pub gamepad: usize,
+}
+
+/// Modification times of a pack folder and its config.json, plus a hash of the config
+///
+/// The folder time changes when files are added, removed or renamed. The hash catches configs
+/// that were rewritten with the same content (checkouts, copies), which are not rescanned.
+/// Audio files replaced in place change neither, so the ones the config uses are stamped too.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackFingerprint {
+    pub dir_modified: u64, // Milliseconds since the Unix epoch
+    pub config_modified: u64,
+    pub config_hash: String,
+    #[serde(default)]
+    pub audio: Vec<AudioStamp>,
+}
+
+/// Modification time and size of one audio file the config points to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AudioStamp {
+    pub file: String, // Relative to the pack folder
+    pub modified: u64,
+    pub size: u64,
+}
+
+fn modified_millis(path: &Path) -> Option<u64> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as u64)
+}
+
+// Audio files named by the config, the pack-wide one and any per-key ones
+fn referenced_audio_files(config: &[u8]) -> Vec<String> {
+    let Ok(config) = serde_json::from_slice::<serde_json::Value>(config) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = std::iter
+        ::once(&config["audio_file"])
+        .chain(
+            config["definitions"]
+                .as_object()
+                .into_iter()
+                .flat_map(|definitions| definitions.values().map(|definition| &definition["audio_file"]))
+        )
+        .filter_map(|file| file.as_str())
+        .map(|file| file.trim_start_matches("./").to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+impl PackFingerprint {
+    /// Current fingerprint of the pack in `dir`, the config is only read when its time changed
+    pub fn read(dir: &Path, previous: Option<&PackFingerprint>) -> Option<Self> {
+        let config_path = dir.join("config.json");
+        let dir_modified = modified_millis(dir)?;
+        let config_modified = modified_millis(&config_path)?;
+
+        let (config_hash, audio_files) = match previous {
+            Some(previous) if previous.config_modified == config_modified => {
+                let files = previous.audio.iter().map(|stamp| stamp.file.clone()).collect();
+                (previous.config_hash.clone(), files)
+            }
+            _ => {
+                let config = std::fs::read(&config_path).ok()?;
+                (crate::utils::soundpack_catalog::sha256_hex(&config), referenced_audio_files(&config))
+            }
+        };
+        // Missing files stamp as zero, adding one later still changes the fingerprint
+        let audio = audio_files
+            .into_iter()
+            .map(|file| {
+                let path = dir.join(&file);
+                AudioStamp {
+                    modified: modified_millis(&path).unwrap_or(0),
+                    size: std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0),
+                    file,
+                }
+            })
+            .collect();
+        Some(Self { dir_modified, config_modified, config_hash, audio })
+    }
+
+    /// Same pack contents, even if the config was touched
+    fn matches(&self, previous: &PackFingerprint) -> bool {
+        self.dir_modified == previous.dir_modified &&
+            self.config_hash == previous.config_hash &&
+            self.audio == previous.audio
+    }
+}
+
+/// How far the running scan is, None when no scan is running
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanProgress {
+    pub scanned: usize,
+    pub total: usize,
+}
+
+static SCAN_PROGRESS: Mutex<Option<ScanProgress>> = Mutex::new(None);
+
+/// Progress of the soundpack scan in progress, polled by the UI to show a spinner
+pub fn scan_progress() -> Option<ScanProgress> {
+    SCAN_PROGRESS.lock().ok().and_then(|progress| *progress)
+}
+
+fn set_scan_progress(progress: Option<ScanProgress>) {
+    if let Ok(mut current) = SCAN_PROGRESS.lock() {
+        *current = progress;
+    }
+}
+
+/// A pack folder found while listing the soundpacks directories
+struct ScanJob {
+    id: String,
+    name: String,
+    soundpack_type: SoundpackType,
+    dir: PathBuf,
 }
 
 impl SoundpackCache {
@@ -142,13 +326,14 @@ impl SoundpackCache {
     }
 
     pub fn load() -> Self {
-        let cache_file = Self::cache_file();
+        Self::load_from(Path::new(&Self::cache_file()))
+    }
+
+    fn load_from(cache_file: &Path) -> Self {
         // Load metadata cache using data utilities
-        let cache = match
-            data::load_json_from_file::<SoundpackCache>(std::path::Path::new(&cache_file))
-        {
+        let mut cache = match data::load_json_from_file::<SoundpackCache>(cache_file) {
             Ok(cache) => {
-                println!(
+                debug_print!(
                     "📦 Loaded soundpack metadata cache with {} entries",
                     cache.soundpacks.len()
                 );
@@ -160,6 +345,18 @@ impl SoundpackCache {
             }
         };
 
+        // Older caches keep their entries (and doctor reports) until the next scan, which
+        // revalidates every pack once since none has a fingerprint yet
+        if cache.cache_version != CACHE_VERSION {
+            debug_print!(
+                "📦 Migrating soundpack cache from version {} to {}",
+                cache.cache_version,
+                CACHE_VERSION
+            );
+            cache.fingerprints.clear();
+            cache.cache_version = CACHE_VERSION;
+        }
+
         // Auto-refresh on startup has been disabled to improve startup performance
         // Cache will be refreshed manually via UI or when importing soundpacks
         cache
@@ -168,8 +365,9 @@ impl SoundpackCache {
         Self {
             soundpacks: HashMap::new(),
             last_scan: 0,
-            cache_version: 4, // Current version with error tracking support
+            cache_version: CACHE_VERSION,
             count: SoundpackCount::default(),
+            fingerprints: HashMap::new(),
         }
     }
 
@@ -186,7 +384,7 @@ impl SoundpackCache {
 
         match data::save_json_to_file(self, std::path::Path::new(&cache_file)) {
             Ok(_) =>
-                println!(
+                debug_print!(
                     "💾 Saved soundpack metadata cache with {} entries",
                     self.soundpacks.len()
                 ),
@@ -196,28 +394,92 @@ impl SoundpackCache {
 
     // Add or update soundpack metadata
     pub fn add_soundpack(&mut self, metadata: SoundpackMetadata) {
+        // Added outside of a scan, so the next scan validates it again
+        self.fingerprints.remove(&metadata.id);
         self.soundpacks.insert(metadata.id.clone(), metadata);
-    } // Refresh cache by scanning soundpacks directory
+    } // Refresh cache by scanning soundpacks directories, only packs that changed are revalidated
     pub fn refresh_from_directory(&mut self) {
-        println!("📂 Scanning soundpacks directories...");
-
-        self.soundpacks.clear(); // Clear all existing entries
-
-        // Scan built-in soundpacks (app root)
-        let builtin_soundpacks_dir = paths::soundpacks::get_builtin_soundpacks_dir()
-            .to_string_lossy()
-            .to_string();
-        println!("📂 Scanning built-in soundpacks in: {}", builtin_soundpacks_dir);
-        self.scan_soundpack_type(&builtin_soundpacks_dir, "keyboard", false);
-        self.scan_soundpack_type(&builtin_soundpacks_dir, "mouse", true);
-
-        // Scan custom soundpacks (system app data)
-        let custom_soundpacks_dir = paths::soundpacks::get_custom_soundpacks_dir()
-            .to_string_lossy()
-            .to_string();
-        println!("📂 Scanning custom soundpacks in: {}", custom_soundpacks_dir);
-        self.scan_soundpack_type(&custom_soundpacks_dir, "keyboard", false);
-        self.scan_soundpack_type(&custom_soundpacks_dir, "mouse", true);
+        debug_print!("📂 Scanning soundpacks directories...");
+
+        // Built-in soundpacks (app root) first, so custom ones with the same ID replace them
+        let builtin_soundpacks_dir = paths::soundpacks::get_builtin_soundpacks_dir();
+        let custom_soundpacks_dir = paths::soundpacks::get_custom_soundpacks_dir();
+        let mut jobs: Vec<ScanJob> = Vec::new();
+        for soundpacks_dir in [&builtin_soundpacks_dir, &custom_soundpacks_dir] {
+            debug_print!("📂 Scanning soundpacks in: {}", soundpacks_dir.display());
+            for soundpack_type in SoundpackType::ALL {
+                for job in Self::list_soundpack_type(soundpacks_dir, soundpack_type) {
+                    jobs.retain(|existing| existing.id != job.id);
+                    jobs.push(job);
+                }
+            }
+        }
+
+        let previous = std::mem::take(&mut self.soundpacks);
+        let previous_fingerprints = std::mem::take(&mut self.fingerprints);
+        set_scan_progress(Some(ScanProgress { scanned: 0, total: jobs.len() }));
+
+        // Validation reads and may convert each config.json, spread it over all cores
+        let next_job = AtomicUsize::new(0);
+        let scanned = AtomicUsize::new(0);
+        let workers = std::thread
+            ::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+            .min(jobs.len())
+            .max(1);
+        let results: Vec<(SoundpackMetadata, Option<PackFingerprint>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        while let Some(job) = jobs.get(next_job.fetch_add(1, Ordering::Relaxed)) {
+                            results.push(
+                                Self::scan_soundpack(
+                                    job,
+                                    previous.get(&job.id),
+                                    previous_fingerprints.get(&job.id)
+                                )
+                            );
+                            set_scan_progress(
+                                Some(ScanProgress {
+                                    scanned: scanned.fetch_add(1, Ordering::Relaxed) + 1,
+                                    total: jobs.len(),
+                                })
+                            );
+                        }
+                        results
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap_or_default())
+                .collect()
+        });
+
+        let mut rescanned = 0;
+        for (metadata, fingerprint) in results {
+            let id = metadata.id.clone();
+            match fingerprint {
+                Some(fingerprint) => {
+                    let unchanged = previous_fingerprints
+                        .get(&id)
+                        .is_some_and(|previous| fingerprint.matches(previous));
+                    if !unchanged {
+                        rescanned += 1;
+                    }
+                    self.fingerprints.insert(id.clone(), fingerprint);
+                }
+                None => {
+                    rescanned += 1;
+                }
+            }
+            // Unchanged packs reuse their old entry with its doctor report, changed ones start
+            // without one since the audio it describes may be different
+            self.soundpacks.insert(id, metadata);
+        }
+        set_scan_progress(None);
 
         // Update count based on loaded soundpacks
         self.update_count();
@@ -228,13 +490,18 @@ impl SoundpackCache {
             .unwrap_or_default()
             .as_secs();
 
-        println!("📦 Loaded {} soundpacks metadata", self.soundpacks.len());
+        debug_print!(
+            "📦 Loaded {} soundpacks metadata ({} revalidated)",
+            self.soundpacks.len(),
+            rescanned
+        );
     }
 
     // Update count based on current soundpacks in cache
     pub fn update_count(&mut self) {
         let mut keyboard_count = 0;
         let mut mouse_count = 0;
+        let mut gamepad_count = 0;
 
         for metadata in self.soundpacks.values() {
             match metadata.soundpack_type {
@@ -244,85 +511,225 @@ impl SoundpackCache {
                 SoundpackType::Mouse => {
                     mouse_count += 1;
                 }
+                SoundpackType::Gamepad => {
+                    gamepad_count += 1;
+                }
             }
         }
         self.count.keyboard = keyboard_count;
         self.count.mouse = mouse_count;
+        self.count.gamepad = gamepad_count;
 
-        println!("📊 Updated count: {} keyboard, {} mouse soundpacks", keyboard_count, mouse_count);
+        debug_print!(
+            "📊 Updated count: {} keyboard, {} mouse, {} gamepad soundpacks",
+            keyboard_count,
+            mouse_count,
+            gamepad_count
+        );
     }
 
-    fn scan_soundpack_type(&mut self, soundpacks_dir: &str, soundpack_type: &str, is_mouse: bool) {
-        let type_dir = std::path::Path::new(soundpacks_dir).join(soundpack_type);
-        println!(
+    fn list_soundpack_type(soundpacks_dir: &Path, pack_type: SoundpackType) -> Vec<ScanJob> {
+        let soundpack_type = pack_type.folder_name();
+        let type_dir = soundpacks_dir.join(soundpack_type);
+        debug_print!(
             "📂 [CACHE DEBUG] Scanning {} soundpacks in: {}",
             soundpack_type,
             type_dir.display()
         );
 
-        if type_dir.exists() {
-            if let Ok(entries) = std::fs::read_dir(&type_dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    if let Some(soundpack_name) = entry.file_name().to_str() {
-                        let full_soundpack_id = format!("{}/{}", soundpack_type, soundpack_name);
-                        println!("🔍 [CACHE DEBUG] Processing soundpack: {}", full_soundpack_id);
-
-                        match soundpack::load_soundpack_metadata(&full_soundpack_id) {
-                            Ok(metadata) => {
-                                println!("✅ [CACHE DEBUG] Successfully loaded metadata for: {}", full_soundpack_id);
-                                self.soundpacks.insert(full_soundpack_id, metadata);
-                            }
-                            Err(e) => {
-                                println!(
-                                    "❌ [CACHE DEBUG] Failed to load {} metadata for {}: {}",
-                                    soundpack_type,
-                                    soundpack_name,
-                                    e
-                                );
-                                self.insert_error_metadata(
-                                    &full_soundpack_id,
-                                    soundpack_name,
-                                    e,
-                                    is_mouse
-                                );
-                            }
-                        }
-                    }
-                }
-            } else {
-                println!("⚠️ [CACHE DEBUG] Failed to read directory: {}", type_dir.display());
+        if !type_dir.exists() {
+            debug_print!("⚠️ [CACHE DEBUG] Directory does not exist: {}", type_dir.display());
+            return Vec::new();
+        }
+        let Ok(entries) = std::fs::read_dir(&type_dir) else {
+            debug_print!("⚠️ [CACHE DEBUG] Failed to read directory: {}", type_dir.display());
+            return Vec::new();
+        };
+        entries
+            .filter_map(|e| e.ok())
+            .filter_map(|entry| {
+                let soundpack_name = entry.file_name().to_str()?.to_string();
+                Some(ScanJob {
+                    id: format!("{}/{}", soundpack_type, soundpack_name),
+                    name: soundpack_name,
+                    soundpack_type: pack_type,
+                    dir: entry.path(),
+                })
+            })
+            .collect()
+    }
+
+    /// Metadata for one pack, reused from the previous scan when its fingerprint still matches
+    fn scan_soundpack(
+        job: &ScanJob,
+        previous: Option<&SoundpackMetadata>,
+        previous_fingerprint: Option<&PackFingerprint>
+    ) -> (SoundpackMetadata, Option<PackFingerprint>) {
+        let fingerprint = PackFingerprint::read(&job.dir, previous_fingerprint);
+        match (&fingerprint, previous_fingerprint, previous) {
+            (Some(fingerprint), Some(old), Some(metadata)) if fingerprint.matches(old) => {
+                return (metadata.clone(), Some(fingerprint.clone()));
+            }
+            _ => {}
+        }
+
+        debug_print!("🔍 [CACHE DEBUG] Processing soundpack: {}", job.id);
+        match soundpack::load_soundpack_metadata(&job.id) {
+            Ok(metadata) => {
+                debug_print!("✅ [CACHE DEBUG] Successfully loaded metadata for: {}", job.id);
+                // Loading may convert config.json, fingerprint what is on disk now
+                (metadata, PackFingerprint::read(&job.dir, None))
+            }
+            Err(e) => {
+                debug_print!(
+                    "❌ [CACHE DEBUG] Failed to load {} metadata for {}: {}",
+                    job.soundpack_type.folder_name(),
+                    job.name,
+                    e
+                );
+                (Self::error_metadata(job, e), fingerprint)
             }
-        } else {
-            println!("⚠️ [CACHE DEBUG] Directory does not exist: {}", type_dir.display());
         }
     }
 
-    fn insert_error_metadata(
-        &mut self,
-        full_soundpack_id: &str,
-        soundpack_name: &str,
-        error: String,
-        is_mouse: bool
-    ) {
-        let soundpack_type = if is_mouse { SoundpackType::Mouse } else { SoundpackType::Keyboard };
-        let error_metadata = SoundpackMetadata {
-            id: full_soundpack_id.to_string(),
-            name: format!("Error: {}", soundpack_name),
+    fn error_metadata(job: &ScanJob, error: String) -> SoundpackMetadata {
+        SoundpackMetadata {
+            id: job.id.clone(),
+            name: format!("Error: {}", job.name),
             author: None,
             description: Some(format!("Failed to load: {}", error)),
             version: "unknown".to_string(),
             tags: vec!["error".to_string()],
             icon: None,
-            soundpack_type,
-            folder_path: full_soundpack_id.to_string(), // Use full_soundpack_id as folder path for error entries
+            soundpack_type: job.soundpack_type,
+            folder_path: job.id.clone(), // Use the ID as folder path for error entries
             last_modified: 0,
             last_accessed: 0,
             config_version: None,
             is_valid_v2: false,
             validation_status: "error".to_string(),
             can_be_converted: false,
+            definition_method: None,
             last_error: Some(error),
-        };
-        self.soundpacks.insert(full_soundpack_id.to_string(), error_metadata);
+            doctor: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{ Duration, SystemTime };
+    use uuid::Uuid;
+
+    fn write_pack(root: &Path, name: &str, config: &str) -> ScanJob {
+        let dir = root.join("keyboard").join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("config.json"), config).unwrap();
+        ScanJob {
+            id: format!("keyboard/{}", name),
+            name: name.to_string(),
+            soundpack_type: SoundpackType::Keyboard,
+            dir,
+        }
+    }
+
+    // Stands in for what the last scan stored, a real rescan would produce something else
+    fn cached_metadata(job: &ScanJob) -> SoundpackMetadata {
+        let mut metadata = SoundpackCache::error_metadata(job, String::new());
+        metadata.name = "From the last scan".to_string();
+        metadata
+    }
+
+    #[test]
+    fn unchanged_pack_reuses_metadata() {
+        let root = std::env::temp_dir().join(format!("mechaura-cache-test-{}", Uuid::new_v4()));
+        let job = write_pack(&root, "cached", r#"{"id":"cached"}"#);
+        let previous = cached_metadata(&job);
+        let fingerprint = PackFingerprint::read(&job.dir, None).unwrap();
+
+        let (metadata, new_fingerprint) = SoundpackCache::scan_soundpack(
+            &job,
+            Some(&previous),
+            Some(&fingerprint)
+        );
+        assert_eq!(metadata, previous);
+        assert_eq!(new_fingerprint, Some(fingerprint));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn touched_config_with_same_hash_is_not_revalidated() {
+        let root = std::env::temp_dir().join(format!("mechaura-cache-test-{}", Uuid::new_v4()));
+        let config = r#"{"id":"touched"}"#;
+        let job = write_pack(&root, "touched", config);
+        let previous = cached_metadata(&job);
+        let fingerprint = PackFingerprint::read(&job.dir, None).unwrap();
+
+        // Same content with a newer time, like a checkout or a copy
+        let config_path = job.dir.join("config.json");
+        std::fs::write(&config_path, config).unwrap();
+        std::fs::File
+            ::options()
+            .write(true)
+            .open(&config_path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        let touched = PackFingerprint::read(&job.dir, Some(&fingerprint)).unwrap();
+        assert_ne!(touched.config_modified, fingerprint.config_modified);
+        assert_eq!(touched.config_hash, fingerprint.config_hash);
+        let (metadata, _) = SoundpackCache::scan_soundpack(&job, Some(&previous), Some(&fingerprint));
+        assert_eq!(metadata, previous);
+
+        // Different content is picked up
+        std::fs::write(&config_path, r#"{"id":"touched","name":"Edited"}"#).unwrap();
+        let edited = PackFingerprint::read(&job.dir, Some(&touched)).unwrap();
+        assert!(!edited.matches(&touched));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn replaced_audio_changes_the_fingerprint() {
+        let root = std::env::temp_dir().join(format!("mechaura-cache-test-{}", Uuid::new_v4()));
+        let job = write_pack(
+            &root,
+            "audio",
+            r#"{"id":"audio","audio_file":"./sound.wav","definitions":{"KeyA":{"timing":[],"audio_file":"key.wav"}}}"#
+        );
+        std::fs::write(job.dir.join("sound.wav"), [0u8; 16]).unwrap();
+        let fingerprint = PackFingerprint::read(&job.dir, None).unwrap();
+        let files: Vec<&str> = fingerprint.audio
+            .iter()
+            .map(|stamp| stamp.file.as_str())
+            .collect();
+        assert_eq!(files, ["key.wav", "sound.wav"]);
+
+        // Same name, same folder time, different audio, so the pack and its doctor report are redone
+        std::fs::write(job.dir.join("sound.wav"), [0u8; 32]).unwrap();
+        let replaced = PackFingerprint::read(&job.dir, Some(&fingerprint)).unwrap();
+        assert_eq!(replaced.dir_modified, fingerprint.dir_modified);
+        assert!(!replaced.matches(&fingerprint));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn old_cache_version_clears_fingerprints() {
+        let root = std::env::temp_dir().join(format!("mechaura-cache-test-{}", Uuid::new_v4()));
+        let job = write_pack(&root, "old", r#"{"id":"old"}"#);
+        let mut cache = SoundpackCache::new();
+        cache.cache_version = 4;
+        cache.soundpacks.insert(job.id.clone(), cached_metadata(&job));
+        cache.fingerprints.insert(job.id.clone(), PackFingerprint::read(&job.dir, None).unwrap());
+        let cache_file = root.join("soundpack_cache.json");
+        std::fs::write(&cache_file, serde_json::to_string(&cache).unwrap()).unwrap();
+
+        let loaded = SoundpackCache::load_from(&cache_file);
+        assert_eq!(loaded.cache_version, CACHE_VERSION);
+        assert!(loaded.fingerprints.is_empty());
+        // Entries stay until the next scan revalidates them
+        assert!(loaded.soundpacks.contains_key(&job.id));
+        std::fs::remove_dir_all(&root).unwrap();
     }
 }
@@ -111,6 +111,22 @@ impl ThemesConfig {
 
         Ok(id)
     }
+    /// Add a shared theme under a new id, numbering its name when it is taken
+    pub fn import_theme(&mut self, theme: CustomThemeData) -> Result<String, String> {
+        let is_taken = |name: &str| {
+            self.custom_themes
+                .values()
+                .any(|existing| existing.name.to_lowercase() == name.to_lowercase())
+        };
+        let mut name = theme.name.trim().to_string();
+        if is_taken(&name) {
+            name = (2..)
+                .map(|n| format!("{} ({})", theme.name.trim(), n))
+                .find(|candidate| !is_taken(candidate))
+                .unwrap_or(name);
+        }
+        self.add_theme(name, theme.description, theme.css)
+    }
     pub fn update_theme(
         &mut self,
         id: &str,
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+#[cfg(target_os = "windows")]
 use crate::utils::constants::APP_NAME;
 
 #[cfg(target_os = "windows")]
@@ -6,9 +7,13 @@ use winreg::enums::*;
 #[cfg(target_os = "windows")]
 use winreg::RegKey;
 
-/// Get the current executable path
+/// Get the current executable path, for an AppImage the image file
 fn get_exe_path() -> Result<PathBuf, String> {
-    std::env::current_exe().map_err(|e| format!("Failed to get executable path: {}", e))
+    #[cfg(target_os = "linux")]
+    let exe_path = crate::utils::desktop_entry::launcher_path();
+    #[cfg(not(target_os = "linux"))]
+    let exe_path = std::env::current_exe();
+    exe_path.map_err(|e| format!("Failed to get executable path: {}", e))
 }
 
 /// Enable auto startup on Windows
@@ -81,27 +86,106 @@ pub fn is_auto_startup_enabled() -> bool {
     }
 }
 
+/// Enable auto startup on Linux through an XDG autostart entry
+#[cfg(target_os = "linux")]
+pub fn enable_auto_startup() -> Result<(), String> {
+    use crate::utils::desktop_entry;
+
+    let exe_path = get_exe_path()?;
+
+    // Check if we should start minimized
+    let config = crate::state::config::AppConfig::load();
+    let args: &[&str] = if config.start_minimized { &["--minimized"] } else { &[] };
+    let exec = desktop_entry::exec_line(&exe_path, args);
+    let content = desktop_entry::render(
+        &exec,
+        &[
+            ("Terminal", "false"),
+            ("X-GNOME-Autostart-enabled", "true"),
+        ]
+    );
+
+    let autostart_dir = desktop_entry::autostart_dir()?;
+    std::fs
+        ::create_dir_all(&autostart_dir)
+        .map_err(|e| format!("Failed to create {}: {}", autostart_dir.display(), e))?;
+    let entry_path = autostart_dir.join(desktop_entry::file_name());
+    std::fs
+        ::write(&entry_path, content)
+        .map_err(|e| format!("Failed to write {}: {}", entry_path.display(), e))?;
+
+    println!("✅ Auto startup enabled: {}", exec);
+    Ok(())
+}
+
+/// Disable auto startup on Linux
+#[cfg(target_os = "linux")]
+pub fn disable_auto_startup() -> Result<(), String> {
+    let entry_path = crate::utils::desktop_entry::autostart_dir()?.join(
+        crate::utils::desktop_entry::file_name()
+    );
+    match std::fs::remove_file(&entry_path) {
+        Ok(_) => {
+            println!("✅ Auto startup disabled");
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            // Entry doesn't exist, which is fine
+            println!("ℹ️ Auto startup was not enabled");
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to remove {}: {}", entry_path.display(), e)),
+    }
+}
+
+/// Check if our autostart entry exists, is not switched off and runs this executable
+#[cfg(target_os = "linux")]
+pub fn is_auto_startup_enabled() -> bool {
+    use crate::utils::desktop_entry;
+
+    let Ok(autostart_dir) = desktop_entry::autostart_dir() else {
+        return false;
+    };
+    let Ok(content) = std::fs::read_to_string(autostart_dir.join(desktop_entry::file_name())) else {
+        return false;
+    };
+
+    // Desktop environments switch entries off instead of deleting them
+    let switched_off =
+        desktop_entry::value(&content, "Hidden") == Some("true") ||
+        desktop_entry::value(&content, "X-GNOME-Autostart-enabled") == Some("false");
+    if switched_off {
+        return false;
+    }
+
+    let current_exe = get_exe_path().unwrap_or_default();
+    desktop_entry
+        ::value(&content, "Exec")
+        .is_some_and(|exec| PathBuf::from(desktop_entry::exec_program(exec)) == current_exe)
+}
+
 /// Set auto startup state (enable or disable)
 pub fn set_auto_startup(enable: bool) -> Result<(), String> {
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     {
         if enable { enable_auto_startup() } else { disable_auto_startup() }
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
     {
-        Err("Auto startup is only supported on Windows".to_string())
+        let _ = enable;
+        Err("Auto startup is not supported on this platform".to_string())
     }
 }
 
 /// Get current auto startup state
 pub fn get_auto_startup_state() -> bool {
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "windows", target_os = "linux"))]
     {
         is_auto_startup_enabled()
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
     {
         false
     }
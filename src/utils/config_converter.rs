@@ -1,3 +1,4 @@
+use crate::debug_print;
 use super::path;
 use serde_json::{ Map, Value };
 use std::collections::HashMap;
@@ -173,11 +174,11 @@ pub fn convert_v1_to_v2(
             }
         }
 
-        println!("🔧 Found {} unique audio files in V1 multi method", audio_files_ordered.len());
+        debug_print!("🔧 Found {} unique audio files in V1 multi method", audio_files_ordered.len());
 
         // Create a concatenated audio file name
         let concat_filename = "concatenated_audio.wav";
-        println!("🎵 Creating concatenated audio file: {}", concat_filename);
+        debug_print!("🎵 Creating concatenated audio file: {}", concat_filename);
 
         // Actually concatenate the audio files and get accurate timing
         let audio_file_info = match
@@ -189,7 +190,7 @@ pub fn convert_v1_to_v2(
         {
             Ok(timing_info) => timing_info,
             Err(e) => {
-                println!("❌ Failed to create concatenated audio file: {}", e);
+                debug_print!("❌ Failed to create concatenated audio file: {}", e);
                 return Err(format!("Audio concatenation failed: {}", e).into());
             }
         };
@@ -199,7 +200,7 @@ pub fn convert_v1_to_v2(
         // For V1 single method, use the main sound file
         let main_file = if let Some(sound) = config.get("sound") {
             if let Some(sound_str) = sound.as_str() {
-                println!("🎵 Using main audio file from V1 single method: {}", sound_str);
+                debug_print!("🎵 Using main audio file from V1 single method: {}", sound_str);
                 sound_str.to_string()
             } else {
                 return Err("Invalid sound field in V1 config".into());
@@ -227,7 +228,7 @@ pub fn convert_v1_to_v2(
             }
 
             if let Some(audio_file) = found_audio {
-                println!("🎵 Found audio file in directory: {}", audio_file);
+                debug_print!("🎵 Found audio file in directory: {}", audio_file);
                 audio_file
             } else {
                 return Err("No audio file found for single method conversion".into());
@@ -250,11 +251,11 @@ pub fn convert_v1_to_v2(
     let mut definitions = Map::new();
     if let Some(defines) = config.get("defines").and_then(|d| d.as_object()) {
         let key_mappings = create_iohook_to_web_key_mapping();
-        println!("🔧 Converting {} key definitions to new format (single method)", defines.len());
+        debug_print!("🔧 Converting {} key definitions to new format (single method)", defines.len());
         if v1_define_type == "multi" {
             // V1 multi method: defines contains IOHook code -> audio filename mappings
             // We need to create timing based on concatenated audio file offsets
-            println!("🔧 Processing V1 multi method defines");
+            debug_print!("🔧 Processing V1 multi method defines");
 
             for (iohook_code, value) in defines {
                 if let Ok(iohook_num) = iohook_code.parse::<u32>() {
@@ -272,12 +273,12 @@ pub fn convert_v1_to_v2(
 
                                     // Special debug for Enter key
                                     if key_name == "Enter" {
-                                        println!("🔍 [ENTER DEBUG] Key: {}", key_name);
-                                        println!("🔍 [ENTER DEBUG] IOHook code: {}", iohook_num);
-                                        println!("🔍 [ENTER DEBUG] Audio file: {}", audio_filename);
-                                        println!("🔍 [ENTER DEBUG] Offset: {}ms", offset);
-                                        println!("🔍 [ENTER DEBUG] Duration: {}ms", duration);
-                                        println!("🔍 [ENTER DEBUG] End time: {}ms", end_time);
+                                        debug_print!("🔍 [ENTER DEBUG] Key: {}", key_name);
+                                        debug_print!("🔍 [ENTER DEBUG] IOHook code: {}", iohook_num);
+                                        debug_print!("🔍 [ENTER DEBUG] Audio file: {}", audio_filename);
+                                        debug_print!("🔍 [ENTER DEBUG] Offset: {}ms", offset);
+                                        debug_print!("🔍 [ENTER DEBUG] Duration: {}ms", duration);
+                                        debug_print!("🔍 [ENTER DEBUG] End time: {}ms", end_time);
 
                                         // Check concatenated audio file duration
                                         let concat_path =
@@ -287,9 +288,9 @@ pub fn convert_v1_to_v2(
                                                 &concat_path
                                             )
                                         {
-                                            println!("🔍 [ENTER DEBUG] Concatenated audio duration: {}ms", concat_duration);
+                                            debug_print!("🔍 [ENTER DEBUG] Concatenated audio duration: {}ms", concat_duration);
                                             if end_time > concat_duration {
-                                                println!(
+                                                debug_print!(
                                                     "❌ [ENTER DEBUG] ERROR: End time ({}) > Concat duration ({})",
                                                     end_time,
                                                     concat_duration
@@ -314,7 +315,7 @@ pub fn convert_v1_to_v2(
                                     key_def.insert("timing".to_string(), Value::Array(timing));
 
                                     definitions.insert(key_name.clone(), Value::Object(key_def));
-                                    println!(
+                                    debug_print!(
                                         "   ✅ Key '{}' -> {} [offset: {}ms, end: {}ms]",
                                         key_name,
                                         audio_filename,
@@ -322,20 +323,20 @@ pub fn convert_v1_to_v2(
                                         end_time
                                     );
                                 } else {
-                                    println!("   ⚠️ No offset found for audio file: {}", audio_filename);
+                                    debug_print!("   ⚠️ No offset found for audio file: {}", audio_filename);
                                 }
                             } else {
-                                println!("   ⚠️ Key IOHook {} has empty/null audio file", iohook_code);
+                                debug_print!("   ⚠️ Key IOHook {} has empty/null audio file", iohook_code);
                             }
                         }
                     } else {
-                        println!("   ⚠️ No key mapping found for IOHook code: {}", iohook_code);
+                        debug_print!("   ⚠️ No key mapping found for IOHook code: {}", iohook_code);
                     }
                 }
             }
         } else {
             // V1 single method: defines contains IOHook code -> timing array mappings
-            println!("🔧 Processing V1 single method defines");
+            debug_print!("🔧 Processing V1 single method defines");
 
             for (iohook_code, value) in defines {
                 if let Ok(iohook_num) = iohook_code.parse::<u32>() {
@@ -365,10 +366,10 @@ pub fn convert_v1_to_v2(
                                 key_def.insert("timing".to_string(), Value::Array(timing));
 
                                 definitions.insert(key_name.clone(), Value::Object(key_def));
-                                println!("   ✅ Key '{}' -> timing [{}, {}]", key_name, start, end);
+                                debug_print!("   ✅ Key '{}' -> timing [{}, {}]", key_name, start, end);
                             }
                         } else {
-                            println!("   ⚠️ Key '{}' has invalid timing format", key_name);
+                            debug_print!("   ⚠️ Key '{}' has invalid timing format", key_name);
                         }
                     }
                 }
@@ -382,8 +383,8 @@ pub fn convert_v1_to_v2(
     let output_json = serde_json::to_string_pretty(&converted_config)?;
     std::fs::write(output_path, output_json)?;
 
-    println!("✅ Successfully converted V1 to V2 config");
-    println!("📁 Output written to: {}", output_path);
+    debug_print!("✅ Successfully converted V1 to V2 config");
+    debug_print!("📁 Output written to: {}", output_path);
 
     Ok(())
 }
@@ -394,7 +395,7 @@ pub fn convert_v2_multi_to_single(
     config_path: &str,
     soundpack_dir: &str
 ) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔄 Converting V2 multi method to single method...");
+    debug_print!("🔄 Converting V2 multi method to single method...");
 
     // Read the existing V2 config
     let content = std::fs::read_to_string(config_path)?;
@@ -403,12 +404,12 @@ pub fn convert_v2_multi_to_single(
     // Check if this is already single method
     if let Some(definition_method) = config.get("definition_method").and_then(|v| v.as_str()) {
         if definition_method == "single" {
-            println!("✅ Already using single method, no conversion needed");
+            debug_print!("✅ Already using single method, no conversion needed");
             return Ok(());
         }
     }
 
-    println!("🔧 Converting from multi method to single method");
+    debug_print!("🔧 Converting from multi method to single method");
 
     // Analyze audio files used in definitions to find the most common one
     let mut audio_file_usage = std::collections::HashMap::new();
@@ -417,7 +418,7 @@ pub fn convert_v2_multi_to_single(
             if let Some(key_obj) = key_def.as_object() {
                 if let Some(audio_file) = key_obj.get("audio_file").and_then(|v| v.as_str()) {
                     *audio_file_usage.entry(audio_file.to_string()).or_insert(0) += 1;
-                    println!("🔍 Key '{}' uses audio file: {}", key_name, audio_file);
+                    debug_print!("🔍 Key '{}' uses audio file: {}", key_name, audio_file);
                 }
             }
         }
@@ -426,7 +427,7 @@ pub fn convert_v2_multi_to_single(
     let main_audio_file = if
         let Some((audio_file, count)) = audio_file_usage.iter().max_by_key(|(_, count)| *count)
     {
-        println!("🎵 Most used audio file: {} (used by {} keys)", audio_file, count);
+        debug_print!("🎵 Most used audio file: {} (used by {} keys)", audio_file, count);
         audio_file.clone()
     } else {
         // Fallback: find any audio file in the directory
@@ -453,7 +454,7 @@ pub fn convert_v2_multi_to_single(
         found_audio.ok_or("No audio file found in soundpack directory")?
     };
 
-    println!("🎵 Using main audio file for single method: {}", main_audio_file);
+    debug_print!("🎵 Using main audio file for single method: {}", main_audio_file);
 
     // Update config to single method
     config
@@ -489,7 +490,7 @@ pub fn convert_v2_multi_to_single(
                     // This key uses the main audio file, keep its timing
                     if let Some(timing) = key_obj.get("timing") {
                         new_key_def.insert("timing".to_string(), timing.clone());
-                        println!("✅ Key '{}' kept timing (uses main audio file)", key_name);
+                        debug_print!("✅ Key '{}' kept timing (uses main audio file)", key_name);
                     } else {
                         // Create default timing for the whole audio file
                         let audio_path = format!("{}/{}", soundpack_dir, main_audio_file);
@@ -504,13 +505,13 @@ pub fn convert_v2_multi_to_single(
                             )
                         ];
                         new_key_def.insert("timing".to_string(), Value::Array(timing));
-                        println!("⚠️ Key '{}' got default timing (no timing specified)", key_name);
+                        debug_print!("⚠️ Key '{}' got default timing (no timing specified)", key_name);
                     }
 
                     new_definitions.insert(key_name, Value::Object(new_key_def));
                 } else if !key_audio_file.is_empty() {
                     // This key uses a different audio file, we'll skip it in single method
-                    println!(
+                    debug_print!(
                         "⚠️ Key '{}' uses different audio file '{}', skipping in single method conversion",
                         key_name,
                         key_audio_file
@@ -529,7 +530,7 @@ pub fn convert_v2_multi_to_single(
                         )
                     ];
                     new_key_def.insert("timing".to_string(), Value::Array(timing));
-                    println!("⚠️ Key '{}' got default timing (no audio_file specified)", key_name);
+                    debug_print!("⚠️ Key '{}' got default timing (no audio_file specified)", key_name);
                     new_definitions.insert(key_name, Value::Object(new_key_def));
                 }
             }
@@ -545,7 +546,7 @@ pub fn convert_v2_multi_to_single(
     let output_json = serde_json::to_string_pretty(&config)?;
     std::fs::write(config_path, output_json)?;
 
-    println!("✅ Successfully converted to single method");
+    debug_print!("✅ Successfully converted to single method");
     Ok(())
 }
 
@@ -555,7 +556,7 @@ fn concatenate_audio_files(
     soundpack_dir: &str,
     output_filename: &str
 ) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔧 Concatenating {} audio files...", audio_files.len());
+    debug_print!("🔧 Concatenating {} audio files...", audio_files.len());
 
     let mut all_samples = Vec::new();
     let mut sample_rate = 44100u32; // Default sample rate
@@ -563,10 +564,10 @@ fn concatenate_audio_files(
 
     for (i, (filename, _duration)) in audio_files.iter().enumerate() {
         let file_path = format!("{}/{}", soundpack_dir, filename);
-        println!("   📁 Loading audio file {}/{}: {}", i + 1, audio_files.len(), filename);
+        debug_print!("   📁 Loading audio file {}/{}: {}", i + 1, audio_files.len(), filename);
 
         if !Path::new(&file_path).exists() {
-            println!("   ⚠️ Audio file not found, skipping: {}", file_path);
+            debug_print!("   ⚠️ Audio file not found, skipping: {}", file_path);
             continue;
         }
 
@@ -577,7 +578,7 @@ fn concatenate_audio_files(
                 if i == 0 {
                     sample_rate = file_sample_rate;
                     channels = file_channels;
-                    println!("   🎵 Using format: {}Hz, {} channels", sample_rate, channels);
+                    debug_print!("   🎵 Using format: {}Hz, {} channels", sample_rate, channels);
                 }
 
                 // Convert to target format if needed
@@ -585,7 +586,7 @@ fn concatenate_audio_files(
                     file_sample_rate != sample_rate ||
                     file_channels != channels
                 {
-                    println!(
+                    debug_print!(
                         "   🔄 Converting from {}Hz {} channels to {}Hz {} channels",
                         file_sample_rate,
                         file_channels,
@@ -609,15 +610,15 @@ fn concatenate_audio_files(
                         ((converted_samples.len() as f64) /
                             ((sample_rate as f64) * (channels as f64))) *
                         1000.0;
-                    println!("🔍 [ENTER CONCAT DEBUG] File: {}", filename);
-                    println!("🔍 [ENTER CONCAT DEBUG] Samples: {}", converted_samples.len());
-                    println!("🔍 [ENTER CONCAT DEBUG] Sample rate: {}Hz", sample_rate);
-                    println!("🔍 [ENTER CONCAT DEBUG] Channels: {}", channels);
-                    println!(
+                    debug_print!("🔍 [ENTER CONCAT DEBUG] File: {}", filename);
+                    debug_print!("🔍 [ENTER CONCAT DEBUG] Samples: {}", converted_samples.len());
+                    debug_print!("🔍 [ENTER CONCAT DEBUG] Sample rate: {}Hz", sample_rate);
+                    debug_print!("🔍 [ENTER CONCAT DEBUG] Channels: {}", channels);
+                    debug_print!(
                         "🔍 [ENTER CONCAT DEBUG] Actual duration: {:.2}ms",
                         actual_duration_ms
                     );
-                    println!(
+                    debug_print!(
                         "🔍 [ENTER CONCAT DEBUG] Current position in concat: {:.2}ms",
                         ((all_samples.len() as f64) / ((sample_rate as f64) * (channels as f64))) *
                             1000.0
@@ -625,10 +626,10 @@ fn concatenate_audio_files(
                 }
 
                 all_samples.extend(&converted_samples);
-                println!("   ✅ Added {} samples from {}", converted_samples.len(), filename);
+                debug_print!("   ✅ Added {} samples from {}", converted_samples.len(), filename);
             }
             Err(e) => {
-                println!("   ❌ Failed to load {}: {}", filename, e);
+                debug_print!("   ❌ Failed to load {}: {}", filename, e);
                 // Continue with other files
             }
         }
@@ -643,11 +644,11 @@ fn concatenate_audio_files(
     let final_duration_ms =
         ((all_samples.len() as f64) / ((sample_rate as f64) * (channels as f64))) * 1000.0;
 
-    println!("✅ Successfully concatenated audio to: {}", output_path);
-    println!("🎵 Total samples: {}, Duration: {:.2}ms", all_samples.len(), final_duration_ms);
+    debug_print!("✅ Successfully concatenated audio to: {}", output_path);
+    debug_print!("🎵 Total samples: {}, Duration: {:.2}ms", all_samples.len(), final_duration_ms);
 
     // Special debug output for comparison
-    println!("🔍 [CONCAT FINAL DEBUG] Final concatenated duration: {:.2}ms", final_duration_ms);
+    debug_print!("🔍 [CONCAT FINAL DEBUG] Final concatenated duration: {:.2}ms", final_duration_ms);
 
     Ok(())
 }
@@ -659,7 +660,7 @@ fn concatenate_audio_files_with_timing(
     soundpack_dir: &str,
     output_filename: &str
 ) -> Result<std::collections::HashMap<String, (f64, f64)>, Box<dyn std::error::Error>> {
-    println!("🔧 Concatenating {} audio files with timing...", audio_files.len());
+    debug_print!("🔧 Concatenating {} audio files with timing...", audio_files.len());
 
     let mut all_samples = Vec::new();
     let mut sample_rate = 44100u32; // Default sample rate
@@ -668,10 +669,10 @@ fn concatenate_audio_files_with_timing(
 
     for (i, filename) in audio_files.iter().enumerate() {
         let file_path = format!("{}/{}", soundpack_dir, filename);
-        println!("   📁 Loading audio file {}/{}: {}", i + 1, audio_files.len(), filename);
+        debug_print!("   📁 Loading audio file {}/{}: {}", i + 1, audio_files.len(), filename);
 
         if !Path::new(&file_path).exists() {
-            println!("   ⚠️ Audio file not found, skipping: {}", file_path);
+            debug_print!("   ⚠️ Audio file not found, skipping: {}", file_path);
             continue;
         }
 
@@ -686,7 +687,7 @@ fn concatenate_audio_files_with_timing(
                 if i == 0 {
                     sample_rate = file_sample_rate;
                     channels = file_channels;
-                    println!("   🎵 Using format: {}Hz, {} channels", sample_rate, channels);
+                    debug_print!("   🎵 Using format: {}Hz, {} channels", sample_rate, channels);
                     // Recalculate offset for first file with correct sample rate
                     let corrected_offset =
                         ((all_samples.len() as f64) / ((sample_rate as f64) * (channels as f64))) *
@@ -699,7 +700,7 @@ fn concatenate_audio_files_with_timing(
                     file_sample_rate != sample_rate ||
                     file_channels != channels
                 {
-                    println!(
+                    debug_print!(
                         "   🔄 Converting from {}Hz {} channels to {}Hz {} channels",
                         file_sample_rate,
                         file_channels,
@@ -728,18 +729,18 @@ fn concatenate_audio_files_with_timing(
 
                 // Special debug for Enter audio file
                 if filename == "SPMEnter.wav" {
-                    println!("🔍 [ENTER TIMING DEBUG] File: {}", filename);
-                    println!("🔍 [ENTER TIMING DEBUG] Offset: {:.2}ms", current_offset_ms);
-                    println!("🔍 [ENTER TIMING DEBUG] Duration: {:.2}ms", actual_duration_ms);
-                    println!(
+                    debug_print!("🔍 [ENTER TIMING DEBUG] File: {}", filename);
+                    debug_print!("🔍 [ENTER TIMING DEBUG] Offset: {:.2}ms", current_offset_ms);
+                    debug_print!("🔍 [ENTER TIMING DEBUG] Duration: {:.2}ms", actual_duration_ms);
+                    debug_print!(
                         "🔍 [ENTER TIMING DEBUG] End time: {:.2}ms",
                         current_offset_ms + actual_duration_ms
                     );
-                    println!("🔍 [ENTER TIMING DEBUG] Samples: {}", converted_samples.len());
+                    debug_print!("🔍 [ENTER TIMING DEBUG] Samples: {}", converted_samples.len());
                 }
 
                 all_samples.extend(&converted_samples);
-                println!(
+                debug_print!(
                     "   ✅ Added {} samples from {} (offset: {:.2}ms, duration: {:.2}ms)",
                     converted_samples.len(),
                     filename,
@@ -748,7 +749,7 @@ fn concatenate_audio_files_with_timing(
                 );
             }
             Err(e) => {
-                println!("   ❌ Failed to load {}: {}", filename, e);
+                debug_print!("   ❌ Failed to load {}: {}", filename, e);
                 // Continue with other files
             }
         }
@@ -765,22 +766,22 @@ fn concatenate_audio_files_with_timing(
     let final_duration_ms =
         ((all_samples.len() as f64) / ((sample_rate as f64) * (channels as f64))) * 1000.0;
 
-    println!("✅ Successfully concatenated audio to: {}", output_path);
-    println!("🎵 Total samples: {}, Final duration: {:.2}ms", all_samples.len(), final_duration_ms);
+    debug_print!("✅ Successfully concatenated audio to: {}", output_path);
+    debug_print!("🎵 Total samples: {}, Final duration: {:.2}ms", all_samples.len(), final_duration_ms);
 
     // Debug output for Enter file timing
     if let Some((offset, duration)) = timing_info.get("SPMEnter.wav") {
-        println!(
+        debug_print!(
             "🔍 [FINAL TIMING DEBUG] SPMEnter.wav: offset={:.2}ms, duration={:.2}ms, end={:.2}ms",
             offset,
             duration,
             offset + duration
         );
-        println!("🔍 [FINAL TIMING DEBUG] Concatenated total: {:.2}ms", final_duration_ms);
+        debug_print!("🔍 [FINAL TIMING DEBUG] Concatenated total: {:.2}ms", final_duration_ms);
         if offset + duration > final_duration_ms {
-            println!("❌ [FINAL TIMING DEBUG] ERROR: End time exceeds total duration!");
+            debug_print!("❌ [FINAL TIMING DEBUG] ERROR: End time exceeds total duration!");
         } else {
-            println!("✅ [FINAL TIMING DEBUG] Timing looks correct!");
+            debug_print!("✅ [FINAL TIMING DEBUG] Timing looks correct!");
         }
     }
 
@@ -788,7 +789,7 @@ fn concatenate_audio_files_with_timing(
 }
 
 /// Load audio file and return samples
-fn load_audio_file_samples(
+pub(crate) fn load_audio_file_samples(
     file_path: &str
 ) -> Result<(Vec<f32>, u16, u32), Box<dyn std::error::Error>> {
     use symphonia::core::formats::FormatOptions;
@@ -933,7 +934,7 @@ fn save_audio_file(
 
 /// Create comprehensive IOHook to Web API key mapping
 /// Supports all platforms (Windows, Linux, macOS)
-fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
+pub(crate) fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
     let mut mapping = HashMap::new();
 
     // Basic keys (VC_* constants from IOHook)
@@ -1046,7 +1047,7 @@ fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
     mapping.insert(115, "Lang1".to_string()); // VC_UNDERSCORE = 0x0073
     mapping.insert(119, "Lang2".to_string()); // VC_FURIGANA = 0x0077
     mapping.insert(121, "KanaMode".to_string()); // VC_KANJI = 0x0079
-    mapping.insert(123, "HiraganaKatakana".to_string()); // VC_HIRAGANA = 0x007B
+    mapping.insert(123, "Lang4".to_string()); // VC_HIRAGANA = 0x007B
     mapping.insert(125, "IntlYen".to_string()); // VC_YEN = 0x007D
     mapping.insert(126, "NumpadComma".to_string()); // VC_KP_COMMA = 0x007E
 
@@ -1055,7 +1056,7 @@ fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
     mapping.insert(3637, "NumpadDivide".to_string()); // VC_KP_DIVIDE = 0x0E35
     mapping.insert(3612, "NumpadEnter".to_string()); // VC_KP_ENTER = 0x0E1C
     mapping.insert(3597, "ControlRight".to_string()); // VC_CONTROL_R = 0x0E1D
-    mapping.insert(3645, "NumpadEquals".to_string()); // VC_KP_EQUALS = 0x0E0D    // Navigation cluster - using CORRECT 0xE0xx values (fixed from incorrect mapping)
+    mapping.insert(3645, "NumpadEqual".to_string()); // VC_KP_EQUALS = 0x0E0D    // Navigation cluster - using CORRECT 0xE0xx values (fixed from incorrect mapping)
     mapping.insert(57399, "PrintScreen".to_string()); // VC_PRINTSCREEN = 0xE037 = 57399
     mapping.insert(58437, "Pause".to_string()); // VC_PAUSE = 0xE045 = 57413 (keeping old for compatibility)
     mapping.insert(57415, "Home".to_string()); // VC_HOME = 0xE047 = 57415
@@ -1087,7 +1088,6 @@ fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
     mapping.insert(57392, "AudioVolumeUp".to_string()); // VC_VOLUME_UP = 0xE030
     mapping.insert(57394, "BrowserHome".to_string()); // VC_BROWSER_HOME = 0xE032
     mapping.insert(57404, "LaunchApp1".to_string()); // VC_APP_MUSIC = 0xE03C
-    mapping.insert(57444, "LaunchApp3".to_string()); // VC_APP_PICTURES = 0xE064
     mapping.insert(57445, "BrowserSearch".to_string()); // VC_BROWSER_SEARCH = 0xE065
     mapping.insert(57446, "BrowserFavorites".to_string()); // VC_BROWSER_FAVORITES = 0xE066
     mapping.insert(57447, "BrowserRefresh".to_string()); // VC_BROWSER_REFRESH = 0xE067
@@ -1101,7 +1101,7 @@ fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
     // Some systems may report different keycode values for extended keys
 
     // Clear key and additional special keys
-    mapping.insert(58444, "Clear".to_string()); // VC_CLEAR = 0xE04C (alternate)
+    mapping.insert(58444, "Numpad5".to_string()); // VC_CLEAR = 0xE04C (numpad 5 with NumLock off)
     mapping.insert(58470, "IntlBackslash".to_string()); // VC_LESSER_GREATER = 0xE046
 
     // Legacy compatibility mappings for V1 configs and alternative implementations
@@ -1139,13 +1139,11 @@ fn create_iohook_to_web_key_mapping() -> HashMap<u32, String> {
     // Additional platform-specific keycodes that might appear
     mapping.insert(94, "IntlBackslash".to_string()); // Less/Greater key on some keyboards
     mapping.insert(95, "Fn".to_string()); // Function key modifier
-    mapping.insert(96, "Clear".to_string()); // Clear key on some keyboards
 
     // Handle potential Sun keyboard extensions (rarely used but in IOHook)
     mapping.insert(65397, "Help".to_string()); // VC_SUN_HELP = 0xFF75
     mapping.insert(65398, "Props".to_string()); // VC_SUN_PROPS = 0xFF76
-    mapping.insert(65399, "Front".to_string()); // VC_SUN_FRONT = 0xFF77
-    mapping.insert(65400, "Stop".to_string()); // VC_SUN_STOP = 0xFF78
+    mapping.insert(65400, "BrowserStop".to_string()); // VC_SUN_STOP = 0xFF78
     mapping.insert(65401, "Again".to_string()); // VC_SUN_AGAIN = 0xFF79
     mapping.insert(65402, "Undo".to_string()); // VC_SUN_UNDO = 0xFF7A
     mapping.insert(65403, "Cut".to_string()); // VC_SUN_CUT = 0xFF7B
@@ -29,7 +29,13 @@ pub fn save_json_to_file<T>(data: &T, file_path: &Path) -> Result<(), String> wh
         ::to_string_pretty(data)
         .map_err(|e| format!("Failed to serialize data: {}", e))?;
 
-    fs::write(file_path, contents).map_err(|e|
+    // Write next to the file and rename it over, readers never see a half-written file
+    let temp_path = file_path.with_extension(format!("tmp-{}", uuid::Uuid::new_v4()));
+    fs::write(&temp_path, contents).map_err(|e|
+        format!("Failed to write file '{}': {}", temp_path.display(), e)
+    )?;
+    fs::rename(&temp_path, file_path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
         format!("Failed to write file '{}': {}", file_path.display(), e)
-    )
+    })
 }
@@ -0,0 +1,138 @@
+/// XDG desktop entries for the `mechaura://` handler and autostart on Linux
+///
+/// Both are plain `.desktop` files: one in `$XDG_DATA_HOME/applications` that claims the
+/// `x-scheme-handler/mechaura` MIME type, one in `$XDG_CONFIG_HOME/autostart`.
+use crate::utils::constants::{ APP_NAME, APP_NAME_LOWERCASE };
+use directories::BaseDirs;
+use std::path::{ Path, PathBuf };
+
+// Characters that make an Exec argument need quotes, from the Desktop Entry spec
+const RESERVED: [char; 19] = [
+    ' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')', '`',
+];
+
+/// File name shared by both entries
+pub fn file_name() -> String {
+    format!("{}.desktop", APP_NAME_LOWERCASE)
+}
+
+/// `$XDG_DATA_HOME/applications`, where the protocol handler entry goes
+pub fn applications_dir() -> Result<PathBuf, String> {
+    BaseDirs::new()
+        .map(|dirs| dirs.data_dir().join("applications"))
+        .ok_or_else(|| "Could not find the home directory".to_string())
+}
+
+/// `$XDG_CONFIG_HOME/autostart`, where the autostart entry goes
+pub fn autostart_dir() -> Result<PathBuf, String> {
+    BaseDirs::new()
+        .map(|dirs| dirs.config_dir().join("autostart"))
+        .ok_or_else(|| "Could not find the home directory".to_string())
+}
+
+/// Program the entries launch, an AppImage itself rather than its temporary `/tmp/.mount_*` copy
+pub fn launcher_path() -> std::io::Result<PathBuf> {
+    match std::env::var_os("APPIMAGE") {
+        Some(appimage) if !appimage.is_empty() => Ok(PathBuf::from(appimage)),
+        _ => std::env::current_exe(),
+    }
+}
+
+/// Quote one Exec argument, escaped for the desktop file
+fn exec_arg(arg: &str) -> String {
+    let arg = arg.replace('%', "%%");
+    let quoted = if arg.is_empty() || arg.contains(RESERVED) {
+        let mut quoted = String::from("\"");
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        quoted.push('"');
+        quoted
+    } else {
+        arg
+    };
+    // Values in the file escape backslashes once more
+    quoted.replace('\\', "\\\\")
+}
+
+/// Exec line running `program` with `args`, which are written as is (e.g. `%u`, `--minimized`)
+pub fn exec_line(program: &Path, args: &[&str]) -> String {
+    std::iter
+        ::once(exec_arg(&program.to_string_lossy()))
+        .chain(args.iter().map(|arg| arg.to_string()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Program of an Exec line as written by `exec_line`
+pub fn exec_program(exec: &str) -> String {
+    let exec = exec.trim_start().replace("\\\\", "\\");
+    let program = match exec.strip_prefix('"') {
+        Some(rest) => {
+            let mut program = String::new();
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => program.extend(chars.next()),
+                    '"' => {
+                        break;
+                    }
+                    c => program.push(c),
+                }
+            }
+            program
+        }
+        None => exec.split_whitespace().next().unwrap_or_default().to_string(),
+    };
+    program.replace("%%", "%")
+}
+
+/// Contents of a desktop entry for the app, `extra` keys are appended in order
+pub fn render(exec: &str, extra: &[(&str, &str)]) -> String {
+    let mut content = format!(
+        "[Desktop Entry]\nType=Application\nName={}\nComment=Mechanical keyboard sound simulator\nExec={}\nIcon={}\n",
+        APP_NAME,
+        exec,
+        APP_NAME_LOWERCASE
+    );
+    for (key, value) in extra {
+        content.push_str(&format!("{}={}\n", key, value));
+    }
+    content
+}
+
+/// Value of `key` in the `[Desktop Entry]` group
+pub fn value<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    content
+        .lines()
+        .skip_while(|line| line.trim() != "[Desktop Entry]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            (name.trim() == key).then(|| value.trim())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exec_lines_round_trip() {
+        for program in ["/usr/bin/mechaura", "/home/me/My Apps/mecha$aura \"1\"", "/opt/100%/C:\\mechaura"] {
+            let exec = exec_line(Path::new(program), &["--minimized"]);
+            assert!(exec.ends_with(" --minimized"));
+            assert_eq!(exec_program(&exec), program, "{}", exec);
+        }
+        assert_eq!(exec_line(Path::new("/usr/bin/mechaura"), &["%u"]), "/usr/bin/mechaura %u");
+
+        let content = render("/usr/bin/mechaura %u", &[("MimeType", "x-scheme-handler/mechaura;")]);
+        assert_eq!(value(&content, "Exec"), Some("/usr/bin/mechaura %u"));
+        assert_eq!(value(&content, "MimeType"), Some("x-scheme-handler/mechaura;"));
+        assert_eq!(value(&content, "Hidden"), None);
+    }
+}
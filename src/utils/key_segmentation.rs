@@ -0,0 +1,284 @@
+/// Automatic key segmentation for "single" method soundpacks
+///
+/// Finds keystroke clicks in one long recording, pairs each press with its release and
+/// assigns them in order to a key list, so authors only have to fine-tune the result.
+use crate::utils::config_converter::load_audio_file_samples;
+use serde_json::{ json, Map, Value };
+
+#[derive(Debug, Clone)]
+pub struct SegmentOptions {
+    pub sensitivity_db: f32, // How far above the noise floor a click has to rise
+    pub min_silence_ms: f32, // Quiet time that ends a click
+    pub min_click_ms: f32, // Shorter bursts are treated as noise
+    pub max_hold_ms: f32, // Longest gap between the press and release click of one key
+    pub pre_roll_ms: f32, // Start segments slightly before the detected onset
+    pub tail_ms: f32, // Keep some decay after the detected offset
+}
+
+impl Default for SegmentOptions {
+    fn default() -> Self {
+        Self {
+            sensitivity_db: 12.0,
+            min_silence_ms: 20.0,
+            min_click_ms: 3.0,
+            max_hold_ms: 350.0,
+            pre_roll_ms: 2.0,
+            tail_ms: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub start_ms: f32,
+    pub end_ms: f32,
+}
+
+impl Segment {
+    fn timing(&self) -> [f32; 2] {
+        // Tenth of a millisecond is plenty and keeps config.json readable
+        [(self.start_ms * 10.0).round() / 10.0, (self.end_ms * 10.0).round() / 10.0]
+    }
+}
+
+/// One key press, `up` is None when no release click was found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keystroke {
+    pub down: Segment,
+    pub up: Option<Segment>,
+}
+
+/// Decode a recording and split it into keystrokes
+pub fn segment_recording(
+    audio_path: &str,
+    options: &SegmentOptions
+) -> Result<Vec<Keystroke>, String> {
+    let (samples, channels, sample_rate) = load_audio_file_samples(audio_path).map_err(|e|
+        format!("Failed to decode {}: {}", audio_path, e)
+    )?;
+    let mono = mix_to_mono(&samples, channels);
+    let clicks = detect_clicks(&mono, sample_rate, options);
+    Ok(pair_keystrokes(&clicks, options))
+}
+
+pub(crate) fn mix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
+    let channels = channels.max(1) as usize;
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / (frame.len() as f32))
+        .collect()
+}
+
+/// Find clicks with a 1 ms RMS envelope, a noise-floor relative threshold and hysteresis
+pub fn detect_clicks(mono: &[f32], sample_rate: u32, options: &SegmentOptions) -> Vec<Segment> {
+    let frame_len = ((sample_rate as usize) / 1000).max(1);
+    // Whole samples per frame, so frames are a little under 1 ms at rates like 44.1 kHz
+    let frame_ms = ((frame_len as f32) * 1000.0) / (sample_rate as f32);
+    let envelope: Vec<f32> = mono
+        .chunks(frame_len)
+        .map(|frame| {
+            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / (frame.len() as f32)).sqrt();
+            20.0 * (rms + 1e-9).log10()
+        })
+        .collect();
+    if envelope.is_empty() {
+        return Vec::new();
+    }
+
+    // Most of a key recording is silence, so a low percentile is a good noise estimate
+    let mut sorted = envelope.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor = sorted[sorted.len() / 5];
+    let peak = sorted[sorted.len() - 1];
+
+    let onset = (noise_floor + options.sensitivity_db).min(peak - 6.0);
+    let release = (onset - 6.0).max(noise_floor + 3.0);
+    let min_silence = (options.min_silence_ms / frame_ms).round().max(1.0) as usize;
+
+    // Frame ranges that rise above `onset` and last until `min_silence` frames below `release`
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    let mut current: Option<(usize, usize)> = None; // (first frame, last loud frame)
+    for (i, &level) in envelope.iter().enumerate() {
+        match current {
+            None if level >= onset => {
+                current = Some((i, i));
+            }
+            Some((start, last_loud)) => {
+                if level >= release {
+                    current = Some((start, i));
+                } else if i - last_loud >= min_silence {
+                    ranges.push((start, last_loud + 1));
+                    current = None;
+                }
+            }
+            None => {}
+        }
+    }
+    if let Some((start, last_loud)) = current {
+        ranges.push((start, last_loud + 1));
+    }
+
+    // The last frame may be shorter, so the length comes from the samples
+    let total_ms = ((mono.len() as f32) * 1000.0) / (sample_rate as f32);
+    let ms = |frame: usize| (frame as f32) * frame_ms;
+    let ranges: Vec<(usize, usize)> = ranges
+        .into_iter()
+        .filter(|(start, end)| ms(end - start) >= options.min_click_ms)
+        .collect();
+
+    // Pad each click, but never into its neighbours
+    let mut clicks = Vec::with_capacity(ranges.len());
+    for (i, &(start, end)) in ranges.iter().enumerate() {
+        let previous_end = if i > 0 { ms(ranges[i - 1].1) } else { 0.0 };
+        let next_start = ranges.get(i + 1).map_or(total_ms, |next| ms(next.0));
+        clicks.push(Segment {
+            start_ms: (ms(start) - options.pre_roll_ms).max(previous_end).max(0.0),
+            end_ms: (ms(end) + options.tail_ms).min(next_start - options.pre_roll_ms).min(total_ms),
+        });
+    }
+    clicks
+}
+
+/// Pair each click with the next one when it follows within `max_hold_ms`
+pub fn pair_keystrokes(clicks: &[Segment], options: &SegmentOptions) -> Vec<Keystroke> {
+    let mut keystrokes = Vec::new();
+    let mut i = 0;
+    while i < clicks.len() {
+        let down = clicks[i];
+        match clicks.get(i + 1) {
+            Some(up) if up.start_ms - down.end_ms <= options.max_hold_ms => {
+                keystrokes.push(Keystroke { down, up: Some(*up) });
+                i += 2;
+            }
+            _ => {
+                keystrokes.push(Keystroke { down, up: None });
+                i += 1;
+            }
+        }
+    }
+    keystrokes
+}
+
+/// Build a V2 "single" config, keystrokes are assigned to `keys` in order
+pub fn build_config(
+    name: &str,
+    author: &str,
+    audio_file: &str,
+    keys: &[String],
+    keystrokes: &[Keystroke]
+) -> Value {
+    let mut definitions = Map::new();
+    for (key, keystroke) in keys.iter().zip(keystrokes) {
+        let mut timing = vec![keystroke.down.timing()];
+        if let Some(up) = keystroke.up {
+            timing.push(up.timing());
+        }
+        definitions.insert(key.clone(), json!({ "timing": timing }));
+    }
+
+    let id = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+
+    json!({
+        "id": id.trim_matches('-'),
+        "name": name,
+        "author": author,
+        "version": "1.0.0",
+        "config_version": "2",
+        "created_at": chrono::Utc::now().to_rfc3339(),
+        "definition_method": "single",
+        "audio_file": audio_file,
+        "options": {
+            "recommended_volume": 1.0,
+            "random_pitch": false,
+        },
+        "definitions": definitions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48_000;
+
+    /// Quiet noise with loud bursts at the given (start_ms, length_ms)
+    fn recording(total_ms: usize, bursts: &[(usize, usize)]) -> Vec<f32> {
+        let per_ms = (RATE / 1000) as usize;
+        let mut samples: Vec<f32> = (0..total_ms * per_ms)
+            .map(|i| if i % 2 == 0 { 0.001 } else { -0.001 })
+            .collect();
+        for &(start, length) in bursts {
+            let burst = &mut samples[start * per_ms..(start + length) * per_ms];
+            for (i, sample) in burst.iter_mut().enumerate() {
+                *sample = if i % 2 == 0 { 0.5 } else { -0.5 };
+            }
+        }
+        samples
+    }
+
+    #[test]
+    fn finds_each_burst() {
+        let options = SegmentOptions::default();
+        let samples = recording(2000, &[(100, 30), (250, 25), (1000, 30), (1180, 20)]);
+        let clicks = detect_clicks(&samples, RATE, &options);
+
+        assert_eq!(clicks.len(), 4);
+        for (click, expected_start) in clicks.iter().zip([100.0, 250.0, 1000.0, 1180.0]) {
+            assert!((click.start_ms - (expected_start - options.pre_roll_ms)).abs() <= 1.0, "{:?}", click);
+            assert!(click.end_ms > click.start_ms);
+        }
+    }
+
+    #[test]
+    fn timings_do_not_drift_at_44_1_khz() {
+        // 44 samples per frame, counting frames as whole milliseconds would be 45 ms off here
+        let rate = 44_100;
+        let burst_start = (20_000 * rate / 1000) as usize;
+        let mut samples: Vec<f32> = (0..(25 * rate) as usize)
+            .map(|i| if i % 2 == 0 { 0.001 } else { -0.001 })
+            .collect();
+        for (i, sample) in samples[burst_start..burst_start + 1323].iter_mut().enumerate() {
+            *sample = if i % 2 == 0 { 0.5 } else { -0.5 };
+        }
+
+        let options = SegmentOptions::default();
+        let clicks = detect_clicks(&samples, rate, &options);
+        assert_eq!(clicks.len(), 1);
+        assert!((clicks[0].start_ms - (20_000.0 - options.pre_roll_ms)).abs() <= 1.0, "{:?}", clicks[0]);
+        assert!((clicks[0].end_ms - (20_030.0 + options.tail_ms)).abs() <= 1.0, "{:?}", clicks[0]);
+    }
+
+    #[test]
+    fn pairs_press_and_release() {
+        let options = SegmentOptions::default();
+        let samples = recording(3000, &[(100, 30), (250, 25), (1000, 30), (2000, 30), (2100, 20)]);
+        let keystrokes = pair_keystrokes(&detect_clicks(&samples, RATE, &options), &options);
+
+        assert_eq!(keystrokes.len(), 3);
+        assert!(keystrokes[0].up.is_some());
+        assert!(keystrokes[1].up.is_none(), "lone click has no release");
+        assert!(keystrokes[2].up.is_some());
+    }
+
+    #[test]
+    fn config_assigns_keys_in_order() {
+        let keystrokes = [
+            Keystroke {
+                down: Segment { start_ms: 10.0, end_ms: 40.0 },
+                up: Some(Segment { start_ms: 120.0, end_ms: 150.0 }),
+            },
+            Keystroke { down: Segment { start_ms: 500.0, end_ms: 530.0 }, up: None },
+        ];
+        let keys = vec!["KeyA".to_string(), "KeyB".to_string(), "KeyC".to_string()];
+        let config = build_config("Test Pack", "me", "sound.ogg", &keys, &keystrokes);
+
+        assert_eq!(config["definitions"]["KeyA"]["timing"], json!([[10.0, 40.0], [120.0, 150.0]]));
+        assert_eq!(config["definitions"]["KeyB"]["timing"], json!([[500.0, 530.0]]));
+        assert!(config["definitions"].get("KeyC").is_none());
+        assert_eq!(config["id"], "test-pack");
+    }
+}
@@ -5,12 +5,22 @@ pub mod config_converter;
 pub mod constants;
 pub mod data;
 pub mod delay;
+pub mod key_segmentation;
 pub mod logger;
 pub mod path;
 pub mod platform;
+pub mod segment_edges;
 pub mod soundpack;
+pub mod soundpack_catalog;
+pub mod soundpack_doctor;
+pub mod soundpack_export;
 pub mod soundpack_installer;
+pub mod soundpack_search;
 pub mod soundpack_validator;
 pub mod spacing;
 pub mod theme;
+pub mod theme_share;
 pub mod time;
+
+#[cfg(target_os = "linux")]
+pub mod desktop_entry;
@@ -0,0 +1,117 @@
+/// Click-free segment edges
+///
+/// Segments are cut sample-exactly out of one recording, and a cut that lands on a loud
+/// sample is heard as a tick. Packs can ramp the edges in and out while playing, and move
+/// their edges onto the nearest zero crossing when they are loaded.
+use crate::state::soundpack::SoundpackOptions;
+
+// How far an edge may move to reach a zero crossing
+pub const SNAP_WINDOW_MS: f32 = 1.0;
+
+/// Ramp lengths applied to every played segment, zero turns a ramp off
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SegmentFades {
+    pub fade_in_ms: f32,
+    pub fade_out_ms: f32,
+}
+
+impl SegmentFades {
+    pub fn from_options(options: &SoundpackOptions) -> Self {
+        if !options.micro_fades {
+            return Self::default();
+        }
+        Self {
+            fade_in_ms: options.fade_in_ms.max(0.0),
+            fade_out_ms: options.fade_out_ms.max(0.0),
+        }
+    }
+
+    /// Apply linear ramps to an interleaved segment, each at most half of its length
+    pub fn apply(&self, samples: &mut [f32], channels: u16, sample_rate: u32) {
+        let channels = channels.max(1) as usize;
+        let frames = samples.len() / channels;
+        let frames_for = |ms: f32| (((ms * (sample_rate as f32)) / 1000.0) as usize).min(frames / 2);
+
+        let fade_in = frames_for(self.fade_in_ms);
+        for frame in 0..fade_in {
+            let gain = (frame as f32) / (fade_in as f32);
+            for sample in &mut samples[frame * channels..(frame + 1) * channels] {
+                *sample *= gain;
+            }
+        }
+
+        let fade_out = frames_for(self.fade_out_ms);
+        for i in 0..fade_out {
+            let frame = frames - 1 - i;
+            let gain = (i as f32) / (fade_out as f32);
+            for sample in &mut samples[frame * channels..(frame + 1) * channels] {
+                *sample *= gain;
+            }
+        }
+    }
+}
+
+/// Move `ms` to the closest zero crossing of `mono` within `window_ms`, or keep it
+pub fn nearest_zero_crossing(mono: &[f32], sample_rate: u32, ms: f32, window_ms: f32) -> f32 {
+    let per_ms = (sample_rate as f32) / 1000.0;
+    let target = (ms.max(0.0) * per_ms).round() as usize;
+    let window = (window_ms * per_ms) as usize;
+    if mono.len() < 2 || target >= mono.len() {
+        return ms;
+    }
+
+    let is_crossing = |i: usize| {
+        i > 0 && i < mono.len() && (mono[i] == 0.0 || (mono[i - 1] < 0.0) != (mono[i] < 0.0))
+    };
+    for distance in 0..=window {
+        for i in [target.saturating_sub(distance), target + distance] {
+            if is_crossing(i) {
+                return (i as f32) / per_ms;
+            }
+        }
+    }
+    ms
+}
+
+/// Snap both edges of a [start, end] pair, keeping the original when they would collapse
+pub fn snap_timing(mono: &[f32], sample_rate: u32, timing: (f64, f64)) -> (f64, f64) {
+    let start = nearest_zero_crossing(mono, sample_rate, timing.0 as f32, SNAP_WINDOW_MS) as f64;
+    let end = nearest_zero_crossing(mono, sample_rate, timing.1 as f32, SNAP_WINDOW_MS) as f64;
+    if end > start { (start, end) } else { timing }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fades_reach_zero_at_both_edges() {
+        let fades = SegmentFades { fade_in_ms: 1.0, fade_out_ms: 2.0 };
+        // Stereo, 10 ms at 48 kHz
+        let mut samples = vec![1.0f32; 2 * 480];
+        fades.apply(&mut samples, 2, 48_000);
+
+        assert_eq!(samples[0], 0.0);
+        assert_eq!(samples[1], 0.0);
+        assert_eq!(samples[samples.len() - 1], 0.0);
+        assert_eq!(samples[2 * 240], 1.0, "middle is untouched");
+        assert!((samples[2 * 24] - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn snaps_to_closest_crossing() {
+        // 1 kHz sine at 48 kHz, shifted half a sample so it changes sign right at every 24th
+        let mono: Vec<f32> = (0..480)
+            .map(|i| (((i as f32) + 0.5) * std::f32::consts::TAU / 48.0).sin())
+            .collect();
+
+        let snapped = nearest_zero_crossing(&mono, 48_000, 5.1, SNAP_WINDOW_MS);
+        let sample = (snapped * 48.0).round() as usize;
+        assert_eq!(sample % 24, 0, "{}", snapped);
+        assert!((snapped - 5.1).abs() <= 0.5);
+
+        // A signal that never crosses zero keeps its edge
+        let flat = vec![0.3f32; 480];
+        assert_eq!(nearest_zero_crossing(&flat, 48_000, 5.1, SNAP_WINDOW_MS), 5.1);
+    }
+}
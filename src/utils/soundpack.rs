@@ -1,3 +1,4 @@
+use crate::debug_print;
 use crate::state::paths;
 use crate::state::soundpack::SoundpackMetadata;
 use crate::utils::config_converter;
@@ -47,7 +48,7 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
     // Check if this is V2 config with multi method and convert to single method
     if let Some(definition_method) = config.get("definition_method").and_then(|v| v.as_str()) {
         if definition_method == "multi" {
-            println!("🔄 [CACHE DEBUG] Found V2 multi method config, converting to single method");
+            debug_print!("🔄 [CACHE DEBUG] Found V2 multi method config, converting to single method");
             let soundpack_dir = paths::soundpacks::soundpack_dir(soundpack_id);
 
             if
@@ -56,7 +57,7 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
                     &soundpack_dir
                 )
             {
-                println!("❌ [CACHE DEBUG] Failed to convert multi to single: {}", e);
+                debug_print!("❌ [CACHE DEBUG] Failed to convert multi to single: {}", e);
                 return Err(format!("Failed to convert multi to single method: {}", e));
             }
 
@@ -68,15 +69,15 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
                 ::from_str(&new_content)
                 .map_err(|e| format!("Failed to parse converted config: {}", e))?;
 
-            println!("✅ [CACHE DEBUG] Successfully converted to single method");
+            debug_print!("✅ [CACHE DEBUG] Successfully converted to single method");
         }
     }
 
     // Debug: Check if config has audio_file field
     let audio_file = config.get("audio_file").and_then(|v| v.as_str());
-    println!("🔍 [CACHE DEBUG] soundpack_id: {}", soundpack_id);
-    println!("🔍 [CACHE DEBUG] config_path: {}", config_path);
-    println!("🔍 [CACHE DEBUG] audio_file in config: {:?}", audio_file);
+    debug_print!("🔍 [CACHE DEBUG] soundpack_id: {}", soundpack_id);
+    debug_print!("🔍 [CACHE DEBUG] config_path: {}", config_path);
+    debug_print!("🔍 [CACHE DEBUG] audio_file in config: {:?}", audio_file);
 
     // If audio_file exists, check if the actual file exists
     if let Some(audio_filename) = audio_file {
@@ -86,18 +87,18 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
             soundpack_dir,
             audio_filename.trim_start_matches("./")
         );
-        println!("🔍 [CACHE DEBUG] soundpack_dir: {}", soundpack_dir);
-        println!("🔍 [CACHE DEBUG] full_audio_path: {}", full_audio_path);
-        println!(
+        debug_print!("🔍 [CACHE DEBUG] soundpack_dir: {}", soundpack_dir);
+        debug_print!("🔍 [CACHE DEBUG] full_audio_path: {}", full_audio_path);
+        debug_print!(
             "🔍 [CACHE DEBUG] audio file exists: {}",
             std::path::Path::new(&full_audio_path).exists()
         );
 
         if !std::path::Path::new(&full_audio_path).exists() {
-            println!("⚠️ [CACHE DEBUG] Audio file not found during cache refresh: {}", full_audio_path);
+            debug_print!("⚠️ [CACHE DEBUG] Audio file not found during cache refresh: {}", full_audio_path);
         }
     } else {
-        println!("⚠️ [CACHE DEBUG] No audio_file field found in config");
+        debug_print!("⚠️ [CACHE DEBUG] No audio_file field found in config");
     }
 
     let name = config
@@ -151,7 +152,7 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
                     paths::soundpacks::soundpack_dir(soundpack_id),
                     icon_filename
                 );
-                println!(
+                debug_print!(
                     "🔍 Checking icon for {}: {} -> exists: {}",
                     soundpack_id,
                     icon_path,
@@ -160,29 +161,30 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
                 if std::path::Path::new(&icon_path).exists() {
                     // Generate dynamic asset URL instead of base64 data URI
                     let asset_url = format!("/soundpack-images/{}/{}", soundpack_id, icon_filename);
-                    println!("✅ Generated asset URL for {}: {}", soundpack_id, asset_url);
+                    debug_print!("✅ Generated asset URL for {}: {}", soundpack_id, asset_url);
                     Some(asset_url)
                 } else {
-                    println!("❌ Icon not found for {}, setting empty string", soundpack_id);
+                    debug_print!("❌ Icon not found for {}, setting empty string", soundpack_id);
                     Some(String::new()) // Empty string if icon file not found
                 }
             } else {
-                println!("ℹ️  No icon specified for {}", soundpack_id);
+                debug_print!("ℹ️  No icon specified for {}", soundpack_id);
                 Some(String::new()) // Empty string if no icon specified
             }
         },
         soundpack_type: {
+            use crate::state::soundpack::SoundpackType;
             // Determine soundpack type based on folder path (more reliable than JSON content)
-            if soundpack_id.starts_with("keyboard/") || soundpack_id.starts_with("keyboard\\") {
-                crate::state::soundpack::SoundpackType::Keyboard
-            } else if soundpack_id.starts_with("mouse/") || soundpack_id.starts_with("mouse\\") {
-                crate::state::soundpack::SoundpackType::Mouse
-            } else {
+            let prefix = soundpack_id.split(['/', '\\']).next().unwrap_or_default();
+            match SoundpackType::ALL.into_iter().find(|t| t.folder_name() == prefix) {
+                Some(soundpack_type) => soundpack_type,
                 // Fallback to JSON content or default to keyboard
-                match config.get("soundpack_type").and_then(|v| v.as_str()) {
-                    Some("mouse") => crate::state::soundpack::SoundpackType::Mouse,
-                    _ => crate::state::soundpack::SoundpackType::Keyboard,
-                }
+                None =>
+                    match config.get("soundpack_type").and_then(|v| v.as_str()) {
+                        Some("mouse") => SoundpackType::Mouse,
+                        Some("gamepad") => SoundpackType::Gamepad,
+                        _ => SoundpackType::Keyboard,
+                    }
             }
         },
         folder_path: soundpack_id.to_string(), // Store the relative path (e.g., "keyboard/Super Paper Mario Talk")
@@ -196,17 +198,14 @@ pub fn load_soundpack_metadata(soundpack_id: &str) -> Result<SoundpackMetadata,
         // Validation fields
         config_version: final_validation.config_version,
         is_valid_v2: final_validation.is_valid_v2,
-        validation_status: match final_validation.status {
-            SoundpackValidationStatus::Valid => "valid".to_string(),
-            SoundpackValidationStatus::InvalidVersion => "invalid_version".to_string(),
-            SoundpackValidationStatus::InvalidStructure(_) => "invalid_structure".to_string(),
-            SoundpackValidationStatus::MissingRequiredFields(_) => "missing_fields".to_string(),
-            SoundpackValidationStatus::VersionOneNeedsConversion => {
-                "v1_needs_conversion".to_string()
-            }
-        },
+        validation_status: final_validation.status.as_str().to_string(),
         can_be_converted: final_validation.can_be_converted,
+        definition_method: config
+            .get("definition_method")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
         // Error tracking - clear error if we successfully loaded metadata
         last_error: last_error,
+        doctor: None,
     })
 }
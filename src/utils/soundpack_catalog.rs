@@ -0,0 +1,309 @@
+/// Soundpack catalog: a JSON index of downloadable soundpack ZIPs
+///
+/// The index lists each pack with its ZIP URL, size and sha256. Downloads are checked
+/// against both before they go through the regular ZIP installer. URLs can also be local
+/// paths or `file://` URLs, and ZIP URLs relative to the index are resolved against it.
+use crate::utils::constants::{ APP_NAME, APP_VERSION };
+use crate::utils::soundpack_installer::{ install_soundpack_zip, InstallOutcome };
+use serde::{ Deserialize, Serialize };
+use sha2::{ Digest, Sha256 };
+use std::path::{ Path, PathBuf };
+
+// Same ceiling the ZIP installer puts on unpacked packs
+const MAX_DOWNLOAD_SIZE: u64 = 1024 * 1024 * 1024;
+const MAX_INDEX_SIZE: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub preview: Option<String>, // Image URL
+    pub zip_url: String,
+    pub sha256: String,
+    #[serde(default)]
+    pub size: u64, // Bytes of the ZIP, 0 when unknown
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Catalog {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub soundpacks: Vec<CatalogEntry>,
+}
+
+impl Catalog {
+    /// Every tag used in the catalog, sorted and without duplicates
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.soundpacks
+            .iter()
+            .flat_map(|entry| entry.tags.iter().map(|tag| tag.to_lowercase()))
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Entries whose name, author, description or tags contain `query`, optionally with `tag`
+    pub fn search(&self, query: &str, tag: Option<&str>) -> Vec<&CatalogEntry> {
+        let query = query.trim().to_lowercase();
+        self.soundpacks
+            .iter()
+            .filter(|entry| {
+                tag.is_none_or(|tag| entry.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            })
+            .filter(|entry| {
+                query.is_empty() ||
+                    [Some(&entry.name), entry.author.as_ref(), entry.description.as_ref()]
+                        .into_iter()
+                        .flatten()
+                        .chain(entry.tags.iter())
+                        .any(|text| text.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+}
+
+fn is_http(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// Local file path of a source, None for http(s) URLs
+fn local_path(source: &str) -> Option<PathBuf> {
+    if let Some(path) = source.strip_prefix("file://") {
+        return Some(PathBuf::from(path));
+    }
+    if is_http(source) {
+        return None;
+    }
+    Some(PathBuf::from(source))
+}
+
+/// Resolve a URL from the index relative to the index itself
+///
+/// Local indexes may point anywhere, remote ones only at http(s) URLs so that a downloaded index
+/// can't make the app read files from this machine.
+fn resolve_url(index_url: &str, url: &str) -> Result<String, String> {
+    let Some(index_path) = local_path(index_url) else {
+        let resolved = if url.contains("://") {
+            url.to_string()
+        } else {
+            reqwest::Url
+                ::parse(index_url)
+                .and_then(|base| base.join(url))
+                .map(|resolved| resolved.to_string())
+                .map_err(|e| format!("Invalid URL '{}': {}", url, e))?
+        };
+        if !is_http(&resolved) {
+            return Err(format!("Remote catalog points at a non-http(s) URL: {}", url));
+        }
+        return Ok(resolved);
+    };
+    if url.contains("://") || Path::new(url).is_absolute() {
+        return Ok(url.to_string());
+    }
+    let base = index_path.parent().unwrap_or(Path::new(""));
+    Ok(base.join(url).to_string_lossy().to_string())
+}
+
+/// Parse and check an index, `index_url` is where it was loaded from
+pub fn parse_catalog(content: &str, index_url: &str) -> Result<Catalog, String> {
+    let mut catalog: Catalog = serde_json
+        ::from_str(content)
+        .map_err(|e| format!("Failed to parse catalog: {}", e))?;
+
+    for entry in catalog.soundpacks.iter_mut() {
+        let sha256 = entry.sha256.trim().to_lowercase();
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Catalog entry '{}' has an invalid sha256", entry.id));
+        }
+        if entry.size > MAX_DOWNLOAD_SIZE {
+            return Err(format!("Catalog entry '{}' is too large to download", entry.id));
+        }
+        entry.sha256 = sha256;
+        entry.zip_url = resolve_url(index_url, &entry.zip_url)?;
+        if let Some(preview) = entry.preview.as_mut() {
+            *preview = resolve_url(index_url, preview)?;
+        }
+    }
+    Ok(catalog)
+}
+
+/// Read a local file or download a URL, refusing anything larger than `max_size`
+async fn fetch_bytes(url: &str, max_size: u64) -> Result<Vec<u8>, String> {
+    if let Some(path) = local_path(url) {
+        let size = std::fs
+            ::metadata(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+            .len();
+        if size > max_size {
+            return Err(format!("{} is larger than {} bytes", path.display(), max_size));
+        }
+        return std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e));
+    }
+
+    let client = reqwest::Client::new();
+    let mut response = client
+        .get(url)
+        .header("User-Agent", format!("{}/{}", APP_NAME, APP_VERSION))
+        .send().await
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to download {}: HTTP {}", url, response.status()));
+    }
+    if response.content_length().is_some_and(|length| length > max_size) {
+        return Err(format!("{} is larger than {} bytes", url, max_size));
+    }
+
+    // Content-Length can be missing or wrong, so count while reading
+    let mut bytes = Vec::new();
+    while
+        let Some(chunk) = response
+            .chunk().await
+            .map_err(|e| format!("Failed to download {}: {}", url, e))?
+    {
+        bytes.extend_from_slice(&chunk);
+        if (bytes.len() as u64) > max_size {
+            return Err(format!("{} is larger than {} bytes", url, max_size));
+        }
+    }
+    Ok(bytes)
+}
+
+/// Load the catalog index from a URL or local path
+pub async fn fetch_catalog(index_url: &str) -> Result<Catalog, String> {
+    if index_url.trim().is_empty() {
+        return Err("No soundpack catalog is set, enter its URL on the Catalog tab".to_string());
+    }
+    let bytes = fetch_bytes(index_url, MAX_INDEX_SIZE).await?;
+    let content = String::from_utf8(bytes).map_err(|_| "Catalog is not valid UTF-8".to_string())?;
+    parse_catalog(&content, index_url)
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Check a downloaded ZIP against the size and checksum from the index
+pub fn verify_download(entry: &CatalogEntry, bytes: &[u8]) -> Result<(), String> {
+    if entry.size > 0 && (bytes.len() as u64) != entry.size {
+        return Err(
+            format!(
+                "Download of '{}' has {} bytes, the catalog says {}",
+                entry.name,
+                bytes.len(),
+                entry.size
+            )
+        );
+    }
+    let actual = sha256_hex(bytes);
+    if actual != entry.sha256 {
+        return Err(format!("Checksum of '{}' does not match the catalog ({})", entry.name, actual));
+    }
+    Ok(())
+}
+
+/// Download a catalog entry and verify it
+pub async fn download_entry(entry: &CatalogEntry) -> Result<Vec<u8>, String> {
+    let max_size = if entry.size > 0 { entry.size } else { MAX_DOWNLOAD_SIZE };
+    let bytes = fetch_bytes(&entry.zip_url, max_size).await?;
+    verify_download(entry, &bytes)?;
+    Ok(bytes)
+}
+
+/// Download, verify and install a catalog entry
+/// Without `replace`, an installed pack with the same ID is reported instead of overwritten
+pub async fn install_entry(entry: &CatalogEntry, replace: bool) -> Result<InstallOutcome, String> {
+    let bytes = download_entry(entry).await?;
+
+    let zip_path = std::env::temp_dir().join(format!("catalog-{}.zip", uuid::Uuid::new_v4()));
+    std::fs
+        ::write(&zip_path, bytes)
+        .map_err(|e| format!("Failed to write {}: {}", zip_path.display(), e))?;
+
+    let install_path = zip_path.clone();
+    let result = tokio::task
+        ::spawn_blocking(move || install_soundpack_zip(&install_path.to_string_lossy(), replace)).await
+        .unwrap_or_else(|e| Err(format!("Install task failed: {}", e)));
+    let _ = std::fs::remove_file(&zip_path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(sha256: &str, size: u64) -> String {
+        format!(
+            r#"{{
+  "soundpacks": [
+    {{ "id": "cream", "name": "NK Cream", "author": "nk", "tags": ["Linear", "thock"],
+       "zip_url": "packs/cream.zip", "sha256": "{}", "size": {} }},
+    {{ "id": "blue", "name": "Cherry Blue", "tags": ["clicky"],
+       "zip_url": "https://example.com/blue.zip", "sha256": "{}" }}
+  ]
+}}"#,
+            sha256,
+            size,
+            "0".repeat(64)
+        )
+    }
+
+    #[test]
+    fn resolves_urls_and_searches() {
+        let catalog = parse_catalog(&index(&"A".repeat(64), 3), "https://example.com/catalog/index.json").unwrap();
+        assert_eq!(catalog.soundpacks[0].zip_url, "https://example.com/catalog/packs/cream.zip");
+        assert_eq!(catalog.soundpacks[0].sha256, "a".repeat(64));
+        assert_eq!(catalog.tags(), vec!["clicky", "linear", "thock"]);
+
+        assert_eq!(catalog.search("cherry", None).len(), 1);
+        assert_eq!(catalog.search("", Some("linear"))[0].id, "cream");
+        assert!(catalog.search("cherry", Some("linear")).is_empty());
+
+        assert!(parse_catalog(&index("not-a-hash", 0), "index.json").is_err());
+    }
+
+    #[test]
+    fn remote_index_only_points_at_http() {
+        let remote = "https://example.com/catalog/index.json";
+        assert_eq!(resolve_url(remote, "/packs/a.zip").unwrap(), "https://example.com/packs/a.zip");
+        assert!(resolve_url(remote, "file:///etc/passwd").is_err());
+        assert!(resolve_url(remote, "ftp://example.com/a.zip").is_err());
+        assert!(resolve_url(remote, "C:/packs/a.zip").is_err());
+
+        // Local indexes keep pointing at local files
+        assert_eq!(resolve_url("/srv/catalog/index.json", "/packs/a.zip").unwrap(), "/packs/a.zip");
+        assert_eq!(resolve_url("file:///srv/index.json", "file:///packs/a.zip").unwrap(), "file:///packs/a.zip");
+    }
+
+    #[test]
+    fn downloads_from_local_index_and_checks_sha256() {
+        let dir = std::env::temp_dir().join(format!("catalog-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("packs")).unwrap();
+        std::fs::write(dir.join("packs/cream.zip"), b"zip").unwrap();
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let index_path = dir.join("index.json");
+
+        std::fs::write(&index_path, index(&sha256_hex(b"zip"), 3)).unwrap();
+        let catalog = runtime.block_on(fetch_catalog(&index_path.to_string_lossy())).unwrap();
+        let bytes = runtime.block_on(download_entry(&catalog.soundpacks[0])).unwrap();
+        assert_eq!(bytes, b"zip");
+
+        std::fs::write(&index_path, index(&sha256_hex(b"other"), 3)).unwrap();
+        let url = format!("file://{}", index_path.to_string_lossy());
+        let catalog = runtime.block_on(fetch_catalog(&url)).unwrap();
+        assert!(runtime.block_on(download_entry(&catalog.soundpacks[0])).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
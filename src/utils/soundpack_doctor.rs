@@ -0,0 +1,341 @@
+/// Soundpack doctor: audio quality checks that go beyond config validation
+///
+/// Looks at every segment of an installed pack and reports the things that make a pack
+/// sound worse than it could, like clipped or badly cut segments and missing keys.
+use crate::libs::key_codes::ANSI_LAYOUT;
+use crate::state::soundpack::{ SoundPack, SoundpackType };
+use crate::utils::config_converter::load_audio_file_samples;
+use crate::utils::key_segmentation::mix_to_mono;
+use serde::{ Deserialize, Serialize };
+use std::collections::HashMap;
+use std::path::Path;
+
+// Keys an ISO keyboard has on top of the full ANSI layout
+const ISO_EXTRA_KEYS: &[&str] = &["IntlBackslash"];
+
+const CLIP_LEVEL: f32 = 0.999;
+const DC_OFFSET_LIMIT: f32 = 0.02;
+const LEADING_SILENCE_LIMIT_MS: f32 = 5.0;
+// Edges louder than this fraction of the segment peak start or stop mid-waveform
+const HARD_CUT_RATIO: f32 = 0.1;
+const HARD_CUT_WINDOW_MS: f32 = 0.5;
+const LOUDNESS_SPREAD_DB: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum DoctorCheck {
+    MissingKeys,
+    Clipping,
+    DcOffset,
+    LeadingSilence,
+    HardCuts,
+    LoudnessSpread,
+}
+
+impl DoctorCheck {
+    /// Short badge text
+    pub fn label(&self) -> &'static str {
+        match self {
+            DoctorCheck::MissingKeys => "missing keys",
+            DoctorCheck::Clipping => "clipping",
+            DoctorCheck::DcOffset => "DC offset",
+            DoctorCheck::LeadingSilence => "leading silence",
+            DoctorCheck::HardCuts => "hard cuts",
+            DoctorCheck::LoudnessSpread => "uneven loudness",
+        }
+    }
+
+    fn summary(&self, count: usize) -> String {
+        match self {
+            DoctorCheck::MissingKeys => format!("{} standard ANSI/ISO keys have no sound", count),
+            DoctorCheck::Clipping => format!("{} keys reach full scale and may distort", count),
+            DoctorCheck::DcOffset => format!("{} keys are offset from zero and thump", count),
+            DoctorCheck::LeadingSilence =>
+                format!(
+                    "{} keys start more than {} ms before the sound, which feels laggy",
+                    count,
+                    LEADING_SILENCE_LIMIT_MS
+                ),
+            DoctorCheck::HardCuts => format!("{} keys start or end mid-waveform and may click", count),
+            DoctorCheck::LoudnessSpread =>
+                format!(
+                    "{} keys are more than {} dB louder or quieter than the rest",
+                    count,
+                    LOUDNESS_SPREAD_DB
+                ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DoctorFinding {
+    pub check: DoctorCheck,
+    pub message: String,
+    pub keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DoctorReport {
+    pub checked_at: u64,
+    pub findings: Vec<DoctorFinding>,
+}
+
+/// Levels of one segment, measured on the mono mix
+struct SegmentStats {
+    peak: f32,
+    mean: f32,
+    rms_db: f32,
+    leading_silence_ms: f32,
+    edge_ratio: f32, // Louder edge relative to the peak
+}
+
+fn segment_stats(mono: &[f32], sample_rate: u32) -> Option<SegmentStats> {
+    if mono.is_empty() {
+        return None;
+    }
+    let peak = mono.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let mean = mono.iter().sum::<f32>() / (mono.len() as f32);
+    let rms = (mono.iter().map(|s| s * s).sum::<f32>() / (mono.len() as f32)).sqrt();
+
+    let per_ms = (sample_rate as f32) / 1000.0;
+    let onset = mono
+        .iter()
+        .position(|s| s.abs() >= peak * HARD_CUT_RATIO)
+        .unwrap_or(0);
+
+    let window = ((HARD_CUT_WINDOW_MS * per_ms) as usize).clamp(1, mono.len());
+    let edge_level = |edge: &[f32]| edge.iter().map(|s| s.abs()).sum::<f32>() / (edge.len() as f32);
+    let edge = edge_level(&mono[..window]).max(edge_level(&mono[mono.len() - window..]));
+
+    Some(SegmentStats {
+        peak,
+        mean,
+        rms_db: 20.0 * (rms + 1e-9).log10(),
+        leading_silence_ms: (onset as f32) / per_ms,
+        edge_ratio: if peak > 0.0 { edge / peak } else { 0.0 },
+    })
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime
+        ::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Run every check on an installed soundpack folder
+pub fn diagnose_soundpack(soundpack_dir: &Path) -> Result<DoctorReport, String> {
+    let config_path = soundpack_dir.join("config.json");
+    let content = std::fs
+        ::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))?;
+    let soundpack: SoundPack = serde_json
+        ::from_str(&content)
+        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+
+    let mut findings = Vec::new();
+
+    if soundpack.soundpack_type == SoundpackType::Keyboard {
+        let mut missing: Vec<String> = ANSI_LAYOUT.iter()
+            .flat_map(|row| row.iter())
+            .chain(ISO_EXTRA_KEYS.iter())
+            .filter(|code| !soundpack.definitions.contains_key(**code))
+            .map(|code| code.to_string())
+            .collect();
+        missing.sort();
+        if !missing.is_empty() {
+            findings.push(DoctorFinding {
+                check: DoctorCheck::MissingKeys,
+                message: DoctorCheck::MissingKeys.summary(missing.len()),
+                keys: missing,
+            });
+        }
+    }
+
+    // Mono mix and sample rate per audio file
+    let mut decoded: HashMap<String, (Vec<f32>, u32)> = HashMap::new();
+    let mut flagged: HashMap<DoctorCheck, Vec<String>> = HashMap::new();
+    let mut loudness: Vec<(String, f32)> = Vec::new();
+
+    let mut keys: Vec<&String> = soundpack.definitions.keys().collect();
+    keys.sort();
+    for key in keys {
+        let definition = &soundpack.definitions[key];
+        let Some(audio_file) = definition.audio_file.as_ref().or(soundpack.audio_file.as_ref()) else {
+            continue;
+        };
+        if !decoded.contains_key(audio_file) {
+            let audio_path = soundpack_dir.join(audio_file.trim_start_matches("./"));
+            let (samples, channels, sample_rate) = load_audio_file_samples(
+                &audio_path.to_string_lossy()
+            ).map_err(|e| format!("Failed to decode {}: {}", audio_file, e))?;
+            decoded.insert(audio_file.clone(), (mix_to_mono(&samples, channels), sample_rate));
+        }
+        let (mono, sample_rate) = &decoded[audio_file];
+
+        for (index, timing) in definition.timing.iter().enumerate() {
+            let sample_at = |ms: f32| ((ms.max(0.0) * (*sample_rate as f32)) / 1000.0) as usize;
+            let first = sample_at(timing[0]).min(mono.len());
+            let last = sample_at(timing[1]).min(mono.len());
+            let Some(stats) = segment_stats(&mono[first..last.max(first)], *sample_rate) else {
+                continue;
+            };
+
+            let mut flag = |check: DoctorCheck| {
+                let keys = flagged.entry(check).or_default();
+                if keys.last() != Some(key) {
+                    keys.push(key.clone());
+                }
+            };
+            if stats.peak >= CLIP_LEVEL {
+                flag(DoctorCheck::Clipping);
+            }
+            if stats.mean.abs() > DC_OFFSET_LIMIT {
+                flag(DoctorCheck::DcOffset);
+            }
+            if stats.leading_silence_ms > LEADING_SILENCE_LIMIT_MS {
+                flag(DoctorCheck::LeadingSilence);
+            }
+            if stats.edge_ratio > HARD_CUT_RATIO {
+                flag(DoctorCheck::HardCuts);
+            }
+            // Keydown sounds are what people hear most, compare those
+            if index == 0 && stats.peak > 0.0 {
+                loudness.push((key.clone(), stats.rms_db));
+            }
+        }
+    }
+
+    if loudness.len() >= 3 {
+        let mut levels: Vec<f32> = loudness
+            .iter()
+            .map(|(_, db)| *db)
+            .collect();
+        levels.sort_by(|a, b| a.total_cmp(b));
+        let median = levels[levels.len() / 2];
+        let outliers: Vec<String> = loudness
+            .iter()
+            .filter(|(_, db)| (db - median).abs() > LOUDNESS_SPREAD_DB)
+            .map(|(key, _)| key.clone())
+            .collect();
+        if !outliers.is_empty() {
+            flagged.insert(DoctorCheck::LoudnessSpread, outliers);
+        }
+    }
+
+    for check in [
+        DoctorCheck::Clipping,
+        DoctorCheck::DcOffset,
+        DoctorCheck::LeadingSilence,
+        DoctorCheck::HardCuts,
+        DoctorCheck::LoudnessSpread,
+    ] {
+        let Some(keys) = flagged.remove(&check) else {
+            continue;
+        };
+        let message = check.summary(keys.len());
+        findings.push(DoctorFinding { check, message, keys });
+    }
+
+    Ok(DoctorReport { checked_at: now_secs(), findings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_leading_silence_and_hard_cuts() {
+        // 10 ms of silence, then a tone that is cut off at full level
+        let rate = 48_000;
+        let mut mono = vec![0.0f32; 480];
+        mono.extend((0..960).map(|i| if i % 2 == 0 { 0.5 } else { -0.5 }));
+
+        let stats = segment_stats(&mono, rate).unwrap();
+        assert!((stats.leading_silence_ms - 10.0).abs() < 0.1);
+        assert!(stats.edge_ratio > HARD_CUT_RATIO);
+        assert!(stats.peak < CLIP_LEVEL);
+        assert!(stats.mean.abs() < DC_OFFSET_LIMIT);
+    }
+
+    #[test]
+    fn diagnoses_a_generated_pack() {
+        // Eight 20 ms keydown segments at 48 kHz, each one built to trip a single check
+        let rate = 48_000u32;
+        let tone = |i: usize, amplitude: f32| {
+            amplitude * ((i as f32) * std::f32::consts::TAU / 48.0).sin()
+        };
+        let hann = |i: usize, len: usize| {
+            (((i as f32) * std::f32::consts::PI) / (len as f32)).sin().powi(2)
+        };
+        let clean = |amplitude: f32| -> Vec<f32> {
+            (0..960).map(|i| hann(i, 960) * tone(i, amplitude)).collect()
+        };
+        let mut clipped = clean(0.5);
+        clipped[480] = 1.0;
+        let offset: Vec<f32> = (0..960).map(|i| hann(i, 960) * (tone(i, 0.5) + 0.1)).collect();
+        let hard_cut: Vec<f32> = (0..960).map(|i| tone(i, 0.5)).collect();
+        let mut late = vec![0.0f32; 480];
+        late.extend((0..480).map(|i| hann(i, 480) * tone(i, 0.5)));
+
+        let segments = [
+            ("KeyA", clean(0.5)),
+            ("KeyB", clean(0.5)),
+            ("KeyC", clean(0.5)),
+            ("KeyD", clipped),
+            ("KeyE", offset),
+            ("KeyF", clean(0.005)),
+            ("KeyG", hard_cut),
+            ("KeyH", late),
+        ];
+
+        let dir = std::env::temp_dir().join(format!("mechaura-doctor-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(dir.join("sound.wav"), spec).unwrap();
+        let mut definitions = serde_json::Map::new();
+        for (index, (key, samples)) in segments.iter().enumerate() {
+            for sample in samples {
+                writer.write_sample((sample.clamp(-1.0, 1.0) * 32767.0) as i16).unwrap();
+            }
+            let start = (index as f32) * 20.0;
+            definitions.insert(key.to_string(), serde_json::json!({ "timing": [[start, start + 20.0]] }));
+        }
+        writer.finalize().unwrap();
+        let config = serde_json::json!({
+            "id": "doctor-test",
+            "name": "Doctor test",
+            "definition_method": "single",
+            "audio_file": "sound.wav",
+            "definitions": definitions,
+        });
+        std::fs::write(dir.join("config.json"), config.to_string()).unwrap();
+
+        let report = diagnose_soundpack(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+        let report = report.unwrap();
+        let keys = |check: DoctorCheck| -> Vec<String> {
+            report.findings
+                .iter()
+                .find(|finding| finding.check == check)
+                .map(|finding| finding.keys.clone())
+                .unwrap_or_default()
+        };
+
+        let missing = keys(DoctorCheck::MissingKeys);
+        assert!(missing.contains(&"Space".to_string()));
+        assert!(missing.contains(&"IntlBackslash".to_string()));
+        assert!(!missing.contains(&"KeyA".to_string()));
+        assert_eq!(keys(DoctorCheck::Clipping), ["KeyD"]);
+        assert_eq!(keys(DoctorCheck::DcOffset), ["KeyE"]);
+        assert_eq!(keys(DoctorCheck::HardCuts), ["KeyG"]);
+        assert_eq!(keys(DoctorCheck::LeadingSilence), ["KeyH"]);
+        // Only the quiet key is far from the median, the clipped and cut ones stay close to it
+        assert_eq!(keys(DoctorCheck::LoudnessSpread), ["KeyF"]);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::state::soundpack::{ SoundPack, SoundpackType };
+use crate::utils::config_converter::create_iohook_to_web_key_mapping;
+use serde_json::{ json, Map, Value };
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{ Component, Path };
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+/// Files that only make sense on the machine that created them
+fn is_local_only(file_name: &str) -> bool {
+    file_name.ends_with(".v1.backup") || file_name.starts_with("temp_")
+}
+
+/// Pack a soundpack folder into a ZIP the import modal accepts
+/// Everything goes under one top-level folder, which the installer strips again
+/// Returns the number of files written
+pub fn export_soundpack_zip(soundpack_dir: &Path, output_path: &Path) -> Result<usize, String> {
+    if !soundpack_dir.join("config.json").exists() {
+        return Err(format!("No config.json found in {}", soundpack_dir.display()));
+    }
+
+    let root_name = soundpack_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "soundpack".to_string());
+
+    let file = File::create(output_path).map_err(|e|
+        format!("Failed to create {}: {}", output_path.display(), e)
+    )?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut written = 0;
+    add_directory(&mut zip, soundpack_dir, &root_name, options, &mut written)?;
+
+    zip.finish().map_err(|e| format!("Failed to finish ZIP: {}", e))?;
+    Ok(written)
+}
+
+fn add_directory(
+    zip: &mut ZipWriter<File>,
+    dir: &Path,
+    prefix: &str,
+    options: SimpleFileOptions,
+    written: &mut usize
+) -> Result<(), String> {
+    let mut entries: Vec<_> = std::fs
+        ::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(|entry| entry.ok())
+        .collect();
+    // Stable order so the same pack always produces the same archive
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let archive_name = format!("{}/{}", prefix, file_name);
+
+        // Never follow links out of the pack folder
+        let file_type = entry.file_type().map_err(|e| format!("Failed to stat {}: {}", path.display(), e))?;
+        if file_type.is_symlink() {
+            continue;
+        }
+
+        if file_type.is_dir() {
+            add_directory(zip, &path, &archive_name, options, written)?;
+        } else if !is_local_only(&file_name) {
+            let contents = std::fs
+                ::read(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            zip
+                .start_file(archive_name.as_str(), options)
+                .map_err(|e| format!("Failed to add {}: {}", archive_name, e))?;
+            zip
+                .write_all(&contents)
+                .map_err(|e| format!("Failed to write {}: {}", archive_name, e))?;
+            *written += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Config layout of a shared soundpack ZIP
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportLayout {
+    V2,
+    /// Mechvibes `defines` keyed by IOHook codes, only has keydown sounds
+    MechvibesV1,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ExportReport {
+    pub files: usize,
+    /// Keys that cannot be written in the chosen layout
+    pub dropped_keys: Vec<String>,
+}
+
+/// Export a soundpack for sharing: a normalized config plus only the files it references
+pub fn export_shareable_zip(
+    soundpack_dir: &Path,
+    output_path: &Path,
+    layout: ExportLayout
+) -> Result<ExportReport, String> {
+    let config_path = soundpack_dir.join("config.json");
+    let content = std::fs
+        ::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read {}: {}", config_path.display(), e))?;
+    let soundpack: SoundPack = serde_json
+        ::from_str(&content)
+        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+
+    let mut report = ExportReport::default();
+    let config = match layout {
+        ExportLayout::V2 => normalized_v2_config(&soundpack)?,
+        ExportLayout::MechvibesV1 => mechvibes_v1_config(&soundpack, &mut report.dropped_keys)?,
+    };
+
+    // Referenced files, in a stable order and each only once
+    let mut referenced: Vec<String> = soundpack.audio_file.iter().cloned().collect();
+    if soundpack.definition_method == "multi" {
+        referenced.extend(soundpack.definitions.values().filter_map(|def| def.audio_file.clone()));
+    }
+    if let Some(icon) = soundpack.icon.as_ref() {
+        // Icons may also be URLs or data URIs, only bundle local files
+        if soundpack_dir.join(icon.trim_start_matches("./")).is_file() {
+            referenced.push(icon.clone());
+        }
+    }
+    let mut referenced: Vec<String> = referenced
+        .iter()
+        .map(|file| file.trim_start_matches("./").to_string())
+        .collect();
+    referenced.sort();
+    referenced.dedup();
+
+    let root_name = soundpack_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "soundpack".to_string());
+
+    let file = File::create(output_path).map_err(|e|
+        format!("Failed to create {}: {}", output_path.display(), e)
+    )?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let config_json = serde_json
+        ::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    add_file(&mut zip, &format!("{}/config.json", root_name), config_json.as_bytes(), options)?;
+    report.files += 1;
+
+    for relative in referenced {
+        let source = soundpack_dir.join(&relative);
+        // Configs are shared, never let them pull in files from outside the pack
+        if
+            Path::new(&relative).components().any(|part| !matches!(part, Component::Normal(_))) ||
+            !std::fs::symlink_metadata(&source).is_ok_and(|meta| meta.is_file())
+        {
+            return Err(format!("Referenced file is missing or outside the soundpack: {}", relative));
+        }
+        let contents = std::fs
+            ::read(&source)
+            .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+        add_file(&mut zip, &format!("{}/{}", root_name, relative), &contents, options)?;
+        report.files += 1;
+    }
+
+    zip.finish().map_err(|e| format!("Failed to finish ZIP: {}", e))?;
+    Ok(report)
+}
+
+fn add_file(
+    zip: &mut ZipWriter<File>,
+    archive_name: &str,
+    contents: &[u8],
+    options: SimpleFileOptions
+) -> Result<(), String> {
+    zip
+        .start_file(archive_name, options)
+        .map_err(|e| format!("Failed to add {}: {}", archive_name, e))?;
+    zip.write_all(contents).map_err(|e| format!("Failed to write {}: {}", archive_name, e))
+}
+
+fn round_ms(ms: f32) -> f64 {
+    ((ms as f64) * 10.0).round() / 10.0
+}
+
+/// The pack as the app writes it, without internal and empty fields
+fn normalized_v2_config(soundpack: &SoundPack) -> Result<Value, String> {
+    let mut config = serde_json
+        ::to_value(soundpack)
+        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let object = config.as_object_mut().ok_or("Invalid soundpack config")?;
+
+    object.remove("config_version_num");
+    object.retain(|_, value| !value.is_null());
+    // soundpack_type stays, the installer reads it to put mouse and gamepad packs in the right folder
+    object.insert("config_version".to_string(), json!("2"));
+
+    let mut definitions = Map::new();
+    for (key, definition) in &soundpack.definitions {
+        let timing: Vec<[f64; 2]> = definition.timing
+            .iter()
+            .map(|t| [round_ms(t[0]), round_ms(t[1])])
+            .collect();
+        let mut entry = json!({ "timing": timing });
+        if let Some(audio_file) = &definition.audio_file {
+            entry["audio_file"] = json!(audio_file.trim_start_matches("./"));
+        }
+        definitions.insert(key.clone(), entry);
+    }
+    object.insert("definitions".to_string(), Value::Object(definitions));
+    Ok(config)
+}
+
+/// Mechvibes V1 config, keys without an IOHook code end up in `dropped_keys`
+fn mechvibes_v1_config(soundpack: &SoundPack, dropped_keys: &mut Vec<String>) -> Result<Value, String> {
+    if soundpack.soundpack_type != SoundpackType::Keyboard {
+        return Err("The Mechvibes layout only supports keyboard soundpacks".to_string());
+    }
+
+    // Several IOHook codes can map to one key, prefer the lowest (the common one)
+    let mut iohook_codes: HashMap<String, u32> = HashMap::new();
+    for (code, key) in create_iohook_to_web_key_mapping() {
+        let entry = iohook_codes.entry(key).or_insert(code);
+        *entry = (*entry).min(code);
+    }
+
+    let multi = soundpack.definition_method == "multi";
+    let mut defines = Map::new();
+    for (key, definition) in &soundpack.definitions {
+        let (Some(code), Some(down)) = (iohook_codes.get(key), definition.timing.first()) else {
+            dropped_keys.push(key.clone());
+            continue;
+        };
+        let value = if multi {
+            match &definition.audio_file {
+                Some(audio_file) => json!(audio_file.trim_start_matches("./")),
+                None => {
+                    dropped_keys.push(key.clone());
+                    continue;
+                }
+            }
+        } else {
+            // V1 timings are [start, duration]
+            json!([round_ms(down[0]), round_ms(down[1] - down[0])])
+        };
+        defines.insert(code.to_string(), value);
+    }
+    dropped_keys.sort();
+
+    let mut config =
+        json!({
+        "id": soundpack.id,
+        "name": soundpack.name,
+        "key_define_type": if multi { "multi" } else { "single" },
+        "includes_numpad": soundpack.definitions.keys().any(|key| key.starts_with("Numpad")),
+        "defines": defines,
+    });
+    if !multi {
+        let audio_file = soundpack.audio_file.as_ref().ok_or("No audio_file field in soundpack config")?;
+        config["sound"] = json!(audio_file.trim_start_matches("./"));
+    }
+    for (field, value) in [
+        ("author", &soundpack.author),
+        ("description", &soundpack.description),
+        ("version", &soundpack.version),
+        ("icon", &soundpack.icon),
+        ("license", &soundpack.license),
+    ] {
+        if let Some(value) = value {
+            config[field] = json!(value);
+        }
+    }
+    if let Some(tags) = &soundpack.tags {
+        config["tags"] = json!(tags);
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::soundpack_installer::{ extract_zip_safely, find_pack_root };
+    use crate::utils::soundpack_validator::{ validate_soundpack_config, SoundpackValidationStatus };
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn write_pack(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        let config =
+            json!({
+            "id": "shared",
+            "name": "Shared",
+            "author": "Tester",
+            "audio_file": "./sound.ogg",
+            "definition_method": "single",
+            "definitions": {
+                "KeyA": { "timing": [[10.0, 60.0], [100.0, 150.0]] },
+                "IntlRo": { "timing": [[200.0, 250.0]] }
+            }
+        });
+        std::fs::write(dir.join("config.json"), config.to_string()).unwrap();
+        std::fs::write(dir.join("sound.ogg"), b"ogg").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"not referenced").unwrap();
+    }
+
+    /// Export, then unpack the ZIP the way the installer does, returns the config path
+    fn round_trip(dir: &Path, layout: ExportLayout) -> (ExportReport, PathBuf) {
+        let zip_path = dir.join("export.zip");
+        let report = export_shareable_zip(&dir.join("shared"), &zip_path, layout).unwrap();
+        let out = dir.join(format!("out-{:?}", layout));
+        let files = extract_zip_safely(&zip_path, &out).unwrap();
+        assert_eq!(files.len(), report.files);
+        let root = find_pack_root(&files).unwrap();
+        assert!(out.join(&root).join("sound.ogg").is_file());
+        assert!(!out.join(&root).join("notes.txt").exists());
+        (report, out.join(root).join("config.json"))
+    }
+
+    #[test]
+    fn v2_export_reads_back() {
+        let dir = std::env::temp_dir().join(format!("mechaura-export-test-{}", Uuid::new_v4()));
+        write_pack(&dir.join("shared"));
+
+        let (report, config_path) = round_trip(&dir, ExportLayout::V2);
+        assert_eq!(report.files, 2);
+        assert!(report.dropped_keys.is_empty());
+        let validation = validate_soundpack_config(&config_path.to_string_lossy());
+        assert_eq!(validation.status, SoundpackValidationStatus::Valid);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mechvibes_export_reads_back() {
+        let dir = std::env::temp_dir().join(format!("mechaura-export-test-{}", Uuid::new_v4()));
+        write_pack(&dir.join("shared"));
+
+        let (report, config_path) = round_trip(&dir, ExportLayout::MechvibesV1);
+        // IntlRo has no IOHook code
+        assert_eq!(report.dropped_keys, vec!["IntlRo".to_string()]);
+        let validation = validate_soundpack_config(&config_path.to_string_lossy());
+        assert_eq!(validation.status, SoundpackValidationStatus::VersionOneNeedsConversion);
+
+        let config: Value = serde_json
+            ::from_str(&std::fs::read_to_string(&config_path).unwrap())
+            .unwrap();
+        assert_eq!(config["sound"], json!("sound.ogg"));
+        // Keydown [10, 60] becomes [start, duration], KeyA is IOHook code 30
+        assert_eq!(config["defines"], json!({ "30": [10.0, 50.0] }));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,8 +1,11 @@
+use crate::debug_print;
 use crate::utils::path;
+use crate::state::soundpack::SoundpackType;
+use crate::utils::soundpack_validator::{ check_timings, clamp_timings, TimingReport };
 use serde_json::Value;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{ Component, Path, PathBuf };
 use uuid::Uuid;
 use zip::ZipArchive;
 
@@ -11,6 +14,22 @@ use zip::ZipArchive;
 pub struct SoundpackInfo {
     pub name: String,
     pub id: String,
+    pub soundpack_type: SoundpackType,
+}
+
+impl SoundpackInfo {
+    /// ID of the installed pack in the soundpack cache, `<type>/<folder>`
+    pub fn cache_id(&self) -> String {
+        format!("{}/{}", self.soundpack_type.folder_name(), self.id)
+    }
+}
+
+/// Result of an install that is not allowed to replace an installed pack
+#[derive(Debug, Clone)]
+pub enum InstallOutcome {
+    Installed(SoundpackInfo),
+    /// A pack of the same type and ID is installed, nothing was changed
+    AlreadyInstalled(SoundpackInfo),
 }
 
 /// Check if a soundpack ID already exists in the app state
@@ -28,14 +47,17 @@ pub fn get_soundpack_id_from_zip(file_path: &str) -> Result<String, String> {
         format!("Failed to read ZIP archive: {}", e)
     )?;
 
-    // Find config.json to determine soundpack ID
+    // Find the root config.json to determine soundpack ID
+    let names: Vec<PathBuf> = (0..archive.len())
+        .map(|i| archive.by_index(i).ok().and_then(|file| file.enclosed_name()).unwrap_or_default())
+        .collect();
+    let root_config = find_pack_root(&names).map(|root| root.join("config.json"));
     for i in 0..archive.len() {
         let mut file = archive
             .by_index(i)
             .map_err(|e| format!("Failed to read archive entry: {}", e))?;
-        let file_path = file.name().to_string();
 
-        if file_path.ends_with("config.json") {
+        if file.enclosed_name().is_some() && file.enclosed_name() == root_config {
             let mut config_content = String::new();
             file
                 .read_to_string(&mut config_content)
@@ -61,181 +83,255 @@ pub fn get_soundpack_id_from_zip(file_path: &str) -> Result<String, String> {
     Err("No config.json found in ZIP file".to_string())
 }
 
-/// Extract and install soundpack from ZIP file
-pub fn extract_and_install_soundpack(file_path: &str) -> Result<SoundpackInfo, String> {
-    // Open ZIP file
-    let file = File::open(file_path).map_err(|e| format!("Failed to open ZIP file: {}", e))?;
-    let mut archive = ZipArchive::new(file).map_err(|e|
-        format!("Failed to read ZIP archive: {}", e)
-    )?;
-
-    // Find config.json to determine soundpack info
-    let mut config_content = String::new();
-    let mut soundpack_id = String::new();
-    let mut found_config = false;
-
-    // First pass: find and read config.json
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
-        let file_path = file.name().to_string();
+/// What an archive may unpack to
+struct ZipLimits {
+    max_entries: usize,
+    max_unpacked_size: u64,
+    max_compression_ratio: u64,
+    // Small files (configs, silence) compress extremely well, only check the ratio above this size
+    ratio_check_min_size: u64,
+}
 
-        // Look for config.json in any directory level
-        if file_path.ends_with("config.json") {
-            file
-                .read_to_string(&mut config_content)
-                .map_err(|e| format!("Failed to read config.json: {}", e))?;
-            found_config = true;
-            break;
-        }
+// Limits for archives from the internet, generous enough for any real soundpack
+const ZIP_LIMITS: ZipLimits = ZipLimits {
+    max_entries: 4096,
+    max_unpacked_size: 1024 * 1024 * 1024, // 1 GiB
+    max_compression_ratio: 200,
+    ratio_check_min_size: 1024 * 1024,
+};
+
+/// Temporary folder that is removed again unless it was moved into place
+struct StagingDir(PathBuf);
+
+impl StagingDir {
+    fn new(parent: &Path) -> Result<Self, String> {
+        // Next to the install location so the final rename never crosses file systems
+        let dir = parent.join(format!(".staging-{}", Uuid::new_v4()));
+        path
+            ::ensure_directory_exists(&dir)
+            .map_err(|e| format!("Failed to create staging directory: {}", e))?;
+        Ok(Self(dir))
     }
+}
 
-    if !found_config {
-        return Err("No config.json found in ZIP file".to_string());
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
     }
+}
 
-    // Parse config to get soundpack info
-    let mut config: Value = serde_json
-        ::from_str(&config_content)
-        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
-
-    let soundpack_name = config
-        .get("name")
-        .and_then(|v| v.as_str())
-        .unwrap_or("Unknown Soundpack")
-        .to_string();
-
-    // Extract ID from config content only
-    if let Some(id) = config.get("id").and_then(|v| v.as_str()) {
-        if !id.trim().is_empty() {
-            soundpack_id = id.to_string();
-        }
-    } // If no ID in config, generate a UUID-based ID
-    if soundpack_id.is_empty() {
-        soundpack_id = format!("imported-{}", Uuid::new_v4());
-
-        // Add the generated ID to the config
-        config["id"] = Value::String(soundpack_id.clone());
-    } // Determine soundpack type from config
-    let is_mouse_soundpack = determine_soundpack_type(&config);
-    let soundpack_type = if is_mouse_soundpack { "mouse" } else { "keyboard" };
-
-    // Determine installation directory using soundpack type and ID
-    // Custom soundpacks go to system app data directory
-    let soundpacks_dir = crate::state::paths::soundpacks::get_custom_soundpacks_dir();
-    let install_dir = soundpacks_dir.join(soundpack_type).join(&soundpack_id);
+/// Extract every entry of a ZIP below `target`, returns the extracted file paths relative to it
+/// Rejects entries that escape `target`, symlinks, and archives over the count, size or ratio limits
+pub(crate) fn extract_zip_safely(zip_path: &Path, target: &Path) -> Result<Vec<PathBuf>, String> {
+    extract_zip_with_limits(zip_path, target, &ZIP_LIMITS)
+}
 
-    // Create installation directory
-    path
-        ::ensure_directory_exists(&install_dir)
-        .map_err(|e| format!("Failed to create soundpack directory: {}", e))?;
+fn extract_zip_with_limits(
+    zip_path: &Path,
+    target: &Path,
+    limits: &ZipLimits
+) -> Result<Vec<PathBuf>, String> {
+    let file = File::open(zip_path).map_err(|e| format!("Failed to open ZIP file: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e|
+        format!("Failed to read ZIP archive: {}", e)
+    )?;
 
-    // Extract all files
-    let mut archive = ZipArchive::new(
-        File::open(file_path).map_err(|e| format!("Failed to reopen ZIP: {}", e))?
-    ).map_err(|e| format!("Failed to reread ZIP archive: {}", e))?;
+    if archive.len() > limits.max_entries {
+        return Err(
+            format!("ZIP has {} entries, at most {} are allowed", archive.len(), limits.max_entries)
+        );
+    }
 
+    let mut extracted = Vec::new();
+    let mut unpacked_total: u64 = 0;
     for i in 0..archive.len() {
-        let mut file = archive
+        let mut entry = archive
             .by_index(i)
             .map_err(|e| format!("Failed to read archive entry: {}", e))?;
-        let file_path = file.name().to_string();
 
-        // Skip directories
-        if file_path.ends_with('/') {
+        // Rejects absolute paths, drive letters and ../ components
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(format!("Unsafe path in archive: {}", entry.name()));
+        };
+        if entry.is_symlink() {
+            return Err(format!("Symbolic links are not allowed in soundpacks: {}", entry.name()));
+        }
+
+        let output_path = target.join(&relative);
+        if entry.is_dir() {
+            path
+                ::ensure_directory_exists(&output_path)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
             continue;
         }
 
-        // Determine output path - strip the first directory level if it exists and place all files at root
-        let output_path = if file_path.contains('/') {
-            let parts: Vec<&str> = file_path.split('/').collect();
-            if parts.len() > 1 {
-                // Skip the first part (directory) and join the rest directly to install_dir
-                install_dir.join(parts[1..].join("/"))
-            } else {
-                install_dir.join(&file_path)
-            }
-        } else {
-            install_dir.join(&file_path)
-        };
+        // Declared sizes can lie, they only allow rejecting obvious bombs early
+        if
+            entry.size() > limits.ratio_check_min_size &&
+            entry.size() / entry.compressed_size().max(1) > limits.max_compression_ratio
+        {
+            return Err(format!("Suspicious compression ratio for {}", relative.display()));
+        }
 
-        // Create parent directories if needed
         if let Some(parent) = output_path.parent() {
             path
                 ::ensure_directory_exists(parent)
                 .map_err(|e| format!("Failed to create directory: {}", e))?;
         }
-
-        // Extract file
         let mut output_file = File::create(&output_path).map_err(|e|
             format!("Failed to create file: {}", e)
         )?;
-        std::io
-            ::copy(&mut file, &mut output_file)
+
+        // Never write more than the remaining budget, whatever the headers say
+        let budget = limits.max_unpacked_size - unpacked_total;
+        let written = std::io
+            ::copy(&mut (&mut entry).take(budget + 1), &mut output_file)
             .map_err(|e| format!("Failed to extract file: {}", e))?;
-    } // Now handle V1 to V2 conversion after files are extracted
-    println!(
-        "🔧 Converting config after file extraction - soundpack_dir: {}",
-        install_dir.to_string_lossy()
-    );
-    let final_config_content = handle_config_conversion(
-        &config.to_string(),
-        &soundpack_id,
-        &install_dir.to_string_lossy()
-    )?; // Write the final config.json at the root level of the soundpack directory
-    let config_path = install_dir.join("config.json");
-    path
-        ::write_file_contents(&config_path.to_string_lossy(), &final_config_content)
-        .map_err(|e| format!("Failed to write config.json: {}", e))?;
+        if written > budget {
+            return Err(
+                format!("ZIP unpacks to more than {} MB", limits.max_unpacked_size / (1024 * 1024))
+            );
+        }
+        if
+            written > limits.ratio_check_min_size &&
+            written / entry.compressed_size().max(1) > limits.max_compression_ratio
+        {
+            return Err(format!("Suspicious compression ratio for {}", relative.display()));
+        }
+        unpacked_total += written;
+        extracted.push(relative);
+    }
+    Ok(extracted)
+}
 
-    Ok(SoundpackInfo {
-        name: soundpack_name,
-        id: soundpack_id,
-    })
+/// Folder of the shallowest config.json, which is the soundpack root
+pub(crate) fn find_pack_root(files: &[PathBuf]) -> Option<PathBuf> {
+    files
+        .iter()
+        .filter(|file| file.file_name().is_some_and(|name| name == "config.json"))
+        .min_by_key(|file| file.components().count())
+        .map(|file| file.parent().map(Path::to_path_buf).unwrap_or_default())
 }
 
-/// Extract and install soundpack from ZIP file with specified target type
-pub fn extract_and_install_soundpack_with_type(
+/// The soundpack ID becomes a folder name, so it has to be a single plain path segment
+fn check_soundpack_id(soundpack_id: &str) -> Result<(), String> {
+    let mut components = Path::new(soundpack_id).components();
+    let is_plain =
+        matches!(components.next(), Some(Component::Normal(_))) &&
+        components.next().is_none() &&
+        !soundpack_id.contains(['/', '\\', ':']);
+    if is_plain {
+        Ok(())
+    } else {
+        Err(format!("Invalid soundpack ID: {}", soundpack_id))
+    }
+}
+
+/// Unpack a ZIP into a staging folder and read its config
+/// Returns the staging guard, the soundpack root inside it and the parsed config
+fn stage_soundpack_zip(
     file_path: &str,
-    target_type: Option<crate::state::soundpack::SoundpackType>
-) -> Result<SoundpackInfo, String> {
-    // Open ZIP file
-    let file = File::open(file_path).map_err(|e| format!("Failed to open ZIP file: {}", e))?;
-    let mut archive = ZipArchive::new(file).map_err(|e|
-        format!("Failed to read ZIP archive: {}", e)
-    )?;
+    soundpacks_dir: &Path,
+    flatten: bool
+) -> Result<(StagingDir, PathBuf, Value), String> {
+    let staging = StagingDir::new(soundpacks_dir)?;
+    let raw_dir = staging.0.join("raw");
+    let files = extract_zip_safely(Path::new(file_path), &raw_dir)?;
+    let root = find_pack_root(&files).ok_or_else(|| "No config.json found in ZIP file".to_string())?;
+
+    let pack_dir = if flatten {
+        // Every file goes to the pack root, the config references bare file names
+        let pack_dir = staging.0.join("pack");
+        path
+            ::ensure_directory_exists(&pack_dir)
+            .map_err(|e| format!("Failed to create soundpack directory: {}", e))?;
+        let root_config = root.join("config.json");
+        for file in &files {
+            // Only the root config.json, nested ones would overwrite it
+            if file.file_name().is_some_and(|name| name == "config.json") && *file != root_config {
+                continue;
+            }
+            if let Some(file_name) = file.file_name() {
+                let destination = pack_dir.join(file_name);
+                let _ = std::fs::remove_file(&destination);
+                std::fs
+                    ::rename(raw_dir.join(file), destination)
+                    .map_err(|e| format!("Failed to stage {}: {}", file.display(), e))?;
+            }
+        }
+        pack_dir
+    } else {
+        // Files outside the soundpack root are left behind in the staging folder
+        raw_dir.join(&root)
+    };
 
-    // Find config.json to determine soundpack info
-    let mut config_content = String::new();
-    let mut soundpack_id = String::new();
-    let mut found_config = false;
+    let config_content = std::fs
+        ::read_to_string(pack_dir.join("config.json"))
+        .map_err(|e| format!("Failed to read config.json: {}", e))?;
+    let config: Value = serde_json
+        ::from_str(&config_content)
+        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
 
-    // First pass: find and read config.json
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
-        let file_path = file.name().to_string();
+    Ok((staging, pack_dir, config))
+}
 
-        // Look for config.json in any directory level
-        if file_path.ends_with("config.json") {
-            file
-                .read_to_string(&mut config_content)
-                .map_err(|e| format!("Failed to read config.json: {}", e))?;
-            found_config = true;
-            break;
+/// Check the staged pack and move it to `install_dir` in one rename
+/// An existing install is only removed once the new one is in place
+fn commit_staged_soundpack(
+    staging: &StagingDir,
+    pack_dir: &Path,
+    install_dir: &Path
+) -> Result<(), String> {
+    use crate::utils::soundpack_validator::SoundpackValidationStatus;
+
+    let validation = crate::utils::soundpack_validator::validate_soundpack_config(
+        &pack_dir.join("config.json").to_string_lossy()
+    );
+    if
+        !matches!(
+            validation.status,
+            SoundpackValidationStatus::Valid | SoundpackValidationStatus::VersionOneNeedsConversion
+        )
+    {
+        return Err(format!("Invalid soundpack: {}", validation.message));
+    }
+
+    if let Some(parent) = install_dir.parent() {
+        path
+            ::ensure_directory_exists(parent)
+            .map_err(|e| format!("Failed to create soundpack directory: {}", e))?;
+    }
+
+    let previous = staging.0.join("previous");
+    let replacing = install_dir.exists();
+    if replacing {
+        std::fs
+            ::rename(install_dir, &previous)
+            .map_err(|e| format!("Failed to move the existing soundpack aside: {}", e))?;
+    }
+    if let Err(e) = std::fs::rename(pack_dir, install_dir) {
+        if replacing {
+            let _ = std::fs::rename(&previous, install_dir);
         }
+        return Err(format!("Failed to install soundpack: {}", e));
     }
+    // The previous copy is deleted together with the staging folder
+    Ok(())
+}
 
-    if !found_config {
-        return Err("No config.json found in ZIP file".to_string());
+/// Extract and install soundpack from ZIP file, replacing an installed copy
+pub fn extract_and_install_soundpack(file_path: &str) -> Result<SoundpackInfo, String> {
+    match install_soundpack_zip(file_path, true)? {
+        InstallOutcome::Installed(info) | InstallOutcome::AlreadyInstalled(info) => Ok(info),
     }
+}
 
-    // Parse config to get soundpack info
-    let mut config: Value = serde_json
-        ::from_str(&config_content)
-        .map_err(|e| format!("Failed to parse config.json: {}", e))?;
+/// Extract and install soundpack from ZIP file
+/// Without `replace`, an installed pack with the same type and ID is left alone
+pub fn install_soundpack_zip(file_path: &str, replace: bool) -> Result<InstallOutcome, String> {
+    // Custom soundpacks go to system app data directory
+    let soundpacks_dir = crate::state::paths::soundpacks::get_custom_soundpacks_dir();
+    let (staging, pack_dir, mut config) = stage_soundpack_zip(file_path, &soundpacks_dir, false)?;
 
     let soundpack_name = config
         .get("name")
@@ -243,89 +339,98 @@ pub fn extract_and_install_soundpack_with_type(
         .unwrap_or("Unknown Soundpack")
         .to_string();
 
-    // Extract ID from config content only
-    if let Some(id) = config.get("id").and_then(|v| v.as_str()) {
-        if !id.trim().is_empty() {
-            soundpack_id = id.to_string();
-        }
-    }
-
-    // If no ID in config, generate a UUID-based ID
-    if soundpack_id.is_empty() {
-        soundpack_id = format!("imported-{}", Uuid::new_v4());
-        // Add the generated ID to the config
-        config["id"] = Value::String(soundpack_id.clone());
-    }
-
-    // Determine soundpack type - use target type if provided, otherwise auto-detect
-    let soundpack_type = if let Some(target) = target_type {
-        match target {
-            crate::state::soundpack::SoundpackType::Keyboard => "keyboard",
-            crate::state::soundpack::SoundpackType::Mouse => "mouse",
-        }
-    } else {
-        // Auto-detect from config
-        let is_mouse_soundpack = determine_soundpack_type(&config);
-        if is_mouse_soundpack {
-            "mouse"
-        } else {
-            "keyboard"
+    // Extract ID from config content, or generate a UUID-based one
+    let soundpack_id = match config.get("id").and_then(|v| v.as_str()) {
+        Some(id) if !id.trim().is_empty() => id.to_string(),
+        _ => {
+            let id = format!("imported-{}", Uuid::new_v4());
+            // Add the generated ID to the config
+            config["id"] = Value::String(id.clone());
+            id
         }
     };
+    check_soundpack_id(&soundpack_id)?;
 
     // Determine installation directory using soundpack type and ID
-    // Custom soundpacks go to system app data directory
-    let soundpacks_dir = crate::state::paths::soundpacks::get_custom_soundpacks_dir();
-    let install_dir = soundpacks_dir.join(soundpack_type).join(&soundpack_id);
+    let soundpack_type = determine_soundpack_type(&config);
+    let install_dir = soundpacks_dir.join(soundpack_type.folder_name()).join(&soundpack_id);
+    let info = SoundpackInfo {
+        name: soundpack_name,
+        id: soundpack_id.clone(),
+        soundpack_type,
+    };
+    if !replace && install_dir.exists() {
+        return Ok(InstallOutcome::AlreadyInstalled(info));
+    }
 
-    // Create installation directory
+    // Handle V1 to V2 conversion inside the staging folder
+    debug_print!(
+        "🔧 Converting config after file extraction - soundpack_dir: {}",
+        pack_dir.to_string_lossy()
+    );
+    let final_config_content = handle_config_conversion(
+        &config.to_string(),
+        &soundpack_id,
+        &pack_dir.to_string_lossy()
+    )?; // Write the final config.json at the root level of the soundpack directory
+    let config_path = pack_dir.join("config.json");
     path
-        ::ensure_directory_exists(&install_dir)
-        .map_err(|e| format!("Failed to create soundpack directory: {}", e))?;
+        ::write_file_contents(&config_path.to_string_lossy(), &final_config_content)
+        .map_err(|e| format!("Failed to write config.json: {}", e))?;
 
-    // Extract all files
-    let mut archive = ZipArchive::new(
-        File::open(file_path).map_err(|e| format!("Failed to reopen ZIP: {}", e))?
-    ).map_err(|e| format!("Failed to reread ZIP archive: {}", e))?;
+    commit_staged_soundpack(&staging, &pack_dir, &install_dir)?;
 
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .map_err(|e| format!("Failed to read archive entry: {}", e))?;
-        let file_path = file.name().to_string();
+    Ok(InstallOutcome::Installed(info))
+}
 
-        // Skip directories
-        if file_path.ends_with('/') {
-            continue;
-        }
+/// Check the timings of a soundpack ZIP against its audio without installing it
+pub fn check_zip_timings(file_path: &str) -> Result<TimingReport, String> {
+    // Same layout `extract_and_install_soundpack_with_type` installs
+    let (_staging, pack_dir, config) = stage_soundpack_zip(file_path, &std::env::temp_dir(), true)?;
+    check_timings(&pack_dir, &config)
+}
 
-        // Determine output path - strip the first directory level if it exists and place all files at root
-        let output_path = if file_path.contains('/') {
-            // Get the filename only (remove directory structure)
-            let filename = file_path.split('/').last().unwrap_or(&file_path);
-            install_dir.join(filename)
-        } else {
-            install_dir.join(&file_path)
-        };
+/// Extract and install soundpack from ZIP file with specified target type
+/// With `fix_timings`, the report `check_zip_timings` made for this ZIP, segments running past the
+/// audio or into their keyup are clamped first
+pub fn extract_and_install_soundpack_with_type(
+    file_path: &str,
+    target_type: Option<SoundpackType>,
+    fix_timings: Option<&TimingReport>
+) -> Result<SoundpackInfo, String> {
+    // Custom soundpacks go to system app data directory
+    let soundpacks_dir = crate::state::paths::soundpacks::get_custom_soundpacks_dir();
+    let (staging, pack_dir, mut config) = stage_soundpack_zip(file_path, &soundpacks_dir, true)?;
 
-        // Create parent directory if needed
-        if let Some(parent) = output_path.parent() {
-            path
-                ::ensure_directory_exists(parent)
-                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+    let soundpack_name = config
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("Unknown Soundpack")
+        .to_string();
+
+    // Extract ID from config content, or generate a UUID-based one
+    let soundpack_id = match config.get("id").and_then(|v| v.as_str()) {
+        Some(id) if !id.trim().is_empty() => id.to_string(),
+        _ => {
+            let id = format!("imported-{}", Uuid::new_v4());
+            // Add the generated ID to the config
+            config["id"] = Value::String(id.clone());
+            id
         }
+    };
+    check_soundpack_id(&soundpack_id)?;
 
-        // Extract file
-        let mut output_file = File::create(&output_path).map_err(|e|
-            format!("Failed to create output file: {}", e)
-        )?;
-        std::io
-            ::copy(&mut file, &mut output_file)
-            .map_err(|e| format!("Failed to extract file: {}", e))?;
+    // Determine soundpack type - use target type if provided, otherwise auto-detect
+    let soundpack_type = target_type.unwrap_or_else(|| determine_soundpack_type(&config));
+    let install_dir = soundpacks_dir.join(soundpack_type.folder_name()).join(&soundpack_id);
+
+    if let Some(report) = fix_timings {
+        let changed = clamp_timings(&mut config, report);
+        debug_print!("✂️ Clamped {} timings of soundpack {}", changed, soundpack_id);
     }
 
-    // Write updated config.json with ID if it was generated
-    let config_path = install_dir.join("config.json");
+    // Write updated config.json with ID or fixed timings
+    let config_path = pack_dir.join("config.json");
     let updated_config = serde_json
         ::to_string_pretty(&config)
         .map_err(|e| format!("Failed to serialize updated config: {}", e))?;
@@ -333,9 +438,12 @@ pub fn extract_and_install_soundpack_with_type(
         ::write(&config_path, updated_config)
         .map_err(|e| format!("Failed to write updated config.json: {}", e))?;
 
+    commit_staged_soundpack(&staging, &pack_dir, &install_dir)?;
+
     Ok(SoundpackInfo {
         name: soundpack_name,
         id: soundpack_id,
+        soundpack_type,
     })
 }
 
@@ -360,20 +468,20 @@ fn handle_config_conversion(
 
     let mut final_config_content = config_content.to_string();
 
-    println!("⚒️ Soundpack validation result: {:?}", validation_result);
+    debug_print!("⚒️ Soundpack validation result: {:?}", validation_result);
 
     if
         validation_result.status ==
         crate::utils::soundpack_validator::SoundpackValidationStatus::VersionOneNeedsConversion
     {
-        println!("🔄 Converting V1 soundpack '{}' to V2 format during import", soundpack_id);
+        debug_print!("🔄 Converting V1 soundpack '{}' to V2 format during import", soundpack_id);
 
         // Create backup of the original V1 config before conversion
         let config_backup_path = std::path::Path::new(soundpack_dir).join("config.json.v1.backup");
         if let Err(e) = std::fs::write(&config_backup_path, config_content) {
-            println!("⚠️ Failed to create V1 config backup for {}: {}", soundpack_id, e);
+            debug_print!("⚠️ Failed to create V1 config backup for {}: {}", soundpack_id, e);
         } else {
-            println!("💾 Created V1 config backup at: {}", config_backup_path.display());
+            debug_print!("💾 Created V1 config backup at: {}", config_backup_path.display());
         }
 
         // Convert V1 to V2 format
@@ -395,7 +503,7 @@ fn handle_config_conversion(
                     ::read_to_string(&temp_output)
                     .map_err(|e| format!("Failed to read converted config: {}", e))?;
 
-                println!("✅ Successfully converted {} from V1 to V2 during import", soundpack_id);
+                debug_print!("✅ Successfully converted {} from V1 to V2 during import", soundpack_id);
 
                 // Clean up temp files
                 let _ = std::fs::remove_file(&temp_input);
@@ -416,29 +524,236 @@ fn handle_config_conversion(
     Ok(final_config_content)
 }
 
-fn determine_soundpack_type(config: &serde_json::Value) -> bool {
+pub(crate) fn determine_soundpack_type(config: &serde_json::Value) -> SoundpackType {
     // Check for explicit type field
-    if let Some(soundpack_type) = config.get("type") {
-        if let Some(type_str) = soundpack_type.as_str() {
-            return type_str == "mouse";
+    for field in ["type", "soundpack_type"] {
+        if let Some(type_str) = config.get(field).and_then(|v| v.as_str()) {
+            match type_str.to_lowercase().as_str() {
+                "mouse" => {
+                    return SoundpackType::Mouse;
+                }
+                "gamepad" => {
+                    return SoundpackType::Gamepad;
+                }
+                _ => {}
+            }
         }
     }
 
-    // Check if defs contain mouse-specific keys
-    if let Some(defs) = config.get("defs") {
-        if let Some(defs_obj) = defs.as_object() {
+    // Check if defs contain mouse- or gamepad-specific keys
+    for field in ["defs", "definitions"] {
+        if let Some(defs_obj) = config.get(field).and_then(|v| v.as_object()) {
             for key in defs_obj.keys() {
+                if key.starts_with("Gamepad") || key.starts_with("Joystick") {
+                    return SoundpackType::Gamepad;
+                }
                 if
                     key.starts_with("Mouse") ||
                     key.starts_with("Button") ||
                     key.starts_with("Wheel")
                 {
-                    return true;
+                    return SoundpackType::Mouse;
                 }
             }
         }
     }
 
     // Default to keyboard
-    false
+    SoundpackType::Keyboard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::CompressionMethod;
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        write_zip_with(path, entries, CompressionMethod::Deflated);
+    }
+
+    fn write_zip_with(path: &Path, entries: &[(&str, &[u8])], method: CompressionMethod) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(method);
+        for (name, contents) in entries {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn test_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mechaura-zip-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Same checks as `ZIP_LIMITS`, small enough to trip in a test
+    const TEST_LIMITS: ZipLimits = ZipLimits {
+        max_entries: 4,
+        max_unpacked_size: 10 * 1024,
+        max_compression_ratio: 20,
+        ratio_check_min_size: 1024,
+    };
+
+    const VALID_CONFIG: &[u8] =
+        br#"{"id":"pack","name":"Pack","author":"Tester","audio_file":"sound.ogg","definition_method":"single","definitions":{"KeyA":{"timing":[[0,50]]}}}"#;
+
+    #[test]
+    fn extracts_below_target_and_finds_root() {
+        let dir = std::env::temp_dir().join(format!("mechaura-zip-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let zip_path = dir.join("pack.zip");
+        write_zip(&zip_path, &[
+            ("pack/config.json", b"{}"),
+            ("pack/sound.ogg", b"ogg"),
+            ("pack/extra/config.json", b"{}"),
+        ]);
+
+        let files = extract_zip_safely(&zip_path, &dir.join("out")).unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(find_pack_root(&files), Some(PathBuf::from("pack")));
+        assert!(dir.join("out/pack/sound.ogg").is_file());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_path_traversal() {
+        let dir = std::env::temp_dir().join(format!("mechaura-zip-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let zip_path = dir.join("evil.zip");
+        write_zip(&zip_path, &[
+            ("pack/config.json", b"{}"),
+            ("pack/../../escaped.txt", b"nope"),
+        ]);
+
+        assert!(extract_zip_safely(&zip_path, &dir.join("out")).is_err());
+        assert!(!dir.join("escaped.txt").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn soundpack_id_must_be_one_segment() {
+        assert!(check_soundpack_id("cherrymx-blue").is_ok());
+        assert!(check_soundpack_id("../evil").is_err());
+        assert!(check_soundpack_id("a/b").is_err());
+        assert!(check_soundpack_id("..").is_err());
+    }
+
+    #[test]
+    fn rejects_too_many_entries() {
+        let dir = test_dir();
+        let zip_path = dir.join("many.zip");
+        let names: Vec<String> = (0..5).map(|i| format!("pack/{}.ogg", i)).collect();
+        let entries: Vec<(&str, &[u8])> = names
+            .iter()
+            .map(|name| (name.as_str(), b"ogg" as &[u8]))
+            .collect();
+        write_zip(&zip_path, &entries);
+
+        let error = extract_zip_with_limits(&zip_path, &dir.join("out"), &TEST_LIMITS).unwrap_err();
+        assert!(error.contains("5 entries"), "{}", error);
+        assert!(!dir.join("out").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_archives_over_the_unpacked_size() {
+        let dir = test_dir();
+        let zip_path = dir.join("big.zip");
+        // Stored, so only the total size can trip
+        let chunk = vec![7u8; 4 * 1024];
+        write_zip_with(
+            &zip_path,
+            &[("pack/a.ogg", &chunk), ("pack/b.ogg", &chunk), ("pack/c.ogg", &chunk)],
+            CompressionMethod::Stored
+        );
+
+        let error = extract_zip_with_limits(&zip_path, &dir.join("out"), &TEST_LIMITS).unwrap_err();
+        assert!(error.contains("unpacks to more than"), "{}", error);
+        // Never more than the budget on disk
+        let written: u64 = ["a", "b", "c"]
+            .iter()
+            .filter_map(|name| std::fs::metadata(dir.join(format!("out/pack/{}.ogg", name))).ok())
+            .map(|metadata| metadata.len())
+            .sum();
+        assert!(written <= TEST_LIMITS.max_unpacked_size + 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_suspicious_compression_ratio() {
+        let dir = test_dir();
+        let zip_path = dir.join("bomb.zip");
+        // Zeros deflate to almost nothing
+        write_zip(&zip_path, &[("pack/config.json", b"{}"), ("pack/zeros.ogg", &[0u8; 8 * 1024])]);
+
+        let error = extract_zip_with_limits(&zip_path, &dir.join("out"), &TEST_LIMITS).unwrap_err();
+        assert!(error.contains("compression ratio"), "{}", error);
+
+        // Below the minimum size the ratio is not checked
+        let small_path = dir.join("small.zip");
+        write_zip(&small_path, &[("pack/config.json", b"{}"), ("pack/zeros.ogg", &[0u8; 512])]);
+        assert!(extract_zip_with_limits(&small_path, &dir.join("small"), &TEST_LIMITS).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_symlinks() {
+        let dir = test_dir();
+        let zip_path = dir.join("link.zip");
+        let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        zip.start_file("pack/config.json", SimpleFileOptions::default()).unwrap();
+        zip.write_all(b"{}").unwrap();
+        zip.add_symlink("pack/sound.ogg", "/etc/passwd", SimpleFileOptions::default()).unwrap();
+        zip.finish().unwrap();
+
+        let error = extract_zip_safely(&zip_path, &dir.join("out")).unwrap_err();
+        assert!(error.contains("Symbolic links"), "{}", error);
+        assert!(std::fs::symlink_metadata(dir.join("out/pack/sound.ogg")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn staging_dirs(dir: &Path) -> Vec<PathBuf> {
+        std::fs
+            ::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(".staging-")))
+            .collect()
+    }
+
+    /// Stage and commit the way `install_soundpack_zip` does, into `soundpacks_dir`
+    fn install_into(zip_path: &Path, soundpacks_dir: &Path, install_dir: &Path) -> Result<(), String> {
+        let (staging, pack_dir, _) = stage_soundpack_zip(&zip_path.to_string_lossy(), soundpacks_dir, false)?;
+        commit_staged_soundpack(&staging, &pack_dir, install_dir)
+    }
+
+    #[test]
+    fn failed_install_keeps_the_installed_pack() {
+        let dir = test_dir();
+        let install_dir = dir.join("keyboard").join("pack");
+        std::fs::create_dir_all(&install_dir).unwrap();
+        std::fs::write(install_dir.join("config.json"), VALID_CONFIG).unwrap();
+        std::fs::write(install_dir.join("edited.ogg"), b"local edit").unwrap();
+
+        // Missing author and definitions, rejected before anything is moved
+        let broken = dir.join("broken.zip");
+        write_zip(&broken, &[("pack/config.json", br#"{"name":"Broken"}"#), ("pack/sound.ogg", b"ogg")]);
+        assert!(install_into(&broken, &dir, &install_dir).is_err());
+        assert_eq!(std::fs::read(install_dir.join("config.json")).unwrap(), VALID_CONFIG);
+        assert!(install_dir.join("edited.ogg").is_file());
+        assert!(staging_dirs(&dir).is_empty());
+
+        // A valid pack replaces it, and the previous copy goes with the staging folder
+        let valid = dir.join("valid.zip");
+        write_zip(&valid, &[("pack/config.json", VALID_CONFIG), ("pack/sound.ogg", b"ogg")]);
+        install_into(&valid, &dir, &install_dir).unwrap();
+        assert!(install_dir.join("sound.ogg").is_file());
+        assert!(!install_dir.join("edited.ogg").exists());
+        assert!(staging_dirs(&dir).is_empty());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
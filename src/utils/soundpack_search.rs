@@ -0,0 +1,206 @@
+/// Search, tag filtering and sorting for the soundpack selectors
+///
+/// Matching is fuzzy: every character of a search term has to appear in order in the pack's
+/// name, author or one of its tags, with runs and word starts scoring higher. Each
+/// whitespace-separated term has to match somewhere.
+use crate::state::config::{ SoundpackBrowserConfig, SoundpackSort };
+use crate::state::soundpack::SoundpackMetadata;
+use std::cmp::Reverse;
+
+/// Score of `query` as a subsequence of `text`, None when it does not match
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let query = query.to_lowercase();
+    let text = text.to_lowercase();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in query.chars() {
+        let found = position + chars[position..].iter().position(|&t| t == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 4;
+        }
+        if found == 0 || !chars[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+
+    if text.starts_with(&query) {
+        score += 10;
+    } else if text.contains(&query) {
+        score += 5;
+    }
+    Some(score)
+}
+
+/// Score of a pack for a search query, names count double
+pub fn pack_score(pack: &SoundpackMetadata, query: &str) -> Option<u32> {
+    query
+        .split_whitespace()
+        .map(|term| {
+            let name = fuzzy_score(term, &pack.name).map(|score| score * 2);
+            let author = pack.author.as_deref().and_then(|author| fuzzy_score(term, author));
+            let tags = pack.tags
+                .iter()
+                .filter_map(|tag| fuzzy_score(term, tag))
+                .max();
+            [name, author, tags].into_iter().flatten().max()
+        })
+        .sum()
+}
+
+fn has_tags(pack: &SoundpackMetadata, tags: &[String]) -> bool {
+    tags.iter().all(|tag| pack.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+}
+
+/// Packs matching the query and selected tags: favorites first, then by relevance when
+/// searching, then by the chosen sort
+pub fn browse(
+    packs: Vec<SoundpackMetadata>,
+    query: &str,
+    browser: &SoundpackBrowserConfig
+) -> Vec<SoundpackMetadata> {
+    let mut results: Vec<(u32, SoundpackMetadata)> = packs
+        .into_iter()
+        .filter(|pack| has_tags(pack, &browser.tags))
+        .filter_map(|pack| Some((pack_score(&pack, query)?, pack)))
+        .collect();
+
+    // Stable sorts, the first applied ends up least significant
+    match browser.sort {
+        SoundpackSort::Recent => {
+            results.sort_by_key(|(_, pack)| Reverse((pack.last_accessed, pack.last_modified)));
+            results.sort_by_key(|(_, pack)| {
+                browser.recent
+                    .iter()
+                    .position(|id| id == &pack.folder_path)
+                    .unwrap_or(usize::MAX)
+            });
+        }
+        SoundpackSort::Name => {
+            results.sort_by_key(|(_, pack)| pack.name.to_lowercase());
+        }
+        SoundpackSort::Author => {
+            results.sort_by_key(|(_, pack)| {
+                let author = pack.author.as_deref().map(|author| author.to_lowercase());
+                (author.is_none(), author, pack.name.to_lowercase())
+            });
+        }
+    }
+    results.sort_by_key(|(score, _)| Reverse(*score));
+    results.sort_by_key(|(_, pack)| !browser.is_favorite(&pack.folder_path));
+
+    results
+        .into_iter()
+        .map(|(_, pack)| pack)
+        .collect()
+}
+
+/// Tags used by the packs with their counts, most common first
+pub fn tag_facets(packs: &[SoundpackMetadata]) -> Vec<(String, usize)> {
+    let mut facets: Vec<(String, usize)> = Vec::new();
+    for tag in packs.iter().flat_map(|pack| &pack.tags) {
+        // "error" marks packs that failed to load, not something to filter by
+        if tag.eq_ignore_ascii_case("error") {
+            continue;
+        }
+        match facets.iter_mut().find(|(existing, _)| existing.eq_ignore_ascii_case(tag)) {
+            Some((_, count)) => {
+                *count += 1;
+            }
+            None => facets.push((tag.clone(), 1)),
+        }
+    }
+    facets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.to_lowercase().cmp(&b.0.to_lowercase())));
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::soundpack::SoundpackType;
+
+    fn pack(id: &str, name: &str, author: Option<&str>, tags: &[&str], last_accessed: u64) -> SoundpackMetadata {
+        SoundpackMetadata {
+            id: id.to_string(),
+            name: name.to_string(),
+            author: author.map(|author| author.to_string()),
+            description: None,
+            version: "1.0.0".to_string(),
+            tags: tags
+                .iter()
+                .map(|tag| tag.to_string())
+                .collect(),
+            icon: None,
+            soundpack_type: SoundpackType::Keyboard,
+            folder_path: id.to_string(),
+            last_modified: 0,
+            last_accessed,
+            config_version: Some(2),
+            is_valid_v2: true,
+            validation_status: "valid".to_string(),
+            can_be_converted: false,
+            definition_method: Some("single".to_string()),
+            last_error: None,
+            doctor: None,
+        }
+    }
+
+    fn ids(packs: &[SoundpackMetadata]) -> Vec<&str> {
+        packs
+            .iter()
+            .map(|pack| pack.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_matches_in_order() {
+        assert!(fuzzy_score("crm", "NK Cream").is_some());
+        assert!(fuzzy_score("mrc", "NK Cream").is_none());
+        assert!(fuzzy_score("cream", "NK Cream") > fuzzy_score("cream", "Cherry MX Brown Ream"));
+        assert!(fuzzy_score("nk", "NK Cream") > fuzzy_score("nk", "Banana Keys"));
+    }
+
+    #[test]
+    fn browses_with_favorites_tags_and_sorting() {
+        let packs = vec![
+            pack("keyboard/cream", "NK Cream", Some("Novelkeys"), &["linear"], 10),
+            pack("keyboard/brown", "MX Brown", Some("Cherry"), &["tactile"], 30),
+            pack("keyboard/box", "Box Jade", None, &["clicky", "linear"], 20)
+        ];
+        let mut browser = SoundpackBrowserConfig::default();
+        assert_eq!(ids(&browse(packs.clone(), "", &browser)), [
+            "keyboard/brown",
+            "keyboard/box",
+            "keyboard/cream",
+        ]);
+
+        browser.record_use("keyboard/cream");
+        browser.toggle_favorite("keyboard/box");
+        assert_eq!(ids(&browse(packs.clone(), "", &browser)), [
+            "keyboard/box",
+            "keyboard/cream",
+            "keyboard/brown",
+        ]);
+
+        browser.sort = SoundpackSort::Author;
+        browser.toggle_favorite("keyboard/box");
+        assert_eq!(ids(&browse(packs.clone(), "", &browser)), [
+            "keyboard/brown",
+            "keyboard/cream",
+            "keyboard/box",
+        ]);
+
+        browser.toggle_tag("linear");
+        assert_eq!(ids(&browse(packs.clone(), "", &browser)), ["keyboard/cream", "keyboard/box"]);
+        assert_eq!(ids(&browse(packs.clone(), "novel", &browser)), ["keyboard/cream"]);
+        assert_eq!(tag_facets(&packs)[0], ("linear".to_string(), 2));
+    }
+}
@@ -1,6 +1,9 @@
+use crate::utils::config_converter::load_audio_file_samples;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
+use std::path::Path;
 use uuid::Uuid;
 use zip::ZipArchive;
 
@@ -13,6 +16,19 @@ pub enum SoundpackValidationStatus {
     VersionOneNeedsConversion,
 }
 
+impl SoundpackValidationStatus {
+    /// Short status string stored in `SoundpackMetadata` and printed by `mechaura pack`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoundpackValidationStatus::Valid => "valid",
+            SoundpackValidationStatus::InvalidVersion => "invalid_version",
+            SoundpackValidationStatus::InvalidStructure(_) => "invalid_structure",
+            SoundpackValidationStatus::MissingRequiredFields(_) => "missing_fields",
+            SoundpackValidationStatus::VersionOneNeedsConversion => "v1_needs_conversion",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SoundpackValidationResult {
     pub status: SoundpackValidationStatus,
@@ -57,11 +73,12 @@ pub fn validate_soundpack_config(config_path: &str) -> SoundpackValidationResult
         }
     };
 
-    // Extract version information
-    let config_version = config
-        .get("config_version")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+    // Extract version information, written as a number or a string ("2") depending on the tool
+    let config_version = config.get("config_version").and_then(|v| {
+        v.as_u64()
+            .map(|v| v as u32)
+            .or_else(|| v.as_str().and_then(|s| s.trim().parse::<u32>().ok()))
+    });
     let package_version = config
         .get("version")
         .and_then(|v| v.as_str())
@@ -73,8 +90,8 @@ pub fn validate_soundpack_config(config_path: &str) -> SoundpackValidationResult
     let has_method_field =
         config.get("method").is_some() || config.get("key_define_type").is_some();
 
-    // Check for V2 indicators
-    let has_defs = config.get("defs").is_some();
+    // Check for V2 indicators, "definitions" is what the app itself writes
+    let has_defs = config.get("defs").is_some() || config.get("definitions").is_some();
     let _has_source_field = config.get("source").is_some();
     let has_author = config.get("author").is_some();
 
@@ -140,8 +157,39 @@ fn validate_v2_structure(
         missing_fields.push("author".to_string());
     }
 
-    if !config.get("defs").is_some() {
-        missing_fields.push("defs".to_string());
+    if !config.get("defs").is_some() && !config.get("definitions").is_some() {
+        missing_fields.push("definitions".to_string());
+    }
+
+    // Validate definitions structure: {"Key": {"timing": [[start, end], ...]}}
+    if let Some(definitions) = config.get("definitions") {
+        if let Some(definitions_obj) = definitions.as_object() {
+            for (key, value) in definitions_obj {
+                match value.get("timing").and_then(|t| t.as_array()) {
+                    Some(timings) => {
+                        for (i, timing) in timings.iter().enumerate() {
+                            let valid = timing
+                                .as_array()
+                                .is_some_and(|pair| pair.len() == 2 && pair.iter().all(|v| v.is_number()));
+                            if !valid {
+                                issues.push(format!(
+                                    "Invalid timing for '{}[{}]': expected [start, end]",
+                                    key, i
+                                ));
+                            }
+                        }
+                    }
+                    None => issues.push(format!("Definition '{}' has no timing array", key)),
+                }
+            }
+        } else {
+            issues.push("definitions field should be an object".to_string());
+        }
+
+        let method = config.get("definition_method").and_then(|v| v.as_str()).unwrap_or("single");
+        if method == "single" && config.get("audio_file").and_then(|v| v.as_str()).is_none() {
+            missing_fields.push("audio_file".to_string());
+        }
     }
 
     // Validate defs structure
@@ -323,3 +371,328 @@ pub async fn validate_soundpack_structure(file_path: &str) -> Result<(String, St
         }
     }
 }
+
+// ===== TIMING CHECKS =====
+
+// Peak level below which a segment counts as silent
+const SILENCE_THRESHOLD_DB: f32 = -60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimingIssueKind {
+    OutOfRange,
+    ZeroLength,
+    Overlapping,
+    Silent,
+}
+
+impl TimingIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimingIssueKind::OutOfRange => "out_of_range",
+            TimingIssueKind::ZeroLength => "zero_length",
+            TimingIssueKind::Overlapping => "overlapping",
+            TimingIssueKind::Silent => "silent",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingIssue {
+    pub key: String,
+    pub index: usize, // 0 is keydown, 1 is keyup
+    pub kind: TimingIssueKind,
+    pub message: String,
+    /// New end time that fixes the issue, when clamping can fix it
+    pub clamped_end_ms: Option<f32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TimingReport {
+    pub segments_checked: usize,
+    pub issues: Vec<TimingIssue>,
+}
+
+impl TimingReport {
+    pub fn fixable_count(&self) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.clamped_end_ms.is_some())
+            .count()
+    }
+}
+
+/// One timing entry of the config, V1 `defines` are converted to [start, end]
+struct ConfigSegment {
+    key: String,
+    index: usize,
+    audio_file: String,
+    start_ms: f32,
+    end_ms: f32,
+}
+
+fn config_segments(config: &Value) -> Vec<ConfigSegment> {
+    let mut segments = Vec::new();
+    let main_audio = config
+        .get("audio_file")
+        .or_else(|| config.get("sound"))
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+
+    if let Some(definitions) = config
+        .get("definitions")
+        .or_else(|| config.get("defs"))
+        .and_then(|v| v.as_object())
+    {
+        for (key, definition) in definitions {
+            let audio_file = definition
+                .get("audio_file")
+                .and_then(|v| v.as_str())
+                .unwrap_or(main_audio);
+            let timings = definition.get("timing").and_then(|v| v.as_array());
+            for (index, timing) in timings.into_iter().flatten().enumerate() {
+                segments.push(ConfigSegment {
+                    key: key.clone(),
+                    index,
+                    audio_file: audio_file.to_string(),
+                    start_ms: timing.get(0).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+                    end_ms: timing.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32,
+                });
+            }
+        }
+    } else if config.get("key_define_type").and_then(|v| v.as_str()) != Some("multi") {
+        // V1 single: [start, duration], V1 multi plays whole files and has nothing to check
+        if let Some(defines) = config.get("defines").and_then(|v| v.as_object()) {
+            for (key, timing) in defines {
+                let Some(timing) = timing.as_array() else {
+                    continue;
+                };
+                let start = timing.first().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                let duration = timing.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+                segments.push(ConfigSegment {
+                    key: key.clone(),
+                    index: 0,
+                    audio_file: main_audio.to_string(),
+                    start_ms: start,
+                    end_ms: start + duration,
+                });
+            }
+        }
+    }
+    segments
+}
+
+/// Decode the audio a config references and check every timing against it
+pub fn check_timings(soundpack_dir: &Path, config: &Value) -> Result<TimingReport, String> {
+    let segments = config_segments(config);
+    let mut report = TimingReport { segments_checked: segments.len(), issues: Vec::new() };
+
+    // (samples, channels, sample_rate) per audio file, multi packs reuse files a lot
+    let mut decoded: HashMap<String, (Vec<f32>, u16, u32)> = HashMap::new();
+    for segment in &segments {
+        if !decoded.contains_key(&segment.audio_file) {
+            let audio_path = soundpack_dir.join(segment.audio_file.trim_start_matches("./"));
+            let audio = load_audio_file_samples(&audio_path.to_string_lossy()).map_err(|e|
+                format!("Failed to decode {}: {}", segment.audio_file, e)
+            )?;
+            decoded.insert(segment.audio_file.clone(), audio);
+        }
+    }
+
+    for segment in &segments {
+        let (samples, channels, sample_rate) = &decoded[&segment.audio_file];
+        let channels = (*channels).max(1) as usize;
+        let duration_ms = ((samples.len() / channels) as f32) * 1000.0 / (*sample_rate as f32);
+        let mut issue = |kind: TimingIssueKind, message: String, clamped_end_ms: Option<f32>| {
+            report.issues.push(TimingIssue {
+                key: segment.key.clone(),
+                index: segment.index,
+                kind,
+                message,
+                clamped_end_ms,
+            });
+        };
+
+        if segment.end_ms <= segment.start_ms || segment.start_ms < 0.0 {
+            issue(
+                TimingIssueKind::ZeroLength,
+                format!("Segment [{}, {}] has no length", segment.start_ms, segment.end_ms),
+                None
+            );
+            continue;
+        }
+        // Same 1 ms tolerance the player uses
+        if segment.end_ms > duration_ms + 1.0 {
+            let fixable = segment.start_ms + 1.0 < duration_ms;
+            issue(
+                TimingIssueKind::OutOfRange,
+                format!(
+                    "Segment [{}, {}] runs past the end of the audio ({:.1} ms)",
+                    segment.start_ms,
+                    segment.end_ms,
+                    duration_ms
+                ),
+                // Round down so the clamped end stays inside the audio
+                fixable.then(|| (duration_ms * 10.0).floor() / 10.0)
+            );
+            if !fixable {
+                continue;
+            }
+        }
+
+        let frame_at = |ms: f32| (((ms / 1000.0) * (*sample_rate as f32)) as usize) * channels;
+        let first = frame_at(segment.start_ms).min(samples.len());
+        let last = frame_at(segment.end_ms).min(samples.len());
+        let peak = samples[first..last].iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+        if 20.0 * (peak + 1e-9).log10() < SILENCE_THRESHOLD_DB {
+            issue(
+                TimingIssueKind::Silent,
+                format!("Segment [{}, {}] is silent", segment.start_ms, segment.end_ms),
+                None
+            );
+        }
+    }
+
+    // A key's keydown running into its own keyup plays the release click twice
+    for down in segments.iter().filter(|segment| segment.index == 0) {
+        let up = segments
+            .iter()
+            .find(|segment| segment.key == down.key && segment.index == 1);
+        if let Some(up) = up {
+            if down.audio_file == up.audio_file && down.start_ms < up.start_ms && down.end_ms > up.start_ms {
+                report.issues.push(TimingIssue {
+                    key: down.key.clone(),
+                    index: 0,
+                    kind: TimingIssueKind::Overlapping,
+                    message: format!(
+                        "Keydown [{}, {}] overlaps the keyup starting at {}",
+                        down.start_ms,
+                        down.end_ms,
+                        up.start_ms
+                    ),
+                    clamped_end_ms: Some(up.start_ms),
+                });
+            }
+        }
+    }
+
+    report.issues.sort_by(|a, b| a.key.cmp(&b.key).then(a.index.cmp(&b.index)));
+    Ok(report)
+}
+
+/// Apply the fixable parts of a timing report to the config, returns how many timings changed
+pub fn clamp_timings(config: &mut Value, report: &TimingReport) -> usize {
+    let is_v1 = config.get("definitions").is_none() && config.get("defs").is_none();
+    let mut changed = 0;
+
+    for issue in &report.issues {
+        let Some(end_ms) = issue.clamped_end_ms else {
+            continue;
+        };
+        let timing = if is_v1 {
+            config.get_mut("defines").and_then(|defines| defines.get_mut(&issue.key))
+        } else {
+            let definitions = if config.get("definitions").is_some() { "definitions" } else { "defs" };
+            config
+                .get_mut(definitions)
+                .and_then(|definitions| definitions.get_mut(&issue.key))
+                .and_then(|definition| definition.get_mut("timing"))
+                .and_then(|timing| timing.get_mut(issue.index))
+        };
+        let Some(timing) = timing.and_then(|timing| timing.as_array_mut()) else {
+            continue;
+        };
+        let start_ms = timing.first().and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+        // V1 stores [start, duration]
+        let value = if is_v1 { end_ms - start_ms } else { end_ms };
+        // Both fixes only ever shorten a segment
+        let current = timing.get(1).and_then(|v| v.as_f64()).unwrap_or(0.0) as f32;
+        if timing.len() >= 2 && value < current {
+            timing[1] = serde_json::json!(value);
+            changed += 1;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn clamp_issue(key: &str, index: usize, end_ms: f32) -> TimingIssue {
+        TimingIssue {
+            key: key.to_string(),
+            index,
+            kind: TimingIssueKind::OutOfRange,
+            message: String::new(),
+            clamped_end_ms: Some(end_ms),
+        }
+    }
+
+    #[test]
+    fn clamps_v2_and_v1_timings() {
+        let report = TimingReport {
+            segments_checked: 2,
+            issues: vec![clamp_issue("KeyA", 1, 500.0), clamp_issue("30", 0, 500.0)],
+        };
+
+        let mut v2 = json!({ "definitions": { "KeyA": { "timing": [[0.0, 40.0], [450.0, 600.0]] } } });
+        assert_eq!(clamp_timings(&mut v2, &report), 1);
+        assert_eq!(v2["definitions"]["KeyA"]["timing"], json!([[0.0, 40.0], [450.0, 500.0]]));
+
+        // V1 stores [start, duration]
+        let mut v1 = json!({ "defines": { "30": [450.0, 150.0] } });
+        assert_eq!(clamp_timings(&mut v1, &report), 1);
+        assert_eq!(v1["defines"]["30"], json!([450.0, 50.0]));
+    }
+
+    /// 100 ms of mono audio: 20 ms of silence, then a tone until the end
+    fn write_test_pack(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 48_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(dir.join("sound.wav"), spec).unwrap();
+        for i in 0..4_800 {
+            let sample = if i < 960 { 0 } else if i % 2 == 0 { 16_000 } else { -16_000 };
+            writer.write_sample(sample as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn checks_timings_against_the_audio() {
+        let dir = std::env::temp_dir().join(format!("mechaura-timing-test-{}", Uuid::new_v4()));
+        write_test_pack(&dir);
+        let config = json!({
+            "audio_file": "sound.wav",
+            "definitions": {
+                "KeyA": { "timing": [[20.0, 60.0], [50.0, 70.0]] }, // Keydown runs into the keyup
+                "KeyB": { "timing": [[80.0, 150.0]] }, // Past the end, clamped to the audio
+                "KeyC": { "timing": [[40.0, 40.0]] },
+                "KeyD": { "timing": [[0.0, 15.0]] },
+                "KeyE": { "timing": [[200.0, 300.0]] }, // Starts after the end, nothing to clamp
+                "KeyF": { "timing": [[30.0, 90.0]] }
+            }
+        });
+
+        let report = check_timings(&dir, &config).unwrap();
+        assert_eq!(report.segments_checked, 7);
+        let issues: Vec<(&str, TimingIssueKind, Option<f32>)> = report.issues
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.kind, issue.clamped_end_ms))
+            .collect();
+        assert_eq!(issues, vec![
+            ("KeyA", TimingIssueKind::Overlapping, Some(50.0)),
+            ("KeyB", TimingIssueKind::OutOfRange, Some(100.0)),
+            ("KeyC", TimingIssueKind::ZeroLength, None),
+            ("KeyD", TimingIssueKind::Silent, None),
+            ("KeyE", TimingIssueKind::OutOfRange, None),
+        ]);
+        assert_eq!(report.fixable_count(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
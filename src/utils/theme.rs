@@ -1,4 +1,5 @@
 use crate::state::themes::ThemesConfig;
+use crate::utils::theme_share::decode_theme;
 use dioxus::prelude::*;
 use once_cell::sync::Lazy;
 use std::rc::Rc;
@@ -41,7 +42,13 @@ pub fn use_themes() -> (Signal<ThemesConfig>, Rc<dyn Fn(Box<dyn FnOnce(&mut Them
     (themes, update_themes)
 }
 
-/// Get a reference to the global themes config (read-only)
-pub fn get_themes_config() -> ThemesConfig {
-    THEMES_CONFIG.lock().unwrap().clone()
+/// Import a shared theme straight into the global config, returns the new theme id
+pub fn import_shared_theme(input: &str) -> Result<String, String> {
+    let theme = decode_theme(input)?;
+    let mut config_guard = THEMES_CONFIG
+        .lock()
+        .map_err(|_| "Themes config is unavailable".to_string())?;
+    let id = config_guard.import_theme(theme)?;
+    config_guard.save()?;
+    Ok(id)
 }
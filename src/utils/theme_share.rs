@@ -0,0 +1,169 @@
+/// Theme sharing: one payload for links, files and the clipboard
+///
+/// A payload is a versioned JSON envelope around `CustomThemeData`, encoded as unpadded
+/// base64url so it fits in a `mechaura://import-theme/<payload>` link as is. `.mechaura-theme`
+/// files hold the same string. Imported CSS is reduced to plain declarations, because it is
+/// injected into the page inside a `[data-theme]` block.
+use crate::state::themes::CustomThemeData;
+use crate::utils::constants::APP_PROTOCOL_URL;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use serde::{ Deserialize, Serialize };
+
+pub const THEME_SHARE_VERSION: u32 = 1;
+pub const THEME_FILE_EXTENSION: &str = "mechaura-theme";
+const IMPORT_THEME_PREFIX: &str = "import-theme/";
+const MAX_PAYLOAD_SIZE: usize = 256 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ThemePayload {
+    version: u32,
+    theme: CustomThemeData,
+}
+
+/// Encode a theme as a base64url payload
+pub fn encode_theme(theme: &CustomThemeData) -> Result<String, String> {
+    let payload = ThemePayload {
+        version: THEME_SHARE_VERSION,
+        theme: CustomThemeData {
+            is_built_in: false,
+            ..theme.clone()
+        },
+    };
+    let json = serde_json
+        ::to_vec(&payload)
+        .map_err(|e| format!("Failed to serialize theme: {}", e))?;
+    Ok(URL_SAFE_NO_PAD.encode(json))
+}
+
+/// `mechaura://import-theme/<payload>` link for a theme
+pub fn theme_share_link(theme: &CustomThemeData) -> Result<String, String> {
+    Ok(format!("{}{}{}", APP_PROTOCOL_URL, IMPORT_THEME_PREFIX, encode_theme(theme)?))
+}
+
+/// Decode a payload, a share link or the contents of a theme file
+///
+/// The CSS of the result is already sanitized. Id and timestamps are the sender's, callers
+/// assign their own when saving.
+pub fn decode_theme(input: &str) -> Result<CustomThemeData, String> {
+    let input = input.trim();
+    let payload = input
+        .strip_prefix(APP_PROTOCOL_URL)
+        .map(|rest| rest.strip_prefix(IMPORT_THEME_PREFIX).unwrap_or(rest))
+        .unwrap_or(input)
+        .trim_end_matches('/')
+        .trim_end_matches('=');
+    if payload.is_empty() {
+        return Err("Theme data is empty".to_string());
+    }
+    if payload.len() > MAX_PAYLOAD_SIZE {
+        return Err("Theme data is too large".to_string());
+    }
+
+    let json = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|e| format!("Theme data is not valid base64url: {}", e))?;
+    let value: serde_json::Value = serde_json
+        ::from_slice(&json)
+        .map_err(|e| format!("Theme data is not valid JSON: {}", e))?;
+    let version = value
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .ok_or_else(|| "Theme data has no version".to_string())?;
+    if version > (THEME_SHARE_VERSION as u64) {
+        return Err(
+            format!("Theme was shared by a newer version of the app (format {})", version)
+        );
+    }
+
+    let payload: ThemePayload = serde_json
+        ::from_value(value)
+        .map_err(|e| format!("Invalid theme data: {}", e))?;
+    let mut theme = payload.theme;
+    if theme.name.trim().is_empty() {
+        return Err("Theme name cannot be empty".to_string());
+    }
+    theme.name = theme.name.trim().to_string();
+    theme.css = sanitize_theme_css(&theme.css);
+    theme.is_built_in = false;
+    Ok(theme)
+}
+
+/// Keep only `property: value` declarations that cannot leave the theme block or load anything
+pub fn sanitize_theme_css(css: &str) -> String {
+    // Drop comments first so they cannot hide a declaration boundary
+    let mut without_comments = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        without_comments.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    without_comments.push_str(rest);
+
+    const BLOCKED: [&str; 7] = ["url(", "image-set(", "expression(", "javascript:", "@import", "</", "\\"];
+    without_comments
+        .split(';')
+        .filter_map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            let property = property.trim();
+            let value = value.trim();
+            let valid_property =
+                !property.is_empty() &&
+                property.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            let lowercase = value.to_lowercase();
+            let valid_value =
+                !value.is_empty() &&
+                !value.contains(['{', '}', '<', '>', '`', '$', '@']) &&
+                !BLOCKED.iter().any(|blocked| lowercase.contains(blocked));
+            (valid_property && valid_value).then(|| format!("{}: {};", property, value))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn theme(css: &str) -> CustomThemeData {
+        CustomThemeData {
+            id: "id".to_string(),
+            name: "Ocean".to_string(),
+            description: String::new(),
+            css: css.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            is_built_in: false,
+        }
+    }
+
+    #[test]
+    fn round_trips_through_links_and_payloads() {
+        let original = theme("--color-primary: oklch(55% 0.3 240);");
+        let link = theme_share_link(&original).unwrap();
+        let payload = link.strip_prefix("mechaura://import-theme/").unwrap();
+        assert!(payload.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let decoded = decode_theme(&link).unwrap();
+        assert_eq!(decoded.name, "Ocean");
+        assert_eq!(decoded.css, original.css);
+        assert_eq!(decode_theme(&encode_theme(&original).unwrap()).unwrap(), decoded);
+
+        let newer = URL_SAFE_NO_PAD.encode(br#"{"version":99,"theme":{}}"#);
+        assert!(decode_theme(&newer).unwrap_err().contains("newer version"));
+        assert!(decode_theme("not base64!").is_err());
+    }
+
+    #[test]
+    fn strips_unsafe_css() {
+        let css =
+            "/* Colors */\n--color-primary: red;\n} body { display: none; }\n\
+            --bg: url(https://example.com/track.png);\n--radius-box: 0.5rem;\n\
+            --evil: </style><script>alert(1)</script>";
+        assert_eq!(sanitize_theme_css(css), "--color-primary: red;\n--radius-box: 0.5rem;");
+    }
+}